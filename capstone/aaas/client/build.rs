@@ -0,0 +1,24 @@
+use std::path::Path;
+use std::{env, fs};
+
+///generates `ERRORS` from the `AaasError` declaration, so the variants stay in code order
+fn main() {
+    let source_path = "../programs/aaas/src/error.rs";
+    println!("cargo:rerun-if-changed={source_path}");
+
+    let source = fs::read_to_string(source_path).expect("program error.rs");
+    let body = source
+        .split_once("pub enum AaasError {")
+        .and_then(|(_, rest)| rest.split_once('}'))
+        .map(|(body, _)| body)
+        .expect("AaasError enum");
+    let variants: String = body
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with("#[") && !line.starts_with("//"))
+        .map(|line| format!("    AaasError::{},\n", line.trim_end_matches(',')))
+        .collect();
+
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("errors.rs");
+    fs::write(out, format!("&[\n{variants}]\n")).unwrap();
+}
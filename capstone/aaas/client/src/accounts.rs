@@ -0,0 +1,53 @@
+use aaas::{
+    AaasConfig, CandidateAccount, Challenge, MilestoneProof, Profile, Proposal, Service,
+    Sponsorship, Validation,
+};
+use anchor_lang::{AccountDeserialize, AnchorDeserialize, Event, Result};
+
+///decodes raw account data, checking the anchor discriminator
+pub fn decode<T: AccountDeserialize>(mut data: &[u8]) -> Result<T> {
+    T::try_deserialize(&mut data)
+}
+
+pub fn decode_config(data: &[u8]) -> Result<AaasConfig> {
+    decode(data)
+}
+
+pub fn decode_proposal(data: &[u8]) -> Result<Proposal> {
+    decode(data)
+}
+
+pub fn decode_service(data: &[u8]) -> Result<Service> {
+    decode(data)
+}
+
+pub fn decode_challenge(data: &[u8]) -> Result<Challenge> {
+    decode(data)
+}
+
+pub fn decode_candidate(data: &[u8]) -> Result<CandidateAccount> {
+    decode(data)
+}
+
+pub fn decode_validation(data: &[u8]) -> Result<Validation> {
+    decode(data)
+}
+
+pub fn decode_milestone(data: &[u8]) -> Result<MilestoneProof> {
+    decode(data)
+}
+
+pub fn decode_profile(data: &[u8]) -> Result<Profile> {
+    decode(data)
+}
+
+pub fn decode_sponsorship(data: &[u8]) -> Result<Sponsorship> {
+    decode(data)
+}
+
+///decodes an emitted event, the logged program data or the self cpi data without its tag,
+///`None` when the discriminator is of another event
+pub fn decode_event<T: Event + AnchorDeserialize>(data: &[u8]) -> Option<T> {
+    let mut data = data.strip_prefix(T::DISCRIMINATOR)?;
+    T::deserialize(&mut data).ok()
+}
@@ -0,0 +1,23 @@
+use aaas::error::AaasError;
+use anchor_lang::error::ERROR_CODE_OFFSET;
+use anchor_lang::solana_program::program_error::ProgramError;
+
+///every program error in declaration order, generated from the `AaasError` declaration
+pub const ERRORS: &[AaasError] = include!(concat!(env!("OUT_DIR"), "/errors.rs"));
+
+///maps a custom program error code back to its `AaasError`
+pub fn from_code(code: u32) -> Option<AaasError> {
+    let index = code.checked_sub(ERROR_CODE_OFFSET)?;
+    ERRORS
+        .get(index as usize)
+        .copied()
+        .filter(|err| u32::from(*err) == code)
+}
+
+///extracts the `AaasError` from a failed instruction, if the program raised one
+pub fn from_program_error(err: &ProgramError) -> Option<AaasError> {
+    match err {
+        ProgramError::Custom(code) => from_code(*code),
+        _ => None,
+    }
+}
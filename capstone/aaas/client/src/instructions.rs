@@ -0,0 +1,1080 @@
+use aaas::{accounts, instruction, AdminAction, Vote};
+use anchor_lang::prelude::{AccountMeta, Pubkey};
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::sysvar;
+use anchor_lang::{system_program, InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::{self, get_associated_token_address_with_program_id};
+
+use crate::pda;
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: aaas::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+pub fn initialize(
+    admin: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    signers: Vec<Pubkey>,
+    threshold: u8,
+) -> Instruction {
+    build(
+        accounts::Initialize {
+            admin: *admin,
+            config: pda::config().0,
+            treasury: pda::treasury(admin, mint, token_program),
+            mint: *mint,
+            system_program: system_program::ID,
+            token_program: *token_program,
+            associated_token_program: associated_token::ID,
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority().0,
+            #[cfg(feature = "event-cpi")]
+            program: aaas::ID,
+        },
+        instruction::Initialize { signers, threshold },
+    )
+}
+
+///multisig actions are proposed once and approved by the other signers with [`approve_proposal`]
+pub fn create_proposal(proposer: &Pubkey, id: Pubkey, action: AdminAction) -> Instruction {
+    build(
+        accounts::CreateProposal {
+            proposer: *proposer,
+            config: pda::config().0,
+            proposal: pda::proposal(&id).0,
+            system_program: system_program::ID,
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority().0,
+            #[cfg(feature = "event-cpi")]
+            program: aaas::ID,
+        },
+        instruction::CreateProposal { id, action },
+    )
+}
+
+pub fn approve_proposal(signer: &Pubkey, id: &Pubkey) -> Instruction {
+    build(
+        accounts::ApproveProposal {
+            signer: *signer,
+            config: pda::config().0,
+            proposal: pda::proposal(id).0,
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority().0,
+            #[cfg(feature = "event-cpi")]
+            program: aaas::ID,
+        },
+        instruction::ApproveProposal {},
+    )
+}
+
+pub fn revoke_approval(signer: &Pubkey, id: &Pubkey) -> Instruction {
+    build(
+        accounts::RevokeApproval {
+            signer: *signer,
+            proposal: pda::proposal(id).0,
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority().0,
+            #[cfg(feature = "event-cpi")]
+            program: aaas::ID,
+        },
+        instruction::RevokeApproval {},
+    )
+}
+
+///the proposer can cancel anytime, anyone else once the proposal expired
+pub fn cancel_proposal(canceller: &Pubkey, id: &Pubkey, proposer: &Pubkey) -> Instruction {
+    build(
+        accounts::CancelProposal {
+            canceller: *canceller,
+            proposal: pda::proposal(id).0,
+            proposer: *proposer,
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority().0,
+            #[cfg(feature = "event-cpi")]
+            program: aaas::ID,
+        },
+        instruction::CancelProposal {},
+    )
+}
+
+///accounts of an executing instruction, `proposer` gets the proposal rent back
+pub fn execution(
+    executor: &Pubkey,
+    proposal_id: &Pubkey,
+    proposer: &Pubkey,
+) -> accounts::ExecuteProposal {
+    accounts::ExecuteProposal {
+        executor: *executor,
+        config: pda::config().0,
+        proposal: pda::proposal(proposal_id).0,
+        proposer: *proposer,
+    }
+}
+
+///executes an approved `AdminAction::CreateService` proposal, `id` has to match the proposed one
+pub fn initialize_service(
+    executor: &Pubkey,
+    proposal_id: &Pubkey,
+    proposer: &Pubkey,
+    id: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::InitService {
+            execution: execution(executor, proposal_id, proposer),
+            service: pda::service(id).0,
+            system_program: system_program::ID,
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority().0,
+            #[cfg(feature = "event-cpi")]
+            program: aaas::ID,
+        },
+        instruction::InitializeService { id: *id },
+    )
+}
+
+///executes an approved `AdminAction::UpdateServiceFee` proposal, the new fees are read from it
+pub fn update_service_fee(
+    executor: &Pubkey,
+    proposal_id: &Pubkey,
+    proposer: &Pubkey,
+    service_id: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::UpdateServiceFee {
+            execution: execution(executor, proposal_id, proposer),
+            service: pda::service(service_id).0,
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority().0,
+            #[cfg(feature = "event-cpi")]
+            program: aaas::ID,
+        },
+        instruction::UpdateServiceFee {},
+    )
+}
+
+pub fn update_service_limits(
+    executor: &Pubkey,
+    proposal_id: &Pubkey,
+    proposer: &Pubkey,
+    service_id: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::UpdateServiceLimits {
+            execution: execution(executor, proposal_id, proposer),
+            service: pda::service(service_id).0,
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority().0,
+            #[cfg(feature = "event-cpi")]
+            program: aaas::ID,
+        },
+        instruction::UpdateServiceLimits {},
+    )
+}
+
+pub fn set_service_mints(
+    executor: &Pubkey,
+    proposal_id: &Pubkey,
+    proposer: &Pubkey,
+    service_id: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::SetServiceMints {
+            execution: execution(executor, proposal_id, proposer),
+            service: pda::service(service_id).0,
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority().0,
+            #[cfg(feature = "event-cpi")]
+            program: aaas::ID,
+        },
+        instruction::SetServiceMints {},
+    )
+}
+
+pub fn set_service_attestor(
+    executor: &Pubkey,
+    proposal_id: &Pubkey,
+    proposer: &Pubkey,
+    service_id: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::SetServiceAttestor {
+            execution: execution(executor, proposal_id, proposer),
+            service: pda::service(service_id).0,
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority().0,
+            #[cfg(feature = "event-cpi")]
+            program: aaas::ID,
+        },
+        instruction::SetServiceAttestor {},
+    )
+}
+
+fn pause_accounts(
+    executor: &Pubkey,
+    proposal_id: &Pubkey,
+    proposer: &Pubkey,
+    service_id: &Pubkey,
+) -> accounts::PauseService {
+    accounts::PauseService {
+        execution: execution(executor, proposal_id, proposer),
+        service: pda::service(service_id).0,
+        #[cfg(feature = "event-cpi")]
+        event_authority: pda::event_authority().0,
+        #[cfg(feature = "event-cpi")]
+        program: aaas::ID,
+    }
+}
+
+pub fn pause_service(
+    executor: &Pubkey,
+    proposal_id: &Pubkey,
+    proposer: &Pubkey,
+    service_id: &Pubkey,
+) -> Instruction {
+    build(
+        pause_accounts(executor, proposal_id, proposer, service_id),
+        instruction::PauseService {},
+    )
+}
+
+///takes the accounts of [`pause_service`], with an approved `AdminAction::ResumeService` proposal
+pub fn resume_service(
+    executor: &Pubkey,
+    proposal_id: &Pubkey,
+    proposer: &Pubkey,
+    service_id: &Pubkey,
+) -> Instruction {
+    build(
+        pause_accounts(executor, proposal_id, proposer, service_id),
+        instruction::ResumeService {},
+    )
+}
+
+///the service rent goes to the proposer, once every challenge of the service is closed
+pub fn close_service(
+    executor: &Pubkey,
+    proposal_id: &Pubkey,
+    proposer: &Pubkey,
+    service_id: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::CloseService {
+            execution: execution(executor, proposal_id, proposer),
+            service: pda::service(service_id).0,
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority().0,
+            #[cfg(feature = "event-cpi")]
+            program: aaas::ID,
+        },
+        instruction::CloseService {},
+    )
+}
+
+///the new signers and threshold are read from the proposal
+pub fn rotate_signers(executor: &Pubkey, proposal_id: &Pubkey, proposer: &Pubkey) -> Instruction {
+    build(
+        accounts::RotateSigners {
+            execution: execution(executor, proposal_id, proposer),
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority().0,
+            #[cfg(feature = "event-cpi")]
+            program: aaas::ID,
+        },
+        instruction::RotateSigners {},
+    )
+}
+
+pub fn add_signer(executor: &Pubkey, proposal_id: &Pubkey, proposer: &Pubkey) -> Instruction {
+    build(
+        accounts::AddSigner {
+            execution: execution(executor, proposal_id, proposer),
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority().0,
+            #[cfg(feature = "event-cpi")]
+            program: aaas::ID,
+        },
+        instruction::AddSigner {},
+    )
+}
+
+pub fn remove_signer(executor: &Pubkey, proposal_id: &Pubkey, proposer: &Pubkey) -> Instruction {
+    build(
+        accounts::RemoveSigner {
+            execution: execution(executor, proposal_id, proposer),
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority().0,
+            #[cfg(feature = "event-cpi")]
+            program: aaas::ID,
+        },
+        instruction::RemoveSigner {},
+    )
+}
+
+pub fn set_threshold(executor: &Pubkey, proposal_id: &Pubkey, proposer: &Pubkey) -> Instruction {
+    build(
+        accounts::SetThreshold {
+            execution: execution(executor, proposal_id, proposer),
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority().0,
+            #[cfg(feature = "event-cpi")]
+            program: aaas::ID,
+        },
+        instruction::SetThreshold {},
+    )
+}
+
+///`new_admin` has to match the proposed one, its treasury is created for `mint` when missing
+pub fn transfer_admin(
+    executor: &Pubkey,
+    proposal_id: &Pubkey,
+    proposer: &Pubkey,
+    new_admin: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::TransferAdmin {
+            execution: execution(executor, proposal_id, proposer),
+            new_admin: *new_admin,
+            new_treasury: pda::treasury(new_admin, mint, token_program),
+            mint: *mint,
+            system_program: system_program::ID,
+            token_program: *token_program,
+            associated_token_program: associated_token::ID,
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority().0,
+            #[cfg(feature = "event-cpi")]
+            program: aaas::ID,
+        },
+        instruction::TransferAdmin {},
+    )
+}
+
+///`args.id` picks the challenge address under `service`
+pub fn create_challenge(
+    creator: &Pubkey,
+    service: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    args: instruction::CreateChallenge,
+) -> Instruction {
+    let challenge = pda::challenge(service, &args.id).0;
+    build(
+        accounts::CreateChallenge {
+            creator: *creator,
+            service: *service,
+            challenge,
+            mint: *mint,
+            vault: pda::vault(&challenge, mint, token_program),
+            system_program: system_program::ID,
+            token_program: *token_program,
+            associated_token_program: associated_token::ID,
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority().0,
+            #[cfg(feature = "event-cpi")]
+            program: aaas::ID,
+        },
+        args,
+    )
+}
+
+pub fn init_profile(owner: &Pubkey) -> Instruction {
+    build(
+        accounts::InitProfile {
+            owner: *owner,
+            profile: pda::profile(owner).0,
+            system_program: system_program::ID,
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority().0,
+            #[cfg(feature = "event-cpi")]
+            program: aaas::ID,
+        },
+        instruction::InitProfile {},
+    )
+}
+
+///`with_profile` passes the candidate's profile, required once they have one
+#[allow(clippy::too_many_arguments)]
+pub fn join_challenge(
+    candidate: &Pubkey,
+    service: &Pubkey,
+    challenge: &Pubkey,
+    mint: &Pubkey,
+    candidate_ata: &Pubkey,
+    token_program: &Pubkey,
+    with_profile: bool,
+    stake: u64,
+    allowlist_proof: Vec<[u8; 32]>,
+) -> Instruction {
+    build(
+        accounts::JoinChallenge {
+            candidate: *candidate,
+            challenge: *challenge,
+            service: *service,
+            candidate_account: pda::candidate(service, challenge, candidate).0,
+            profile: with_profile.then(|| pda::profile(candidate).0),
+            mint: *mint,
+            vault: pda::vault(challenge, mint, token_program),
+            candidate_ata: *candidate_ata,
+            system_program: system_program::ID,
+            token_program: *token_program,
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority().0,
+            #[cfg(feature = "event-cpi")]
+            program: aaas::ID,
+        },
+        instruction::JoinChallenge {
+            stake,
+            allowlist_proof,
+        },
+    )
+}
+
+///`with_profile` passes the candidate's profile, required when it joined with it
+#[allow(clippy::too_many_arguments)]
+pub fn exit_challenge(
+    candidate: &Pubkey,
+    service: &Pubkey,
+    challenge: &Pubkey,
+    mint: &Pubkey,
+    candidate_ata: &Pubkey,
+    token_program: &Pubkey,
+    with_profile: bool,
+) -> Instruction {
+    build(
+        accounts::ExitChallenge {
+            candidate: *candidate,
+            challenge: *challenge,
+            candidate_account: pda::candidate(service, challenge, candidate).0,
+            profile: with_profile.then(|| pda::profile(candidate).0),
+            mint: *mint,
+            vault: pda::vault(challenge, mint, token_program),
+            candidate_ata: *candidate_ata,
+            system_program: system_program::ID,
+            token_program: *token_program,
+            associated_token_program: associated_token::ID,
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority().0,
+            #[cfg(feature = "event-cpi")]
+            program: aaas::ID,
+        },
+        instruction::ExitChallenge {},
+    )
+}
+
+///the creator cancels before the start, the multisig uses [`emergency_cancel_challenge`]
+pub fn cancel_challenge(creator: &Pubkey, challenge: &Pubkey) -> Instruction {
+    build(
+        accounts::CancelChallenge {
+            creator: *creator,
+            challenge: *challenge,
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority().0,
+            #[cfg(feature = "event-cpi")]
+            program: aaas::ID,
+        },
+        instruction::CancelChallenge {},
+    )
+}
+
+///executes an approved `AdminAction::CancelChallenge` proposal
+pub fn emergency_cancel_challenge(
+    executor: &Pubkey,
+    proposal_id: &Pubkey,
+    proposer: &Pubkey,
+    challenge: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::EmergencyCancelChallenge {
+            execution: execution(executor, proposal_id, proposer),
+            challenge: *challenge,
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority().0,
+            #[cfg(feature = "event-cpi")]
+            program: aaas::ID,
+        },
+        instruction::EmergencyCancelChallenge {},
+    )
+}
+
+pub fn sponsor_challenge(
+    sponsor: &Pubkey,
+    challenge: &Pubkey,
+    mint: &Pubkey,
+    sponsor_ata: &Pubkey,
+    token_program: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    build(
+        accounts::SponsorChallenge {
+            sponsor: *sponsor,
+            challenge: *challenge,
+            sponsorship: pda::sponsorship(challenge, sponsor).0,
+            mint: *mint,
+            vault: pda::vault(challenge, mint, token_program),
+            sponsor_ata: *sponsor_ata,
+            system_program: system_program::ID,
+            token_program: *token_program,
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority().0,
+            #[cfg(feature = "event-cpi")]
+            program: aaas::ID,
+        },
+        instruction::SponsorChallenge { amount },
+    )
+}
+
+pub fn submit_proof(
+    candidate: &Pubkey,
+    service: &Pubkey,
+    challenge: &Pubkey,
+    proof_hash: [u8; 32],
+    proof: Option<String>,
+) -> Instruction {
+    build(
+        accounts::SubmitProof {
+            candidate: *candidate,
+            challenge: *challenge,
+            candidate_account: pda::candidate(service, challenge, candidate).0,
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority().0,
+            #[cfg(feature = "event-cpi")]
+            program: aaas::ID,
+        },
+        instruction::SubmitProof { proof_hash, proof },
+    )
+}
+
+///`candidate` is the wallet whose proof is voted on, not its candidate account
+#[allow(clippy::too_many_arguments)]
+pub fn validate_proof(
+    validator: &Pubkey,
+    service: &Pubkey,
+    challenge: &Pubkey,
+    candidate: &Pubkey,
+    with_profile: bool,
+    vote: Vote,
+    proof_hash: [u8; 32],
+) -> Instruction {
+    let candidate_account = pda::candidate(service, challenge, candidate).0;
+    build(
+        accounts::ValidateProof {
+            validator: *validator,
+            challenge: *challenge,
+            validator_account: pda::candidate(service, challenge, validator).0,
+            profile: with_profile.then(|| pda::profile(validator).0),
+            candidate_account,
+            validation: pda::validation(service, challenge, &candidate_account, validator).0,
+            system_program: system_program::ID,
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority().0,
+            #[cfg(feature = "event-cpi")]
+            program: aaas::ID,
+        },
+        instruction::ValidateProof { vote, proof_hash },
+    )
+}
+
+///`index` picks the milestone, the proof address is derived from it
+pub fn submit_milestone(
+    candidate: &Pubkey,
+    service: &Pubkey,
+    challenge: &Pubkey,
+    index: u8,
+    proof_hash: [u8; 32],
+    proof: Option<String>,
+) -> Instruction {
+    let candidate_account = pda::candidate(service, challenge, candidate).0;
+    build(
+        accounts::SubmitMilestone {
+            candidate: *candidate,
+            challenge: *challenge,
+            candidate_account,
+            milestone_proof: pda::milestone(challenge, &candidate_account, index).0,
+            system_program: system_program::ID,
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority().0,
+            #[cfg(feature = "event-cpi")]
+            program: aaas::ID,
+        },
+        instruction::SubmitMilestone {
+            index,
+            proof_hash,
+            proof,
+        },
+    )
+}
+
+///`candidate` is the wallet whose milestone proof is voted on
+#[allow(clippy::too_many_arguments)]
+pub fn validate_milestone(
+    validator: &Pubkey,
+    service: &Pubkey,
+    challenge: &Pubkey,
+    candidate: &Pubkey,
+    index: u8,
+    with_profile: bool,
+    vote: Vote,
+    proof_hash: [u8; 32],
+) -> Instruction {
+    let candidate_account = pda::candidate(service, challenge, candidate).0;
+    let milestone_proof = pda::milestone(challenge, &candidate_account, index).0;
+    build(
+        accounts::ValidateMilestone {
+            validator: *validator,
+            challenge: *challenge,
+            validator_account: pda::candidate(service, challenge, validator).0,
+            profile: with_profile.then(|| pda::profile(validator).0),
+            candidate_account,
+            milestone_proof,
+            validation: pda::validation(service, challenge, &milestone_proof, validator).0,
+            system_program: system_program::ID,
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority().0,
+            #[cfg(feature = "event-cpi")]
+            program: aaas::ID,
+        },
+        instruction::ValidateMilestone { vote, proof_hash },
+    )
+}
+
+pub fn finalize_milestone(
+    cranker: &Pubkey,
+    service: &Pubkey,
+    challenge: &Pubkey,
+    candidate: &Pubkey,
+    index: u8,
+) -> Instruction {
+    let candidate_account = pda::candidate(service, challenge, candidate).0;
+    build(
+        accounts::FinalizeMilestone {
+            cranker: *cranker,
+            challenge: *challenge,
+            candidate_account,
+            milestone_proof: pda::milestone(challenge, &candidate_account, index).0,
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority().0,
+            #[cfg(feature = "event-cpi")]
+            program: aaas::ID,
+        },
+        instruction::FinalizeMilestone {},
+    )
+}
+
+///has to follow the ed25519 program instruction verifying the attestor's signature of
+///challenge || candidate || passed
+pub fn attest_result(
+    submitter: &Pubkey,
+    service: &Pubkey,
+    challenge: &Pubkey,
+    candidate: &Pubkey,
+    passed: bool,
+) -> Instruction {
+    build(
+        accounts::AttestResult {
+            submitter: *submitter,
+            challenge: *challenge,
+            candidate_account: pda::candidate(service, challenge, candidate).0,
+            instruction_sysvar: sysvar::instructions::ID,
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority().0,
+            #[cfg(feature = "event-cpi")]
+            program: aaas::ID,
+        },
+        instruction::AttestResult { passed },
+    )
+}
+
+///`commitment` is [`Vote::commitment`] of the hidden vote, revealed later with [`reveal_vote`]
+pub fn commit_vote(
+    validator: &Pubkey,
+    service: &Pubkey,
+    challenge: &Pubkey,
+    candidate: &Pubkey,
+    commitment: [u8; 32],
+    proof_hash: [u8; 32],
+) -> Instruction {
+    let candidate_account = pda::candidate(service, challenge, candidate).0;
+    build(
+        accounts::CommitVote {
+            validator: *validator,
+            challenge: *challenge,
+            validator_account: pda::candidate(service, challenge, validator).0,
+            candidate_account,
+            validation: pda::validation(service, challenge, &candidate_account, validator).0,
+            system_program: system_program::ID,
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority().0,
+            #[cfg(feature = "event-cpi")]
+            program: aaas::ID,
+        },
+        instruction::CommitVote {
+            commitment,
+            proof_hash,
+        },
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn reveal_vote(
+    validator: &Pubkey,
+    service: &Pubkey,
+    challenge: &Pubkey,
+    candidate: &Pubkey,
+    with_profile: bool,
+    vote: Vote,
+    salt: [u8; 32],
+) -> Instruction {
+    let candidate_account = pda::candidate(service, challenge, candidate).0;
+    build(
+        accounts::RevealVote {
+            validator: *validator,
+            challenge: *challenge,
+            validator_account: pda::candidate(service, challenge, validator).0,
+            profile: with_profile.then(|| pda::profile(validator).0),
+            candidate_account,
+            validation: pda::validation(service, challenge, &candidate_account, validator).0,
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority().0,
+            #[cfg(feature = "event-cpi")]
+            program: aaas::ID,
+        },
+        instruction::RevealVote { vote, salt },
+    )
+}
+
+///candidate accounts are passed as writable remaining accounts, batch them to stay within the
+///transaction size and compute limits
+pub fn finalize_challenge(
+    cranker: &Pubkey,
+    challenge: &Pubkey,
+    candidate_accounts: &[Pubkey],
+) -> Instruction {
+    let mut ix = build(
+        accounts::FinalizeChallenge {
+            cranker: *cranker,
+            challenge: *challenge,
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority().0,
+            #[cfg(feature = "event-cpi")]
+            program: aaas::ID,
+        },
+        instruction::FinalizeChallenge {},
+    );
+    ix.accounts.extend(
+        candidate_accounts
+            .iter()
+            .map(|candidate_account| AccountMeta::new(*candidate_account, false)),
+    );
+    ix
+}
+
+///`with_profile` passes the validator's profile, required when it joined with it
+pub fn tally_vote(
+    cranker: &Pubkey,
+    service: &Pubkey,
+    challenge: &Pubkey,
+    candidate: &Pubkey,
+    validator: &Pubkey,
+    with_profile: bool,
+) -> Instruction {
+    let candidate_account = pda::candidate(service, challenge, candidate).0;
+    build(
+        accounts::TallyVote {
+            cranker: *cranker,
+            challenge: *challenge,
+            candidate_account,
+            validator_account: pda::candidate(service, challenge, validator).0,
+            validation: pda::validation(service, challenge, &candidate_account, validator).0,
+            profile: with_profile.then(|| pda::profile(validator).0),
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority().0,
+            #[cfg(feature = "event-cpi")]
+            program: aaas::ID,
+        },
+        instruction::TallyVote {},
+    )
+}
+
+pub fn claim_validator_reward(
+    validator: &Pubkey,
+    service: &Pubkey,
+    challenge: &Pubkey,
+    mint: &Pubkey,
+    validator_ata: &Pubkey,
+    token_program: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::ClaimValidatorReward {
+            validator: *validator,
+            validator_account: pda::candidate(service, challenge, validator).0,
+            challenge: *challenge,
+            mint: *mint,
+            vault: pda::vault(challenge, mint, token_program),
+            validator_ata: *validator_ata,
+            system_program: system_program::ID,
+            token_program: *token_program,
+            associated_token_program: associated_token::ID,
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority().0,
+            #[cfg(feature = "event-cpi")]
+            program: aaas::ID,
+        },
+        instruction::ClaimValidatorReward {},
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn withdraw_reward(
+    winner: &Pubkey,
+    admin: &Pubkey,
+    service: &Pubkey,
+    challenge: &Pubkey,
+    mint: &Pubkey,
+    winner_ata: &Pubkey,
+    token_program: &Pubkey,
+    with_profile: bool,
+) -> Instruction {
+    build(
+        accounts::WithdrawReward {
+            winner: *winner,
+            winner_account: pda::candidate(service, challenge, winner).0,
+            profile: with_profile.then(|| pda::profile(winner).0),
+            config: pda::config().0,
+            admin: *admin,
+            challenge: *challenge,
+            treasury: pda::treasury(admin, mint, token_program),
+            mint: *mint,
+            vault: pda::vault(challenge, mint, token_program),
+            winner_ata: *winner_ata,
+            system_program: system_program::ID,
+            token_program: *token_program,
+            associated_token_program: associated_token::ID,
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority().0,
+            #[cfg(feature = "event-cpi")]
+            program: aaas::ID,
+        },
+        instruction::WithdrawReward {},
+    )
+}
+
+///`service_id` signs for the service, so badges can be claimed once it's closed
+pub fn claim_badge(winner: &Pubkey, service_id: &Pubkey, challenge: &Pubkey) -> Instruction {
+    let service = pda::service(service_id).0;
+    let badge_mint = pda::badge(challenge, winner).0;
+    build(
+        accounts::ClaimBadge {
+            winner: *winner,
+            service,
+            challenge: *challenge,
+            winner_account: pda::candidate(&service, challenge, winner).0,
+            badge_mint,
+            collection: pda::collection(&service).0,
+            badge_ata: get_associated_token_address_with_program_id(
+                winner,
+                &badge_mint,
+                &anchor_spl::token_2022::ID,
+            ),
+            system_program: system_program::ID,
+            token_program: anchor_spl::token_2022::ID,
+            associated_token_program: associated_token::ID,
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority().0,
+            #[cfg(feature = "event-cpi")]
+            program: aaas::ID,
+        },
+        instruction::ClaimBadge {
+            service_id: *service_id,
+        },
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn refund_stake(
+    candidate: &Pubkey,
+    admin: &Pubkey,
+    service: &Pubkey,
+    challenge: &Pubkey,
+    mint: &Pubkey,
+    candidate_ata: &Pubkey,
+    token_program: &Pubkey,
+    with_profile: bool,
+) -> Instruction {
+    build(
+        accounts::RefundStake {
+            candidate: *candidate,
+            candidate_account: pda::candidate(service, challenge, candidate).0,
+            profile: with_profile.then(|| pda::profile(candidate).0),
+            config: pda::config().0,
+            admin: *admin,
+            challenge: *challenge,
+            treasury: pda::treasury(admin, mint, token_program),
+            mint: *mint,
+            vault: pda::vault(challenge, mint, token_program),
+            candidate_ata: *candidate_ata,
+            system_program: system_program::ID,
+            token_program: *token_program,
+            associated_token_program: associated_token::ID,
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority().0,
+            #[cfg(feature = "event-cpi")]
+            program: aaas::ID,
+        },
+        instruction::RefundStake {},
+    )
+}
+
+pub fn refund_sponsorship(
+    sponsor: &Pubkey,
+    challenge: &Pubkey,
+    mint: &Pubkey,
+    sponsor_ata: &Pubkey,
+    token_program: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::RefundSponsorship {
+            sponsor: *sponsor,
+            sponsorship: pda::sponsorship(challenge, sponsor).0,
+            challenge: *challenge,
+            mint: *mint,
+            vault: pda::vault(challenge, mint, token_program),
+            sponsor_ata: *sponsor_ata,
+            token_program: *token_program,
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority().0,
+            #[cfg(feature = "event-cpi")]
+            program: aaas::ID,
+        },
+        instruction::RefundSponsorship {},
+    )
+}
+
+///`target` is the candidate account for proof votes, or the milestone proof for milestone votes,
+///the rent goes back to the validator
+pub fn close_validation(
+    closer: &Pubkey,
+    service: &Pubkey,
+    challenge: &Pubkey,
+    target: &Pubkey,
+    validator: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::CloseValidation {
+            closer: *closer,
+            validator: *validator,
+            challenge: *challenge,
+            candidate_account: *target,
+            validation: pda::validation(service, challenge, target, validator).0,
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority().0,
+            #[cfg(feature = "event-cpi")]
+            program: aaas::ID,
+        },
+        instruction::CloseValidation {},
+    )
+}
+
+///`with_profile` passes the candidate's profile, the rent goes back to the candidate
+pub fn close_candidate(
+    closer: &Pubkey,
+    service: &Pubkey,
+    challenge: &Pubkey,
+    candidate: &Pubkey,
+    with_profile: bool,
+) -> Instruction {
+    build(
+        accounts::CloseCandidate {
+            closer: *closer,
+            candidate: *candidate,
+            challenge: *challenge,
+            candidate_account: pda::candidate(service, challenge, candidate).0,
+            profile: with_profile.then(|| pda::profile(candidate).0),
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority().0,
+            #[cfg(feature = "event-cpi")]
+            program: aaas::ID,
+        },
+        instruction::CloseCandidate {},
+    )
+}
+
+pub fn close_sponsorship(closer: &Pubkey, sponsor: &Pubkey, challenge: &Pubkey) -> Instruction {
+    build(
+        accounts::CloseSponsorship {
+            closer: *closer,
+            sponsor: *sponsor,
+            challenge: *challenge,
+            sponsorship: pda::sponsorship(challenge, sponsor).0,
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority().0,
+            #[cfg(feature = "event-cpi")]
+            program: aaas::ID,
+        },
+        instruction::CloseSponsorship {},
+    )
+}
+
+///the rent goes back to the candidate, even once its candidate account is closed
+pub fn close_milestone(
+    closer: &Pubkey,
+    service: &Pubkey,
+    challenge: &Pubkey,
+    candidate: &Pubkey,
+    index: u8,
+) -> Instruction {
+    let candidate_account = pda::candidate(service, challenge, candidate).0;
+    build(
+        accounts::CloseMilestone {
+            closer: *closer,
+            candidate: *candidate,
+            challenge: *challenge,
+            candidate_account,
+            milestone_proof: pda::milestone(challenge, &candidate_account, index).0,
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority().0,
+            #[cfg(feature = "event-cpi")]
+            program: aaas::ID,
+        },
+        instruction::CloseMilestone { index },
+    )
+}
+
+///closed last, the vault leftovers are swept to the admin treasury and the rent goes to `creator`
+#[allow(clippy::too_many_arguments)]
+pub fn close_challenge(
+    closer: &Pubkey,
+    creator: &Pubkey,
+    admin: &Pubkey,
+    service: &Pubkey,
+    challenge: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::CloseChallenge {
+            closer: *closer,
+            creator: *creator,
+            challenge: *challenge,
+            service: *service,
+            config: pda::config().0,
+            admin: *admin,
+            treasury: pda::treasury(admin, mint, token_program),
+            mint: *mint,
+            vault: pda::vault(challenge, mint, token_program),
+            system_program: system_program::ID,
+            token_program: *token_program,
+            associated_token_program: associated_token::ID,
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority().0,
+            #[cfg(feature = "event-cpi")]
+            program: aaas::ID,
+        },
+        instruction::CloseChallenge {},
+    )
+}
@@ -0,0 +1,11 @@
+//! typed helpers for building and decoding aaas transactions off-chain
+
+pub mod accounts;
+pub mod error;
+pub mod instructions;
+pub mod pda;
+
+pub use aaas::ID as PROGRAM_ID;
+pub use accounts::*;
+pub use error::*;
+pub use instructions::*;
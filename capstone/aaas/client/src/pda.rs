@@ -0,0 +1,90 @@
+use aaas::constants::*;
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+
+///every helper returns the address with its bump, mirroring the seeds of the program
+fn find(seeds: &[&[u8]]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(seeds, &aaas::ID)
+}
+
+pub fn config() -> (Pubkey, u8) {
+    find(&[CONFIG_SEED])
+}
+
+pub fn proposal(id: &Pubkey) -> (Pubkey, u8) {
+    find(&[PROPOSAL_SEED, id.as_ref()])
+}
+
+pub fn service(id: &Pubkey) -> (Pubkey, u8) {
+    find(&[SERVICE_SEED, id.as_ref()])
+}
+
+pub fn challenge(service: &Pubkey, id: &Pubkey) -> (Pubkey, u8) {
+    find(&[CHALLENGE_SEED, service.as_ref(), id.as_ref()])
+}
+
+pub fn candidate(service: &Pubkey, challenge: &Pubkey, candidate: &Pubkey) -> (Pubkey, u8) {
+    find(&[
+        CANDIDATE_SEED,
+        service.as_ref(),
+        challenge.as_ref(),
+        candidate.as_ref(),
+    ])
+}
+
+///`target` is the candidate account for proof votes, or the milestone proof for milestone votes
+pub fn validation(
+    service: &Pubkey,
+    challenge: &Pubkey,
+    target: &Pubkey,
+    validator: &Pubkey,
+) -> (Pubkey, u8) {
+    find(&[
+        VALIDATE_SEED,
+        service.as_ref(),
+        challenge.as_ref(),
+        target.as_ref(),
+        validator.as_ref(),
+    ])
+}
+
+pub fn milestone(challenge: &Pubkey, candidate_account: &Pubkey, index: u8) -> (Pubkey, u8) {
+    find(&[
+        MILESTONE_SEED,
+        challenge.as_ref(),
+        candidate_account.as_ref(),
+        &[index],
+    ])
+}
+
+pub fn profile(owner: &Pubkey) -> (Pubkey, u8) {
+    find(&[PROFILE_SEED, owner.as_ref()])
+}
+
+pub fn sponsorship(challenge: &Pubkey, sponsor: &Pubkey) -> (Pubkey, u8) {
+    find(&[SPONSORSHIP_SEED, challenge.as_ref(), sponsor.as_ref()])
+}
+
+pub fn badge(challenge: &Pubkey, winner: &Pubkey) -> (Pubkey, u8) {
+    find(&[BADGE_SEED, challenge.as_ref(), winner.as_ref()])
+}
+
+///token group of the service badges
+pub fn collection(service: &Pubkey) -> (Pubkey, u8) {
+    find(&[COLLECTION_SEED, service.as_ref()])
+}
+
+///signs the self-cpi of events when built with `event-cpi`
+pub fn event_authority() -> (Pubkey, u8) {
+    find(&[b"__event_authority"])
+}
+
+///challenge vault, the challenge's associated token account
+pub fn vault(challenge: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    get_associated_token_address_with_program_id(challenge, mint, token_program)
+}
+
+///admin treasury, the admin's associated token account
+pub fn treasury(admin: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    get_associated_token_address_with_program_id(admin, mint, token_program)
+}
@@ -0,0 +1,35 @@
+use aaas::error::AaasError;
+use aaas_client::{from_code, from_program_error, ERRORS};
+use anchor_lang::error::ERROR_CODE_OFFSET;
+use anchor_lang::solana_program::program_error::ProgramError;
+
+///every `AaasError` variant carries a message
+#[test]
+fn errors_cover_every_declared_variant() {
+    let source = include_str!("../../programs/aaas/src/error.rs");
+    assert_eq!(ERRORS.len(), source.matches("#[msg(").count());
+}
+
+#[test]
+fn errors_are_listed_by_code() {
+    for (index, err) in ERRORS.iter().enumerate() {
+        let code = ERROR_CODE_OFFSET + index as u32;
+        assert_eq!(u32::from(*err), code, "{} is out of order", err.name());
+        assert_eq!(from_code(code).map(u32::from), Some(code));
+        assert_eq!(
+            from_program_error(&ProgramError::Custom(code)).map(u32::from),
+            Some(code)
+        );
+    }
+}
+
+#[test]
+fn unknown_codes_map_to_none() {
+    assert!(from_code(0).is_none());
+    assert!(from_code(ERROR_CODE_OFFSET - 1).is_none());
+    assert!(from_code(ERROR_CODE_OFFSET + ERRORS.len() as u32).is_none());
+    assert!(from_program_error(&ProgramError::InvalidArgument).is_none());
+    assert!(
+        from_program_error(&ProgramError::Custom(u32::from(AaasError::InvalidMint) - 1)).is_none()
+    );
+}
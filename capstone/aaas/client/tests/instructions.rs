@@ -0,0 +1,302 @@
+use aaas::{instruction, Vote};
+use aaas_client::pda;
+use anchor_lang::prelude::{AccountMeta, Pubkey};
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::sysvar;
+use anchor_lang::{system_program, AnchorDeserialize, Discriminator};
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anchor_spl::{token, token_2022};
+
+///the instruction targets the program, and its data is the discriminator and the args
+fn args<T: Discriminator + AnchorDeserialize>(ix: &Instruction) -> T {
+    assert_eq!(ix.program_id, aaas::ID);
+    let data = ix
+        .data
+        .strip_prefix(T::DISCRIMINATOR)
+        .expect("instruction discriminator");
+    T::try_from_slice(data).unwrap()
+}
+
+///accounts of the instruction, without the event-cpi ones which are checked apart
+fn metas(ix: &Instruction) -> &[AccountMeta] {
+    #[cfg(feature = "event-cpi")]
+    {
+        let (metas, event_cpi) = ix.accounts.split_at(ix.accounts.len() - 2);
+        assert_eq!(
+            event_cpi,
+            [
+                AccountMeta::new_readonly(pda::event_authority().0, false),
+                AccountMeta::new_readonly(aaas::ID, false),
+            ]
+        );
+        metas
+    }
+    #[cfg(not(feature = "event-cpi"))]
+    &ix.accounts
+}
+
+#[test]
+fn initialize_builds_the_config_and_treasury() {
+    let admin = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    let signers = vec![Pubkey::new_unique(), Pubkey::new_unique()];
+    let ix = aaas_client::initialize(&admin, &mint, &token::ID, signers.clone(), 2);
+
+    let data: instruction::Initialize = args(&ix);
+    assert_eq!(data.signers, signers);
+    assert_eq!(data.threshold, 2);
+    assert_eq!(
+        metas(&ix),
+        [
+            AccountMeta::new(admin, true),
+            AccountMeta::new(pda::config().0, false),
+            AccountMeta::new(pda::treasury(&admin, &mint, &token::ID), false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(token::ID, false),
+            AccountMeta::new_readonly(anchor_spl::associated_token::ID, false),
+        ]
+    );
+}
+
+#[test]
+fn executions_start_with_the_proposal_accounts() {
+    let executor = Pubkey::new_unique();
+    let proposal_id = Pubkey::new_unique();
+    let proposer = Pubkey::new_unique();
+    let id = Pubkey::new_unique();
+    let ix = aaas_client::initialize_service(&executor, &proposal_id, &proposer, &id);
+
+    let data: instruction::InitializeService = args(&ix);
+    assert_eq!(data.id, id);
+    assert_eq!(
+        metas(&ix),
+        [
+            AccountMeta::new(executor, true),
+            AccountMeta::new(pda::config().0, false),
+            AccountMeta::new(pda::proposal(&proposal_id).0, false),
+            AccountMeta::new(proposer, false),
+            AccountMeta::new(pda::service(&id).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ]
+    );
+}
+
+#[test]
+fn join_passes_the_profile_only_when_asked() {
+    let candidate = Pubkey::new_unique();
+    let service = Pubkey::new_unique();
+    let challenge = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    let ata = Pubkey::new_unique();
+    let join = |with_profile| {
+        aaas_client::join_challenge(
+            &candidate,
+            &service,
+            &challenge,
+            &mint,
+            &ata,
+            &token_2022::ID,
+            with_profile,
+            500,
+            vec![[1u8; 32]],
+        )
+    };
+
+    let ix = join(true);
+    let data: instruction::JoinChallenge = args(&ix);
+    assert_eq!(data.stake, 500);
+    assert_eq!(data.allowlist_proof, vec![[1u8; 32]]);
+    let accounts = metas(&ix);
+    assert_eq!(accounts[0], AccountMeta::new(candidate, true));
+    assert_eq!(
+        accounts[3],
+        AccountMeta::new(pda::candidate(&service, &challenge, &candidate).0, false)
+    );
+    assert_eq!(
+        accounts[4],
+        AccountMeta::new(pda::profile(&candidate).0, false)
+    );
+    assert_eq!(
+        accounts[6],
+        AccountMeta::new(pda::vault(&challenge, &mint, &token_2022::ID), false)
+    );
+
+    //a missing optional account is passed as the program id
+    let ix = join(false);
+    assert_eq!(metas(&ix)[4], AccountMeta::new_readonly(aaas::ID, false));
+}
+
+#[test]
+fn validate_proof_votes_on_the_candidate_account() {
+    let validator = Pubkey::new_unique();
+    let service = Pubkey::new_unique();
+    let challenge = Pubkey::new_unique();
+    let candidate = Pubkey::new_unique();
+    let ix = aaas_client::validate_proof(
+        &validator,
+        &service,
+        &challenge,
+        &candidate,
+        false,
+        Vote::Reject,
+        [3u8; 32],
+    );
+
+    let data: instruction::ValidateProof = args(&ix);
+    assert!(data.vote == Vote::Reject);
+    assert_eq!(data.proof_hash, [3u8; 32]);
+    let candidate_account = pda::candidate(&service, &challenge, &candidate).0;
+    let accounts = metas(&ix);
+    assert_eq!(
+        accounts[2],
+        AccountMeta::new(pda::candidate(&service, &challenge, &validator).0, false)
+    );
+    assert_eq!(accounts[4], AccountMeta::new(candidate_account, false));
+    assert_eq!(
+        accounts[5],
+        AccountMeta::new(
+            pda::validation(&service, &challenge, &candidate_account, &validator).0,
+            false
+        )
+    );
+}
+
+#[test]
+fn finalize_appends_the_candidate_accounts() {
+    let cranker = Pubkey::new_unique();
+    let challenge = Pubkey::new_unique();
+    let candidate_accounts = [Pubkey::new_unique(), Pubkey::new_unique()];
+    let ix = aaas_client::finalize_challenge(&cranker, &challenge, &candidate_accounts);
+
+    let _: instruction::FinalizeChallenge = args(&ix);
+    let (accounts, remaining) = ix.accounts.split_at(ix.accounts.len() - 2);
+    assert_eq!(
+        remaining,
+        candidate_accounts.map(|candidate_account| AccountMeta::new(candidate_account, false))
+    );
+    let ix = Instruction {
+        accounts: accounts.to_vec(),
+        ..ix
+    };
+    assert_eq!(
+        metas(&ix),
+        [
+            AccountMeta::new_readonly(cranker, true),
+            AccountMeta::new(challenge, false),
+        ]
+    );
+}
+
+#[test]
+fn claim_badge_signs_for_the_service_id() {
+    let winner = Pubkey::new_unique();
+    let service_id = Pubkey::new_unique();
+    let service = pda::service(&service_id).0;
+    let challenge = Pubkey::new_unique();
+    let ix = aaas_client::claim_badge(&winner, &service_id, &challenge);
+
+    let data: instruction::ClaimBadge = args(&ix);
+    assert_eq!(data.service_id, service_id);
+    let badge = pda::badge(&challenge, &winner).0;
+    assert_eq!(
+        metas(&ix),
+        [
+            AccountMeta::new(winner, true),
+            AccountMeta::new_readonly(service, false),
+            AccountMeta::new_readonly(challenge, false),
+            AccountMeta::new_readonly(pda::candidate(&service, &challenge, &winner).0, false),
+            AccountMeta::new(badge, false),
+            AccountMeta::new(pda::collection(&service).0, false),
+            AccountMeta::new(
+                get_associated_token_address_with_program_id(&winner, &badge, &token_2022::ID),
+                false
+            ),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(token_2022::ID, false),
+            AccountMeta::new_readonly(anchor_spl::associated_token::ID, false),
+        ]
+    );
+}
+
+#[test]
+fn pause_and_resume_share_the_accounts() {
+    let executor = Pubkey::new_unique();
+    let proposal_id = Pubkey::new_unique();
+    let proposer = Pubkey::new_unique();
+    let service_id = Pubkey::new_unique();
+    let pause = aaas_client::pause_service(&executor, &proposal_id, &proposer, &service_id);
+    let resume = aaas_client::resume_service(&executor, &proposal_id, &proposer, &service_id);
+
+    let _: instruction::PauseService = args(&pause);
+    let _: instruction::ResumeService = args(&resume);
+    assert_eq!(pause.accounts, resume.accounts);
+    assert_eq!(
+        metas(&pause)[4],
+        AccountMeta::new(pda::service(&service_id).0, false)
+    );
+}
+
+#[test]
+fn milestone_votes_are_on_the_milestone_proof() {
+    let validator = Pubkey::new_unique();
+    let service = Pubkey::new_unique();
+    let challenge = Pubkey::new_unique();
+    let candidate = Pubkey::new_unique();
+    let ix = aaas_client::validate_milestone(
+        &validator,
+        &service,
+        &challenge,
+        &candidate,
+        1,
+        false,
+        Vote::Approve,
+        [5u8; 32],
+    );
+
+    let data: instruction::ValidateMilestone = args(&ix);
+    assert!(data.vote == Vote::Approve);
+    assert_eq!(data.proof_hash, [5u8; 32]);
+    let candidate_account = pda::candidate(&service, &challenge, &candidate).0;
+    let milestone_proof = pda::milestone(&challenge, &candidate_account, 1).0;
+    let accounts = metas(&ix);
+    assert_eq!(accounts[5], AccountMeta::new(milestone_proof, false));
+    let validation = pda::validation(&service, &challenge, &milestone_proof, &validator).0;
+    assert_eq!(accounts[6], AccountMeta::new(validation, false));
+
+    //the same validation is closed with the milestone proof as target
+    let ix = aaas_client::close_validation(
+        &Pubkey::new_unique(),
+        &service,
+        &challenge,
+        &milestone_proof,
+        &validator,
+    );
+    let accounts = metas(&ix);
+    assert_eq!(
+        accounts[3],
+        AccountMeta::new_readonly(milestone_proof, false)
+    );
+    assert_eq!(accounts[4], AccountMeta::new(validation, false));
+}
+
+#[test]
+fn attest_result_reads_the_instructions_sysvar() {
+    let submitter = Pubkey::new_unique();
+    let service = Pubkey::new_unique();
+    let challenge = Pubkey::new_unique();
+    let candidate = Pubkey::new_unique();
+    let ix = aaas_client::attest_result(&submitter, &service, &challenge, &candidate, true);
+
+    let data: instruction::AttestResult = args(&ix);
+    assert!(data.passed);
+    assert_eq!(
+        metas(&ix),
+        [
+            AccountMeta::new_readonly(submitter, true),
+            AccountMeta::new_readonly(challenge, false),
+            AccountMeta::new(pda::candidate(&service, &challenge, &candidate).0, false),
+            AccountMeta::new_readonly(sysvar::instructions::ID, false),
+        ]
+    );
+}
@@ -0,0 +1,110 @@
+use aaas_client::pda;
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anchor_spl::{token, token_2022};
+
+///the helper's address is the canonical one of the literal seeds, as the program derives it
+fn assert_derived(derived: (Pubkey, u8), seeds: &[&[u8]]) {
+    assert_eq!(derived, Pubkey::find_program_address(seeds, &aaas::ID));
+    let bump = [derived.1];
+    let with_bump: Vec<&[u8]> = seeds.iter().copied().chain([&bump[..]]).collect();
+    assert_eq!(
+        Pubkey::create_program_address(&with_bump, &aaas::ID).unwrap(),
+        derived.0
+    );
+}
+
+#[test]
+fn addresses_use_the_program_seeds() {
+    let id = Pubkey::new_unique();
+    let service = pda::service(&id).0;
+    let challenge = pda::challenge(&service, &id).0;
+    let candidate = Pubkey::new_unique();
+    let candidate_account = pda::candidate(&service, &challenge, &candidate).0;
+
+    assert_derived(pda::config(), &[b"aaasConfig"]);
+    assert_derived(pda::proposal(&id), &[b"aaasProposal", id.as_ref()]);
+    assert_derived(pda::service(&id), &[b"aaasService", id.as_ref()]);
+    assert_derived(
+        pda::challenge(&service, &id),
+        &[b"aaasChallenge", service.as_ref(), id.as_ref()],
+    );
+    assert_derived(
+        pda::candidate(&service, &challenge, &candidate),
+        &[
+            b"aaasCandidate",
+            service.as_ref(),
+            challenge.as_ref(),
+            candidate.as_ref(),
+        ],
+    );
+    assert_derived(
+        pda::validation(&service, &challenge, &candidate_account, &candidate),
+        &[
+            b"aaasValidation",
+            service.as_ref(),
+            challenge.as_ref(),
+            candidate_account.as_ref(),
+            candidate.as_ref(),
+        ],
+    );
+    assert_derived(
+        pda::milestone(&challenge, &candidate_account, 2),
+        &[
+            b"aaasMilestone",
+            challenge.as_ref(),
+            candidate_account.as_ref(),
+            &[2],
+        ],
+    );
+    assert_derived(
+        pda::profile(&candidate),
+        &[b"aaasProfile", candidate.as_ref()],
+    );
+    assert_derived(
+        pda::sponsorship(&challenge, &candidate),
+        &[b"aaasSponsorship", challenge.as_ref(), candidate.as_ref()],
+    );
+    assert_derived(
+        pda::badge(&challenge, &candidate),
+        &[b"aaasBadge", challenge.as_ref(), candidate.as_ref()],
+    );
+    assert_derived(
+        pda::collection(&service),
+        &[b"aaasCollection", service.as_ref()],
+    );
+    assert_derived(pda::event_authority(), &[b"__event_authority"]);
+}
+
+#[test]
+fn milestones_have_their_own_address() {
+    let challenge = Pubkey::new_unique();
+    let candidate_account = Pubkey::new_unique();
+    let first = pda::milestone(&challenge, &candidate_account, 0).0;
+    assert_ne!(first, pda::milestone(&challenge, &candidate_account, 1).0);
+    assert_ne!(
+        first,
+        pda::milestone(&challenge, &Pubkey::new_unique(), 0).0
+    );
+}
+
+#[test]
+fn vault_and_treasury_are_associated_token_accounts() {
+    let challenge = Pubkey::new_unique();
+    let admin = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    for token_program in [token::ID, token_2022::ID] {
+        assert_eq!(
+            pda::vault(&challenge, &mint, &token_program),
+            get_associated_token_address_with_program_id(&challenge, &mint, &token_program)
+        );
+        assert_eq!(
+            pda::treasury(&admin, &mint, &token_program),
+            get_associated_token_address_with_program_id(&admin, &mint, &token_program)
+        );
+    }
+    assert_ne!(
+        pda::vault(&challenge, &mint, &token::ID),
+        pda::vault(&challenge, &mint, &token_2022::ID)
+    );
+}
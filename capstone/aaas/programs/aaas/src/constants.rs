@@ -2,7 +2,19 @@ use anchor_lang::prelude::*;
 
 pub const DISCRIMINATOR: usize = 8;
 
-pub const VALIDATION_PERIOD: u64 = 60 * 60 * 24; //in seconds
+pub const MAX_SIGNERS: usize = 5; //keep in sync with AaasConfig.signers max_len
+
+pub const MAX_SERVICE_MINTS: usize = 10; //keep in sync with Service.mints max_len
+
+pub const BASIS_POINTS: u64 = 10000;
+
+pub const MAX_CHALLENGE_PROOF_LEN: usize = 200; //keep in sync with Challenge.proof max_len
+
+pub const MAX_CANDIDATE_PROOF_LEN: usize = 100; //keep in sync with CandidateAccount.proof max_len
+
+pub const MAX_MILESTONES: usize = 10; //keep in sync with Challenge.milestones max_len
+
+pub const PROPOSAL_LIFETIME: u64 = 7 * 24 * 60 * 60; //in seconds, from creation
 
 #[constant]
 pub const TREASURY_SEED: &[u8] = b"aaasTreasury";
@@ -22,3 +34,20 @@ pub const CANDIDATE_SEED: &[u8] = b"aaasCandidate";
 #[constant]
 pub const VALIDATE_SEED: &[u8] = b"aaasValidation";
 
+#[constant]
+pub const MILESTONE_SEED: &[u8] = b"aaasMilestone";
+
+#[constant]
+pub const PROFILE_SEED: &[u8] = b"aaasProfile";
+
+#[constant]
+pub const SPONSORSHIP_SEED: &[u8] = b"aaasSponsorship";
+
+#[constant]
+pub const BADGE_SEED: &[u8] = b"aaasBadge";
+
+#[constant]
+pub const COLLECTION_SEED: &[u8] = b"aaasCollection";
+
+#[constant]
+pub const PROPOSAL_SEED: &[u8] = b"aaasProposal";
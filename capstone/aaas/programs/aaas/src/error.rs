@@ -2,8 +2,8 @@ use anchor_lang::prelude::*;
 
 #[error_code]
 pub enum AaasError {
-    #[msg("Invalid USDC mint address!")]
-    InvalidUSDC,
+    #[msg("Mint doesn't match the challenge mint!")]
+    InvalidMint,
 
     #[msg("Challenge started!")]
     ChallengeStarted,
@@ -31,4 +31,244 @@ pub enum AaasError {
 
     #[msg("Multi-Signer threshold is violated!")]
     MutliSignerThreshold,
+
+    #[msg("Signer list must be non-empty and within the max signers!")]
+    InvalidSigners,
+
+    #[msg("Duplicate signer!")]
+    DuplicateSigner,
+
+    #[msg("Threshold must be between 1 and the number of signers!")]
+    InvalidThreshold,
+
+    #[msg("Not a config signer!")]
+    NotASigner,
+
+    #[msg("Proposal already approved by the signer!")]
+    AlreadyApproved,
+
+    #[msg("Proposal not approved by the signer!")]
+    NotApproved,
+
+    #[msg("Proposal action doesn't match the instruction!")]
+    ProposalActionMismatch,
+
+    #[msg("Fee must be within 10000 basis points!")]
+    InvalidFee,
+
+    #[msg("Signer is not in the config!")]
+    SignerNotFound,
+
+    #[msg("Service is paused!")]
+    ServicePaused,
+
+    #[msg("Service is already in the requested state!")]
+    ServiceStateUnchanged,
+
+    #[msg("Service limits are invalid!")]
+    InvalidServiceLimits,
+
+    #[msg("Challenge must start in the future!")]
+    InvalidStartTime,
+
+    #[msg("Challenge must end after it starts!")]
+    InvalidEndTime,
+
+    #[msg("Challenge is shorter than the service minimum duration!")]
+    ChallengeTooShort,
+
+    #[msg("Challenge is longer than the service maximum duration!")]
+    ChallengeTooLong,
+
+    #[msg("Stake amount must be greater than zero!")]
+    InvalidStakeAmount,
+
+    #[msg("Winning threshold must be between 1 and 10000 basis points!")]
+    InvalidWinningThreshold,
+
+    #[msg("Proof is too long!")]
+    ProofTooLong,
+
+    #[msg("Validation period is shorter than the service minimum!")]
+    ValidationPeriodTooShort,
+
+    #[msg("Validation period is longer than the service maximum!")]
+    ValidationPeriodTooLong,
+
+    #[msg("Reward already withdrawn!")]
+    AlreadyRewarded,
+
+    #[msg("Challenge has winners, stakes are paid out as rewards!")]
+    ChallengeHasWinners,
+
+    #[msg("Stake already refunded!")]
+    AlreadyRefunded,
+
+    #[msg("Challenge is not settled!")]
+    ChallengeNotSettled,
+
+    #[msg("Challenge still has open candidate or validation accounts!")]
+    ChallengeHasOpenAccounts,
+
+    #[msg("Mint is not allowed by the service!")]
+    MintNotAllowed,
+
+    #[msg("Service mints must be unique and within the max mints!")]
+    InvalidServiceMints,
+
+    #[msg("Vault didn't receive the expected amount!")]
+    UnexpectedVaultAmount,
+
+    #[msg("Quorum must be between 1 and 10000 basis point!")]
+    InvalidQuorum,
+
+    #[msg("Challenge is not finalized yet!")]
+    ChallengeNotFinalized,
+
+    #[msg("Challenge is already finalized!")]
+    ChallengeFinalized,
+
+    #[msg("Candidate is already finalized!")]
+    CandidateFinalized,
+
+    #[msg("Invalid candidate account!")]
+    InvalidCandidateAccount,
+
+    #[msg("Invalid validator incentives!")]
+    InvalidIncentives,
+
+    #[msg("Votes are not tallied yet!")]
+    VotesNotTallied,
+
+    #[msg("Vote is already tallied!")]
+    VoteTallied,
+
+    #[msg("Not eligible for the validator reward!")]
+    NotEligible,
+
+    #[msg("Validator reward is already claimed!")]
+    ValidatorAlreadyRewarded,
+
+    #[msg("Reveal period must be shorter than the validation window!")]
+    InvalidRevealPeriod,
+
+    #[msg("Challenge uses commit-reveal voting!")]
+    CommitRevealVoting,
+
+    #[msg("Challenge doesn't use commit-reveal voting!")]
+    OpenVoting,
+
+    #[msg("Commit phase ended!")]
+    CommitPhaseEnded,
+
+    #[msg("Not in the reveal phase!")]
+    NotRevealPhase,
+
+    #[msg("Revealed vote doesn't match the commitment!")]
+    InvalidReveal,
+
+    #[msg("Vote is already revealed!")]
+    AlreadyRevealed,
+
+    #[msg("Proof hash is required!")]
+    InvalidProofHash,
+
+    #[msg("Proof hash doesn't match the submitted proof!")]
+    ProofHashMismatch,
+
+    #[msg("Milestones must be ascending, end with the challenge, and min milestones within them!")]
+    InvalidMilestones,
+
+    #[msg("Milestone challenges don't support commit-reveal voting nor validator incentives!")]
+    MilestonesUnsupported,
+
+    #[msg("Invalid milestone!")]
+    InvalidMilestone,
+
+    #[msg("Milestone submission window is not open!")]
+    MilestoneNotOpen,
+
+    #[msg("Challenge uses milestones!")]
+    MilestoneChallenge,
+
+    #[msg("Challenge doesn't use milestones!")]
+    NotMilestoneChallenge,
+
+    #[msg("Candidate milestones are not finalized yet!")]
+    MilestonesNotFinalized,
+
+    #[msg("Milestone is already finalized!")]
+    MilestoneFinalized,
+
+    #[msg("Attestor validated challenges don't support commit-reveal voting, validator incentives nor milestones!")]
+    AttestorUnsupported,
+
+    #[msg("Challenge is validated by an attestor!")]
+    AttestorChallenge,
+
+    #[msg("Challenge isn't validated by an attestor!")]
+    NotAttestorChallenge,
+
+    #[msg("Candidate result is already attested!")]
+    AlreadyAttested,
+
+    #[msg("Invalid attestation instruction data!")]
+    InvalidAttestation,
+
+    #[msg("Unexpected program, expected the ed25519 program!")]
+    UnexpectedProgram,
+
+    #[msg("Unexpected accounts in the ed25519 instruction!")]
+    UnexpectedAccounts,
+
+    #[msg("Expected exactly one signature!")]
+    UnexpectedSignatures,
+
+    #[msg("Signature data isn't in the ed25519 instruction!")]
+    UnverifiableSignature,
+
+    #[msg("Attestation isn't signed by the attestor!")]
+    UnexpectedSigner,
+
+    #[msg("Unexpected attestation message!")]
+    UnexpectedMessage,
+
+    #[msg("Max candidates must be greater than 0!")]
+    InvalidMaxCandidates,
+
+    #[msg("Challenge is full!")]
+    ChallengeFull,
+
+    #[msg("Candidate isn't in the challenge allowlist!")]
+    NotAllowlisted,
+
+    #[msg("Sponsor amount must be greater than 0!")]
+    InvalidSponsorAmount,
+
+    #[msg("Challenge is cancelled!")]
+    ChallengeCancelled,
+
+    #[msg("Candidate reputation is below the service minimum!")]
+    InsufficientReputation,
+
+    #[msg("Candidate joined with a profile, it must be passed!")]
+    ProfileRequired,
+
+    #[msg("Badge is already claimed!")]
+    BadgeClaimed,
+
+    #[msg("Proposal expired!")]
+    ProposalExpired,
+
+    #[msg("Only the proposer can cancel a proposal before it expires!")]
+    ProposalNotExpired,
+
+    #[msg("Service has challenges that aren't closed!")]
+    ServiceHasChallenges,
+
+    #[msg("Payouts already started, the challenge can't be cancelled!")]
+    PayoutsStarted,
+
+    #[msg("Amount overflows with the transfer fee!")]
+    MathOverflow,
 }
@@ -0,0 +1,350 @@
+use anchor_lang::prelude::*;
+
+use crate::{AdminAction, ServiceLimits, Vote};
+
+/// logs the event, or with the `event-cpi` feature emits it through a self cpi,
+/// as logs can be truncated
+macro_rules! emit_event {
+    ($ctx:ident, $event:expr) => {{
+        #[cfg(feature = "event-cpi")]
+        {
+            //emit_cpi! reads the event authority from `ctx`
+            let ctx = &$ctx;
+            emit_cpi!($event);
+        }
+        #[cfg(not(feature = "event-cpi"))]
+        emit!($event);
+    }};
+}
+pub(crate) use emit_event;
+
+#[event]
+pub struct ConfigInitialized {
+    pub admin: Pubkey,
+    pub signers: Vec<Pubkey>,
+    pub threshold: u8,
+}
+
+#[event]
+pub struct ProposalCreated {
+    pub proposal: Pubkey,
+    pub id: Pubkey,
+    pub proposer: Pubkey,
+    pub action: AdminAction,
+    pub expires_at: u64,
+}
+
+#[event]
+pub struct ProposalApproved {
+    pub proposal: Pubkey,
+    pub signer: Pubkey,
+    ///approvals after this one, those of keys rotated out of the config are dropped
+    pub approvals: u8,
+}
+
+#[event]
+pub struct ApprovalRevoked {
+    pub proposal: Pubkey,
+    pub signer: Pubkey,
+    pub approvals: u8,
+}
+
+#[event]
+pub struct ProposalCancelled {
+    pub proposal: Pubkey,
+    pub canceller: Pubkey,
+}
+
+#[event]
+pub struct ServiceInitialized {
+    pub service: Pubkey,
+    pub id: Pubkey,
+    pub fee: u16,
+    pub forfeit_fee: u16,
+    pub mints: Vec<Pubkey>,
+}
+
+#[event]
+pub struct ServiceFeeUpdated {
+    pub service: Pubkey,
+    pub fee: u16,
+    pub forfeit_fee: u16,
+}
+
+#[event]
+pub struct ServiceLimitsUpdated {
+    pub service: Pubkey,
+    pub limits: ServiceLimits,
+}
+
+#[event]
+pub struct ServiceMintsSet {
+    pub service: Pubkey,
+    pub mints: Vec<Pubkey>,
+}
+
+#[event]
+pub struct ServiceAttestorSet {
+    pub service: Pubkey,
+    pub attestor: Option<Pubkey>,
+}
+
+///by pause_service, or resume_service with `paused` false
+#[event]
+pub struct ServicePaused {
+    pub service: Pubkey,
+    pub paused: bool,
+}
+
+#[event]
+pub struct ServiceClosed {
+    pub service: Pubkey,
+    pub id: Pubkey,
+}
+
+///the config signers and threshold after a rotation, an added or removed signer, or a threshold change
+#[event]
+pub struct SignersUpdated {
+    pub signers: Vec<Pubkey>,
+    pub threshold: u8,
+}
+
+#[event]
+pub struct AdminTransferred {
+    pub previous_admin: Pubkey,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct ProfileInitialized {
+    pub profile: Pubkey,
+    pub owner: Pubkey,
+}
+
+#[event]
+pub struct ChallengeCreated {
+    pub challenge: Pubkey,
+    pub service: Pubkey,
+    pub creator: Pubkey,
+    pub mint: Pubkey,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub stake_amnt: u64,
+    pub validation_period: u64,
+    pub winning_threshold: u16,
+    pub quorum: u16,
+    pub max_candidates: u32,
+}
+
+#[event]
+pub struct CandidateJoined {
+    pub challenge: Pubkey,
+    pub candidate: Pubkey,
+    pub stake: u64,
+    pub weight: u64,
+    pub candidate_count: u32,
+}
+
+#[event]
+pub struct CandidateExited {
+    pub challenge: Pubkey,
+    pub candidate: Pubkey,
+    ///stake sent back to the candidate
+    pub stake: u64,
+    pub candidate_count: u32,
+}
+
+#[event]
+pub struct ProofSubmitted {
+    pub challenge: Pubkey,
+    pub candidate: Pubkey,
+    pub proof_hash: [u8; 32],
+    pub proof: String,
+}
+
+///an open vote, or a revealed one
+#[event]
+pub struct ProofValidated {
+    pub challenge: Pubkey,
+    pub candidate: Pubkey,
+    pub validator: Pubkey,
+    pub vote: Vote,
+    pub weight: u64,
+    ///candidate's tallies after the vote
+    pub acceptance: u64,
+    pub rejections: u64,
+}
+
+///the attestor's result for a candidate, passed or not
+#[event]
+pub struct ResultAttested {
+    pub challenge: Pubkey,
+    pub candidate: Pubkey,
+    pub attestor: Pubkey,
+    pub passed: bool,
+}
+
+///the candidate's proof reached the quorum and winning threshold, or was attested as passed,
+///it isn't final till finalize_challenge as later votes still count
+#[event]
+pub struct WinnerThresholdReached {
+    pub challenge: Pubkey,
+    pub candidate: Pubkey,
+    pub acceptance: u64,
+    pub rejections: u64,
+}
+
+#[event]
+pub struct RewardWithdrawn {
+    pub challenge: Pubkey,
+    pub winner: Pubkey,
+    ///sent to the winner, its stake back included
+    pub amount: u64,
+    ///service fee sent to the treasury
+    pub fee: u64,
+    pub penalty: u64,
+}
+
+#[event]
+pub struct ChallengeSponsored {
+    pub challenge: Pubkey,
+    pub sponsor: Pubkey,
+    ///received by the vault
+    pub amount: u64,
+    pub bonus_pool: u64,
+}
+
+#[event]
+pub struct ChallengeCancelled {
+    pub challenge: Pubkey,
+    ///cancelled by the multisig, otherwise by the creator before the start
+    pub emergency: bool,
+}
+
+#[event]
+pub struct VoteCommitted {
+    pub challenge: Pubkey,
+    pub candidate: Pubkey,
+    pub validator: Pubkey,
+    pub commitment: [u8; 32],
+}
+
+#[event]
+pub struct MilestoneSubmitted {
+    pub challenge: Pubkey,
+    pub candidate: Pubkey,
+    pub index: u8,
+    pub proof_hash: [u8; 32],
+    pub proof: String,
+}
+
+#[event]
+pub struct MilestoneValidated {
+    pub challenge: Pubkey,
+    pub candidate: Pubkey,
+    pub validator: Pubkey,
+    pub index: u8,
+    pub vote: Vote,
+    pub weight: u64,
+    ///milestone's tallies after the vote
+    pub acceptance: u64,
+    pub rejections: u64,
+}
+
+#[event]
+pub struct MilestoneFinalized {
+    pub challenge: Pubkey,
+    pub candidate: Pubkey,
+    pub index: u8,
+    pub passed: bool,
+    pub milestones_passed: u8,
+}
+
+///emitted by the batch that records the last candidate's result
+#[event]
+pub struct ChallengeFinalized {
+    pub challenge: Pubkey,
+    pub winner_count: u32,
+    pub winner_stake: u64,
+}
+
+#[event]
+pub struct VoteTallied {
+    pub challenge: Pubkey,
+    pub candidate: Pubkey,
+    pub validator: Pubkey,
+    ///None for an unrevealed vote
+    pub vote: Option<Vote>,
+    pub agreed: bool,
+    ///the validator's standing, decided with its last vote
+    pub eligible: bool,
+    pub penalized: bool,
+}
+
+#[event]
+pub struct ValidatorRewardClaimed {
+    pub challenge: Pubkey,
+    pub validator: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct StakeRefunded {
+    pub challenge: Pubkey,
+    pub candidate: Pubkey,
+    ///sent back to the candidate
+    pub amount: u64,
+    ///forfeit fee sent to the treasury
+    pub forfeit: u64,
+    pub penalty: u64,
+}
+
+#[event]
+pub struct BadgeMinted {
+    pub challenge: Pubkey,
+    pub winner: Pubkey,
+    pub badge: Pubkey,
+    pub collection: Pubkey,
+}
+
+#[event]
+pub struct SponsorshipRefunded {
+    pub challenge: Pubkey,
+    pub sponsor: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ValidationClosed {
+    pub challenge: Pubkey,
+    ///the candidate account may already be closed
+    pub candidate_account: Pubkey,
+    pub validator: Pubkey,
+}
+
+#[event]
+pub struct CandidateClosed {
+    pub challenge: Pubkey,
+    pub candidate: Pubkey,
+}
+
+#[event]
+pub struct SponsorshipClosed {
+    pub challenge: Pubkey,
+    pub sponsor: Pubkey,
+}
+
+#[event]
+pub struct MilestoneClosed {
+    pub challenge: Pubkey,
+    pub candidate: Pubkey,
+    pub index: u8,
+}
+
+#[event]
+pub struct ChallengeClosed {
+    pub challenge: Pubkey,
+    pub service: Pubkey,
+    ///left in the vault, swept to the treasury
+    pub swept: u64,
+}
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AaasError;
+use crate::instructions::execute_proposal::*;
+use crate::{AaasConfig, AdminAction, SignersUpdated};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct AddSigner<'info> {
+    pub execution: ExecuteProposal<'info>,
+}
+
+impl<'info> AddSigner<'info> {
+    pub fn handler(&mut self) -> Result<SignersUpdated> {
+        let AdminAction::AddSigner { signer } = self.execution.approved_action()? else {
+            return err!(AaasError::ProposalActionMismatch);
+        };
+
+        let config = &mut self.execution.config;
+        let mut signers = config.signers.clone();
+        signers.push(signer);
+        AaasConfig::validate_signers(&signers, config.threshold)?;
+
+        config.signers = signers.clone();
+        Ok(SignersUpdated {
+            signers,
+            threshold: config.threshold,
+        })
+    }
+}
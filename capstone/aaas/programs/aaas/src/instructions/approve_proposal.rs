@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{CONFIG_SEED, PROPOSAL_SEED};
+use crate::error::AaasError;
+use crate::{AaasConfig, Proposal, ProposalApproved};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ApproveProposal<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.signers.contains(&signer.key()) @ AaasError::NotASigner,
+    )]
+    pub config: Account<'info, AaasConfig>,
+
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, proposal.id.key().as_ref()],
+        bump = proposal.bump,
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+impl<'info> ApproveProposal<'info> {
+    pub fn handler(&mut self) -> Result<ProposalApproved> {
+        let now = Clock::get()?.unix_timestamp as u64;
+        require!(!self.proposal.is_expired(now), AaasError::ProposalExpired);
+
+        let signer = self.signer.key();
+        require!(
+            !self.proposal.approvals.contains(&signer),
+            AaasError::AlreadyApproved
+        );
+
+        //drop approvals of keys rotated out of the config, so they don't take up the space
+        let signers = &self.config.signers;
+        self.proposal
+            .approvals
+            .retain(|approver| signers.contains(approver));
+        self.proposal.approvals.push(signer);
+
+        Ok(ProposalApproved {
+            proposal: self.proposal.key(),
+            signer,
+            approvals: self.proposal.approvals.len() as u8,
+        })
+    }
+}
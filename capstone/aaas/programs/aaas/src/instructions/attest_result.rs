@@ -0,0 +1,108 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::sysvar::instructions::load_instruction_at_checked;
+
+use crate::constants::{CANDIDATE_SEED, CHALLENGE_SEED};
+use crate::error::AaasError;
+use crate::{ed25519_signed_message, CandidateAccount, Challenge, ResultAttested, WinnerThresholdReached};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct AttestResult<'info> {
+    ///anyone can submit the attestation, it's authenticated by the attestor signature
+    pub submitter: Signer<'info>,
+
+    #[account(
+        seeds = [CHALLENGE_SEED, challenge.service.key().as_ref(), challenge.id.key().as_ref()],
+        bump = challenge.bump,
+        constraint = !challenge.cancelled @ AaasError::ChallengeCancelled,
+    )]
+    pub challenge: Account<'info, Challenge>,
+
+    #[account(
+        mut,
+        seeds = [CANDIDATE_SEED, challenge.service.key().as_ref(), challenge.key().as_ref(), candidate_account.candidate.key().as_ref()],
+        bump = candidate_account.bump,
+        has_one = challenge,
+        constraint = candidate_account.attestation.is_none() @ AaasError::AlreadyAttested,
+    )]
+    pub candidate_account: Account<'info, CandidateAccount>,
+
+    /// CHECK: this is read only account
+    #[account(
+        address = solana_program::sysvar::instructions::ID
+    )]
+    pub instruction_sysvar: AccountInfo<'info>,
+}
+
+impl<'info> AttestResult<'info> {
+    /// signed message: challenge || candidate || passed
+    pub fn attestation_message(&self, passed: bool) -> Vec<u8> {
+        [
+            self.challenge.key().as_ref(),
+            self.candidate_account.candidate.as_ref(),
+            &[passed as u8],
+        ]
+        .concat()
+    }
+
+    pub fn verify_ed25519_signature(&self, attestor: Pubkey, passed: bool) -> Result<()> {
+        //grab the first instruction in the transaction, the attestor's ed25519 signature
+        let ix = load_instruction_at_checked(0, &self.instruction_sysvar.to_account_info())?;
+
+        require_keys_eq!(
+            ix.program_id,
+            ed25519_program::ID,
+            AaasError::UnexpectedProgram
+        );
+
+        require_eq!(ix.accounts.len(), 0, AaasError::UnexpectedAccounts);
+
+        let (public_key, message) = ed25519_signed_message(&ix.data)?;
+        require_keys_eq!(public_key, attestor, AaasError::UnexpectedSigner);
+        require!(
+            message == self.attestation_message(passed),
+            AaasError::UnexpectedMessage
+        );
+
+        Ok(())
+    }
+
+    pub fn handler(
+        &mut self,
+        passed: bool,
+    ) -> Result<(ResultAttested, Option<WinnerThresholdReached>)> {
+        let attestor = self
+            .challenge
+            .attestor
+            .ok_or(AaasError::NotAttestorChallenge)?;
+
+        //results are attested within the challenge and its validation period
+        let now = Clock::get()?.unix_timestamp as u64;
+        require!(
+            now > self.challenge.start_time,
+            AaasError::ChallengeNotStarted
+        );
+        require!(
+            now < self.challenge.validation_end(),
+            AaasError::ValidationPeriodEnded
+        );
+
+        self.verify_ed25519_signature(attestor, passed)?;
+
+        //an attestation is final, so a replayed older one can't flip it
+        self.candidate_account.attestation = Some(passed);
+
+        let attested = ResultAttested {
+            challenge: self.challenge.key(),
+            candidate: self.candidate_account.candidate,
+            attestor,
+            passed,
+        };
+        Ok((
+            attested,
+            passed.then(|| self.candidate_account.threshold_reached()),
+        ))
+    }
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::CHALLENGE_SEED;
+use crate::error::AaasError;
+use crate::{Challenge, ChallengeCancelled};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct CancelChallenge<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CHALLENGE_SEED, challenge.service.key().as_ref(), challenge.id.key().as_ref()],
+        bump = challenge.bump,
+        has_one = creator,
+    )]
+    pub challenge: Account<'info, Challenge>,
+}
+
+impl<'info> CancelChallenge<'info> {
+    pub fn handler(&mut self) -> Result<ChallengeCancelled> {
+        //candidates joined on the creator's terms, so the creator can only back out before the start
+        let now = Clock::get()?.unix_timestamp as u64;
+        require!(now < self.challenge.start_time, AaasError::ChallengeStarted);
+
+        self.challenge.cancel()?;
+
+        Ok(ChallengeCancelled {
+            challenge: self.challenge.key(),
+            emergency: false,
+        })
+    }
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::PROPOSAL_SEED;
+use crate::error::AaasError;
+use crate::{Proposal, ProposalCancelled};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct CancelProposal<'info> {
+    pub canceller: Signer<'info>,
+
+    /// the cancelled proposal is closed, and its rent goes back to the proposer
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, proposal.id.key().as_ref()],
+        bump = proposal.bump,
+        has_one = proposer,
+        close = proposer,
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(mut)]
+    pub proposer: SystemAccount<'info>,
+}
+
+impl<'info> CancelProposal<'info> {
+    pub fn handler(&mut self) -> Result<ProposalCancelled> {
+        //the proposer can drop its proposal anytime, anyone can clean it up once expired
+        let now = Clock::get()?.unix_timestamp as u64;
+        require!(
+            self.canceller.key() == self.proposer.key() || self.proposal.is_expired(now),
+            AaasError::ProposalNotExpired
+        );
+        Ok(ProposalCancelled {
+            proposal: self.proposal.key(),
+            canceller: self.canceller.key(),
+        })
+    }
+}
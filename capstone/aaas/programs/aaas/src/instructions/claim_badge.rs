@@ -0,0 +1,361 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::{self, AssociatedToken};
+use anchor_spl::token_2022::spl_token_2022::extension::ExtensionType;
+use anchor_spl::token_2022::spl_token_2022::instruction::AuthorityType;
+use anchor_spl::token_2022::spl_token_2022::state::Mint;
+use anchor_spl::token_2022::{self, Token2022};
+use anchor_spl::token_2022_extensions::spl_pod::optional_keys::OptionalNonZeroPubkey;
+use anchor_spl::token_2022_extensions::spl_token_metadata_interface::state::{
+    Field, TokenMetadata,
+};
+use anchor_spl::token_2022_extensions::{
+    group_member_pointer_initialize, group_pointer_initialize, metadata_pointer_initialize,
+    non_transferable_mint_initialize, token_group_initialize, token_member_initialize,
+    token_metadata_initialize, token_metadata_update_field, GroupMemberPointerInitialize,
+    GroupPointerInitialize, MetadataPointerInitialize, NonTransferableMintInitialize,
+    TokenGroupInitialize, TokenMemberInitialize, TokenMetadataInitialize, TokenMetadataUpdateField,
+};
+
+use crate::constants::{BADGE_SEED, CANDIDATE_SEED, CHALLENGE_SEED, COLLECTION_SEED, SERVICE_SEED};
+use crate::error::AaasError;
+use crate::{create_pda_account, BadgeMinted, CandidateAccount, Challenge};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(service_id: Pubkey)]
+pub struct ClaimBadge<'info> {
+    #[account(mut)]
+    pub winner: Signer<'info>,
+
+    /// CHECK: badge collection authority, only signs so badges can still be claimed once the service is closed
+    #[account(
+        seeds = [SERVICE_SEED, service_id.as_ref()],
+        bump,
+    )]
+    pub service: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [CHALLENGE_SEED, challenge.service.key().as_ref(), challenge.id.key().as_ref()],
+        bump = challenge.bump,
+        has_one = service,
+        constraint = challenge.finalized @ AaasError::ChallengeNotFinalized,
+        constraint = !challenge.cancelled @ AaasError::ChallengeCancelled,
+    )]
+    pub challenge: Account<'info, Challenge>,
+
+    #[account(
+        seeds = [CANDIDATE_SEED, challenge.service.key().as_ref(), challenge.key().as_ref(), winner.key().as_ref()],
+        bump = winner_account.bump,
+        has_one = challenge,
+        constraint = winner_account.candidate == winner.key(),
+        constraint = winner_account.winner @ AaasError::WinningThreshold,
+    )]
+    pub winner_account: Account<'info, CandidateAccount>,
+
+    /// CHECK: created in the instruction as a non-transferable token-2022 mint, one per winner of a challenge
+    #[account(
+        mut,
+        seeds = [BADGE_SEED, challenge.key().as_ref(), winner.key().as_ref()],
+        bump,
+    )]
+    pub badge_mint: UncheckedAccount<'info>,
+
+    /// CHECK: token-2022 group mint of the service badges, created by the first claim of the service
+    #[account(
+        mut,
+        seeds = [COLLECTION_SEED, service.key().as_ref()],
+        bump,
+    )]
+    pub collection: UncheckedAccount<'info>,
+
+    /// CHECK: winner's badge token account, created in the instruction once the mint exists
+    #[account(mut)]
+    pub badge_ata: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> ClaimBadge<'info> {
+    pub fn handler(&mut self, service_id: Pubkey, bumps: &ClaimBadgeBumps) -> Result<BadgeMinted> {
+        require!(self.badge_mint.data_is_empty(), AaasError::BadgeClaimed);
+
+        let service_seeds: &[&[&[u8]]] = &[&[SERVICE_SEED, service_id.as_ref(), &[bumps.service]]];
+        if self.collection.data_is_empty() {
+            self.create_collection(service_seeds, bumps.collection)?;
+        }
+
+        let challenge_key = self.challenge.key();
+        let winner_key = self.winner.key();
+        let mint_seeds: &[&[&[u8]]] = &[&[
+            BADGE_SEED,
+            challenge_key.as_ref(),
+            winner_key.as_ref(),
+            &[bumps.badge_mint],
+        ]];
+
+        //metadata lives in the mint itself, and points at the challenge
+        let metadata = TokenMetadata {
+            update_authority: OptionalNonZeroPubkey(self.service.key()),
+            mint: self.badge_mint.key(),
+            name: "AAAS Badge".to_string(),
+            symbol: "AAAS".to_string(),
+            uri: self.challenge.proof.clone(),
+            additional_metadata: vec![("challenge".to_string(), challenge_key.to_string())],
+        };
+
+        //the mint is created with the fixed size extensions, token metadata and the group member
+        //realloc it, so their rent is paid upfront
+        let extensions = [
+            ExtensionType::NonTransferable,
+            ExtensionType::MetadataPointer,
+            ExtensionType::GroupMemberPointer,
+        ];
+        let space = ExtensionType::try_calculate_account_len::<Mint>(&extensions)?;
+        let full_space = ExtensionType::try_calculate_account_len::<Mint>(
+            &[&extensions[..], &[ExtensionType::TokenGroupMember]].concat(),
+        )?;
+        let lamports = Rent::get()?.minimum_balance(full_space + metadata.tlv_size_of()?);
+        create_pda_account(
+            &self.winner.to_account_info(),
+            &self.badge_mint.to_account_info(),
+            &self.system_program.to_account_info(),
+            lamports,
+            space as u64,
+            &self.token_program.key(),
+            mint_seeds,
+        )?;
+
+        //extensions are initialized before the mint
+        non_transferable_mint_initialize(CpiContext::new(
+            self.token_program.to_account_info(),
+            NonTransferableMintInitialize {
+                token_program_id: self.token_program.to_account_info(),
+                mint: self.badge_mint.to_account_info(),
+            },
+        ))?;
+        metadata_pointer_initialize(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                MetadataPointerInitialize {
+                    token_program_id: self.token_program.to_account_info(),
+                    mint: self.badge_mint.to_account_info(),
+                },
+            ),
+            Some(self.service.key()),
+            Some(self.badge_mint.key()),
+        )?;
+        group_member_pointer_initialize(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                GroupMemberPointerInitialize {
+                    token_program_id: self.token_program.to_account_info(),
+                    mint: self.badge_mint.to_account_info(),
+                },
+            ),
+            Some(self.service.key()),
+            Some(self.badge_mint.key()),
+        )?;
+        token_2022::initialize_mint2(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                token_2022::InitializeMint2 {
+                    mint: self.badge_mint.to_account_info(),
+                },
+            ),
+            0,
+            &self.service.key(),
+            None,
+        )?;
+
+        token_metadata_initialize(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                TokenMetadataInitialize {
+                    program_id: self.token_program.to_account_info(),
+                    metadata: self.badge_mint.to_account_info(),
+                    update_authority: self.service.to_account_info(),
+                    mint_authority: self.service.to_account_info(),
+                    mint: self.badge_mint.to_account_info(),
+                },
+                service_seeds,
+            ),
+            metadata.name,
+            metadata.symbol,
+            metadata.uri,
+        )?;
+        for (key, value) in metadata.additional_metadata {
+            token_metadata_update_field(
+                CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    TokenMetadataUpdateField {
+                        program_id: self.token_program.to_account_info(),
+                        metadata: self.badge_mint.to_account_info(),
+                        update_authority: self.service.to_account_info(),
+                    },
+                    service_seeds,
+                ),
+                Field::Key(key),
+                value,
+            )?;
+        }
+
+        //the badge joins the service collection
+        token_member_initialize(CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            TokenMemberInitialize {
+                program_id: self.token_program.to_account_info(),
+                member: self.badge_mint.to_account_info(),
+                member_mint: self.badge_mint.to_account_info(),
+                member_mint_authority: self.service.to_account_info(),
+                group: self.collection.to_account_info(),
+                group_update_authority: self.service.to_account_info(),
+            },
+            service_seeds,
+        ))?;
+
+        //mint the badge to the winner
+        associated_token::create(CpiContext::new(
+            self.associated_token_program.to_account_info(),
+            associated_token::Create {
+                payer: self.winner.to_account_info(),
+                associated_token: self.badge_ata.to_account_info(),
+                authority: self.winner.to_account_info(),
+                mint: self.badge_mint.to_account_info(),
+                system_program: self.system_program.to_account_info(),
+                token_program: self.token_program.to_account_info(),
+            },
+        ))?;
+        token_2022::mint_to(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                token_2022::MintTo {
+                    mint: self.badge_mint.to_account_info(),
+                    to: self.badge_ata.to_account_info(),
+                    authority: self.service.to_account_info(),
+                },
+                service_seeds,
+            ),
+            1,
+        )?;
+
+        //a badge is one of a kind
+        token_2022::set_authority(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                token_2022::SetAuthority {
+                    current_authority: self.service.to_account_info(),
+                    account_or_mint: self.badge_mint.to_account_info(),
+                },
+                service_seeds,
+            ),
+            AuthorityType::MintTokens,
+            None,
+        )?;
+
+        Ok(BadgeMinted {
+            challenge: challenge_key,
+            winner: winner_key,
+            badge: self.badge_mint.key(),
+            collection: self.collection.key(),
+        })
+    }
+
+    ///the collection is a token-2022 group mint, with the service as group and metadata authority
+    fn create_collection(&self, service_seeds: &[&[&[u8]]], bump: u8) -> Result<()> {
+        let service_key = self.service.key();
+        let collection_seeds: &[&[&[u8]]] = &[&[COLLECTION_SEED, service_key.as_ref(), &[bump]]];
+
+        let metadata = TokenMetadata {
+            update_authority: OptionalNonZeroPubkey(service_key),
+            mint: self.collection.key(),
+            name: "AAAS Badges".to_string(),
+            symbol: "AAAS".to_string(),
+            uri: String::new(),
+            additional_metadata: vec![],
+        };
+
+        let extensions = [ExtensionType::GroupPointer, ExtensionType::MetadataPointer];
+        let space = ExtensionType::try_calculate_account_len::<Mint>(&extensions)?;
+        let full_space = ExtensionType::try_calculate_account_len::<Mint>(
+            &[&extensions[..], &[ExtensionType::TokenGroup]].concat(),
+        )?;
+        let lamports = Rent::get()?.minimum_balance(full_space + metadata.tlv_size_of()?);
+        create_pda_account(
+            &self.winner.to_account_info(),
+            &self.collection.to_account_info(),
+            &self.system_program.to_account_info(),
+            lamports,
+            space as u64,
+            &self.token_program.key(),
+            collection_seeds,
+        )?;
+
+        group_pointer_initialize(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                GroupPointerInitialize {
+                    token_program_id: self.token_program.to_account_info(),
+                    mint: self.collection.to_account_info(),
+                },
+            ),
+            Some(service_key),
+            Some(self.collection.key()),
+        )?;
+        metadata_pointer_initialize(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                MetadataPointerInitialize {
+                    token_program_id: self.token_program.to_account_info(),
+                    mint: self.collection.to_account_info(),
+                },
+            ),
+            Some(service_key),
+            Some(self.collection.key()),
+        )?;
+        token_2022::initialize_mint2(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                token_2022::InitializeMint2 {
+                    mint: self.collection.to_account_info(),
+                },
+            ),
+            0,
+            &service_key,
+            None,
+        )?;
+
+        token_metadata_initialize(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                TokenMetadataInitialize {
+                    program_id: self.token_program.to_account_info(),
+                    metadata: self.collection.to_account_info(),
+                    update_authority: self.service.to_account_info(),
+                    mint_authority: self.service.to_account_info(),
+                    mint: self.collection.to_account_info(),
+                },
+                service_seeds,
+            ),
+            metadata.name,
+            metadata.symbol,
+            metadata.uri,
+        )?;
+        //no size limit, every winner of the service joins it
+        token_group_initialize(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                TokenGroupInitialize {
+                    program_id: self.token_program.to_account_info(),
+                    group: self.collection.to_account_info(),
+                    mint: self.collection.to_account_info(),
+                    mint_authority: self.service.to_account_info(),
+                },
+                service_seeds,
+            ),
+            Some(service_key),
+            u64::MAX,
+        )
+    }
+}
@@ -0,0 +1,129 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface;
+
+use crate::constants::{CANDIDATE_SEED, CHALLENGE_SEED};
+use crate::error::AaasError;
+use crate::{CandidateAccount, Challenge, ValidatorRewardClaimed};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ClaimValidatorReward<'info> {
+    #[account(mut)]
+    pub validator: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CANDIDATE_SEED, challenge.service.as_ref(), challenge.key().as_ref(), validator.key().as_ref()],
+        bump = validator_account.bump,
+        has_one = challenge,
+        constraint = validator.key() == validator_account.candidate.key()
+    )]
+    pub validator_account: Account<'info, CandidateAccount>,
+
+    #[account(
+        mut,
+        seeds = [CHALLENGE_SEED, challenge.service.as_ref(), challenge.id.key().as_ref()],
+        bump = challenge.bump,
+        constraint = !challenge.cancelled @ AaasError::ChallengeCancelled,
+    )]
+    pub challenge: Account<'info, Challenge>,
+
+    #[account(
+        address = challenge.mint @ AaasError::InvalidMint
+    )]
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = challenge
+    )]
+    pub vault: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = validator,
+    )]
+    pub validator_ata: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, token_interface::TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> ClaimValidatorReward<'info> {
+    pub fn handler(&mut self) -> Result<ValidatorRewardClaimed> {
+        //the validator pool is only known once every vote is tallied
+        require!(self.challenge.finalized, AaasError::ChallengeNotFinalized);
+        require!(self.challenge.tallied(), AaasError::VotesNotTallied);
+        require!(self.validator_account.eligible, AaasError::NotEligible);
+        require!(
+            !self.validator_account.validator_rewarded,
+            AaasError::ValidatorAlreadyRewarded
+        );
+
+        //validator pool is the cut of the losers stake, and the penalties
+        let pool = self
+            .challenge
+            .validator_cut()
+            .checked_add(self.challenge.penalty_pool)
+            .unwrap();
+        let is_last_claimant = self
+            .challenge
+            .validator_rewarded_count
+            .checked_add(1)
+            .unwrap()
+            == self.challenge.eligible_count;
+        let reward = if is_last_claimant {
+            //last validator takes the integer division dust
+            pool.checked_sub(self.challenge.validator_distributed)
+                .unwrap()
+        } else {
+            pool.checked_div(self.challenge.eligible_count as u64)
+                .unwrap()
+        };
+
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: self.vault.to_account_info(),
+                    mint: self.mint.to_account_info(),
+                    to: self.validator_ata.to_account_info(),
+                    authority: self.challenge.to_account_info(),
+                },
+                &[&[
+                    CHALLENGE_SEED,
+                    self.challenge.service.key().as_ref(),
+                    self.challenge.id.key().as_ref(),
+                    &[self.challenge.bump],
+                ]],
+            ),
+            reward,
+            self.mint.decimals,
+        )?;
+
+        self.validator_account.validator_rewarded = true;
+
+        //update settlement in challenge
+        self.challenge.validator_distributed = self
+            .challenge
+            .validator_distributed
+            .checked_add(reward)
+            .unwrap();
+        self.challenge.validator_rewarded_count = self
+            .challenge
+            .validator_rewarded_count
+            .checked_add(1)
+            .unwrap();
+        self.challenge.settled = self.challenge.is_settled();
+
+        Ok(ValidatorRewardClaimed {
+            challenge: self.challenge.key(),
+            validator: self.validator.key(),
+            amount: reward,
+        })
+    }
+}
@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{CANDIDATE_SEED, CHALLENGE_SEED, PROFILE_SEED};
+use crate::error::AaasError;
+use crate::{CandidateAccount, CandidateClosed, Challenge, Profile};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct CloseCandidate<'info> {
+    ///anyone can crank the close, rent always goes back to the candidate
+    pub closer: Signer<'info>,
+
+    ///paid the candidate account rent
+    #[account(mut)]
+    pub candidate: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [CHALLENGE_SEED, challenge.service.key().as_ref(), challenge.id.key().as_ref()],
+        bump = challenge.bump,
+        constraint = challenge.settled @ AaasError::ChallengeNotSettled,
+    )]
+    pub challenge: Account<'info, Challenge>,
+
+    #[account(
+        mut,
+        seeds = [CANDIDATE_SEED, challenge.service.key().as_ref(), challenge.key().as_ref(), candidate.key().as_ref()],
+        bump = candidate_account.bump,
+        has_one = challenge,
+        has_one = candidate,
+        close = candidate,
+    )]
+    pub candidate_account: Account<'info, CandidateAccount>,
+
+    ///candidate history, required when the candidate joined with it, left untouched otherwise
+    #[account(
+        mut,
+        seeds = [PROFILE_SEED, candidate.key().as_ref()],
+        bump = profile.bump,
+    )]
+    pub profile: Option<Account<'info, Profile>>,
+}
+
+impl<'info> CloseCandidate<'info> {
+    pub fn handler(&mut self) -> Result<CandidateClosed> {
+        require!(
+            !self.candidate_account.profiled || self.profile.is_some(),
+            AaasError::ProfileRequired
+        );
+        //losers of a challenge with winners never get a payout, so their loss is recorded on close
+        if let Some(profile) = self
+            .profile
+            .as_mut()
+            .filter(|_| self.candidate_account.profiled)
+        {
+            if self.challenge.winner_count > 0
+                && !self.challenge.cancelled
+                && !self.candidate_account.winner
+            {
+                profile.record_loss();
+            }
+        }
+
+        //settled challenge has every payout done, so losers and paid winners can both be closed
+        self.challenge.closed_count = self.challenge.closed_count.checked_add(1).unwrap();
+
+        Ok(CandidateClosed {
+            challenge: self.challenge.key(),
+            candidate: self.candidate.key(),
+        })
+    }
+}
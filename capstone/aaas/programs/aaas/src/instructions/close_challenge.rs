@@ -0,0 +1,154 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface;
+
+use crate::constants::{CHALLENGE_SEED, CONFIG_SEED, SERVICE_SEED};
+use crate::error::AaasError;
+use crate::{transfer_fee_config, AaasConfig, Challenge, ChallengeClosed, Service};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct CloseChallenge<'info> {
+    ///anyone can crank the close, rent always goes back to the creator
+    #[account(mut)]
+    pub closer: Signer<'info>,
+
+    ///paid the challenge and vault rent
+    #[account(mut)]
+    pub creator: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [CHALLENGE_SEED, challenge.service.key().as_ref(), challenge.id.key().as_ref()],
+        bump = challenge.bump,
+        has_one = creator,
+        close = creator,
+    )]
+    pub challenge: Account<'info, Challenge>,
+
+    ///counts the challenge as closed, the service can't close before its challenges
+    #[account(
+        mut,
+        seeds = [SERVICE_SEED, service.id.key().as_ref()],
+        bump = service.bump,
+        address = challenge.service,
+    )]
+    pub service: Account<'info, Service>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, AaasConfig>,
+
+    ///config admin, owns the treasury
+    #[account(address = config.admin)]
+    pub admin: SystemAccount<'info>,
+
+    /// receives anything left in the vault, e.g. tokens sent to it directly
+    #[account(
+        init_if_needed,
+        payer = closer,
+        associated_token::mint = mint,
+        associated_token::authority = admin,
+        associated_token::token_program = token_program
+    )]
+    pub treasury: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    ///writable, as withheld transfer fees are harvested to it
+    #[account(
+        mut,
+        address = challenge.mint @ AaasError::InvalidMint
+    )]
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = challenge
+    )]
+    pub vault: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, token_interface::TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> CloseChallenge<'info> {
+    pub fn handler(&mut self) -> Result<ChallengeClosed> {
+        //a challenge nobody joined has nothing to settle, once its validation period is over
+        let now = Clock::get()?.unix_timestamp as u64;
+        require!(
+            self.challenge.settled
+                || (self.challenge.candidate_count == 0 && now > self.challenge.validation_end()),
+            AaasError::ChallengeNotSettled
+        );
+
+        //challenge is closed last, after every candidate, validation, milestone and sponsorship account
+        require!(
+            self.challenge.closed_count == self.challenge.candidate_count
+                && self.challenge.validation_count == 0
+                && self.challenge.milestone_count == 0
+                && self.challenge.sponsor_count == 0,
+            AaasError::ChallengeHasOpenAccounts
+        );
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            CHALLENGE_SEED,
+            self.challenge.service.as_ref(),
+            self.challenge.id.as_ref(),
+            &[self.challenge.bump],
+        ]];
+
+        //sweep leftovers, so the vault can be closed
+        let swept = self.vault.amount;
+        if swept > 0 {
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    token_interface::TransferChecked {
+                        from: self.vault.to_account_info(),
+                        mint: self.mint.to_account_info(),
+                        to: self.treasury.to_account_info(),
+                        authority: self.challenge.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                swept,
+                self.mint.decimals,
+            )?;
+        }
+
+        //token-2022 doesn't close accounts holding withheld transfer fees, so they are moved to the mint
+        if transfer_fee_config(&self.mint)?.is_some() {
+            token_interface::harvest_withheld_tokens_to_mint(
+                CpiContext::new(
+                    self.token_program.to_account_info(),
+                    token_interface::HarvestWithheldTokensToMint {
+                        token_program_id: self.token_program.to_account_info(),
+                        mint: self.mint.to_account_info(),
+                    },
+                ),
+                vec![self.vault.to_account_info()],
+            )?;
+        }
+
+        //close the vault
+        token_interface::close_account(CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            token_interface::CloseAccount {
+                account: self.vault.to_account_info(),
+                destination: self.creator.to_account_info(),
+                authority: self.challenge.to_account_info(),
+            },
+            signer_seeds,
+        ))?;
+
+        self.service.challenge_count = self.service.challenge_count.checked_sub(1).unwrap();
+        Ok(ChallengeClosed {
+            challenge: self.challenge.key(),
+            service: self.service.key(),
+            swept,
+        })
+    }
+}
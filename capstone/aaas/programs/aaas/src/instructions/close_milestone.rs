@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{CANDIDATE_SEED, CHALLENGE_SEED, MILESTONE_SEED};
+use crate::error::AaasError;
+use crate::{close_account, Challenge, MilestoneClosed};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(index: u8)]
+pub struct CloseMilestone<'info> {
+    ///anyone can crank the close, rent always goes back to the candidate
+    pub closer: Signer<'info>,
+
+    ///paid the milestone proof rent
+    #[account(mut)]
+    pub candidate: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [CHALLENGE_SEED, challenge.service.key().as_ref(), challenge.id.key().as_ref()],
+        bump = challenge.bump,
+        constraint = challenge.settled @ AaasError::ChallengeNotSettled,
+    )]
+    pub challenge: Account<'info, Challenge>,
+
+    /// CHECK: only used as a seed, the candidate account may already be closed
+    #[account(
+        seeds = [CANDIDATE_SEED, challenge.service.key().as_ref(), challenge.key().as_ref(), candidate.key().as_ref()],
+        bump,
+    )]
+    pub candidate_account: UncheckedAccount<'info>,
+
+    /// CHECK: milestone proof created by submit_milestone, seeds and owner are enough to authenticate it
+    #[account(
+        mut,
+        seeds = [MILESTONE_SEED, challenge.key().as_ref(), candidate_account.key().as_ref(), &[index]],
+        bump,
+        owner = crate::ID,
+    )]
+    pub milestone_proof: UncheckedAccount<'info>,
+}
+
+impl<'info> CloseMilestone<'info> {
+    pub fn handler(&mut self, index: u8) -> Result<MilestoneClosed> {
+        close_account(
+            &self.milestone_proof.to_account_info(),
+            &self.candidate.to_account_info(),
+        )?;
+
+        self.challenge.milestone_count = self.challenge.milestone_count.checked_sub(1).unwrap();
+
+        Ok(MilestoneClosed {
+            challenge: self.challenge.key(),
+            candidate: self.candidate.key(),
+            index,
+        })
+    }
+}
@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::SERVICE_SEED;
+use crate::error::AaasError;
+use crate::instructions::execute_proposal::*;
+use crate::{AdminAction, Service, ServiceClosed};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct CloseService<'info> {
+    pub execution: ExecuteProposal<'info>,
+
+    /// every challenge of the service has to be closed first
+    #[account(
+        mut,
+        seeds = [SERVICE_SEED, service.id.key().as_ref()],
+        bump = service.bump,
+        constraint = service.challenge_count == 0 @ AaasError::ServiceHasChallenges,
+    )]
+    pub service: Account<'info, Service>,
+}
+
+impl<'info> CloseService<'info> {
+    pub fn handler(&mut self) -> Result<ServiceClosed> {
+        let AdminAction::CloseService { id } = self.execution.approved_action()? else {
+            return err!(AaasError::ProposalActionMismatch);
+        };
+        require_keys_eq!(id, self.service.id, AaasError::ProposalActionMismatch);
+
+        //the service rent goes back to the proposer, with the proposal's
+        self.service
+            .close(self.execution.proposer.to_account_info())?;
+        Ok(ServiceClosed {
+            service: self.service.key(),
+            id,
+        })
+    }
+}
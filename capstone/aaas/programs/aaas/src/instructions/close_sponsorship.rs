@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{CHALLENGE_SEED, SPONSORSHIP_SEED};
+use crate::error::AaasError;
+use crate::{Challenge, Sponsorship, SponsorshipClosed};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct CloseSponsorship<'info> {
+    ///anyone can crank the close, rent always goes back to the sponsor
+    pub closer: Signer<'info>,
+
+    ///paid the sponsorship account rent
+    #[account(mut)]
+    pub sponsor: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [CHALLENGE_SEED, challenge.service.key().as_ref(), challenge.id.key().as_ref()],
+        bump = challenge.bump,
+        constraint = challenge.settled @ AaasError::ChallengeNotSettled,
+    )]
+    pub challenge: Account<'info, Challenge>,
+
+    #[account(
+        mut,
+        seeds = [SPONSORSHIP_SEED, challenge.key().as_ref(), sponsor.key().as_ref()],
+        bump = sponsorship.bump,
+        has_one = challenge,
+        has_one = sponsor,
+        close = sponsor,
+    )]
+    pub sponsorship: Account<'info, Sponsorship>,
+}
+
+impl<'info> CloseSponsorship<'info> {
+    pub fn handler(&mut self) -> Result<SponsorshipClosed> {
+        //settled challenge either paid the bonus to the winners, or refunded it
+        self.challenge.sponsor_count = self.challenge.sponsor_count.checked_sub(1).unwrap();
+
+        Ok(SponsorshipClosed {
+            challenge: self.challenge.key(),
+            sponsor: self.sponsor.key(),
+        })
+    }
+}
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{CHALLENGE_SEED, VALIDATE_SEED};
+use crate::error::AaasError;
+use crate::{close_account, Challenge, ValidationClosed};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct CloseValidation<'info> {
+    ///anyone can crank the close, rent always goes back to the validator
+    pub closer: Signer<'info>,
+
+    ///paid the validation rent
+    #[account(mut)]
+    pub validator: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [CHALLENGE_SEED, challenge.service.key().as_ref(), challenge.id.key().as_ref()],
+        bump = challenge.bump,
+        constraint = challenge.settled @ AaasError::ChallengeNotSettled,
+    )]
+    pub challenge: Account<'info, Challenge>,
+
+    /// CHECK: only used as a seed, the candidate account may already be closed
+    pub candidate_account: UncheckedAccount<'info>,
+
+    /// CHECK: validation account created by validate_proof, seeds and owner are enough to authenticate it
+    #[account(
+        mut,
+        seeds = [VALIDATE_SEED, challenge.service.key().as_ref(), challenge.key().as_ref(), candidate_account.key().as_ref(), validator.key().as_ref()],
+        bump,
+        owner = crate::ID,
+    )]
+    pub validation: UncheckedAccount<'info>,
+}
+
+impl<'info> CloseValidation<'info> {
+    pub fn handler(&mut self) -> Result<ValidationClosed> {
+        close_account(
+            &self.validation.to_account_info(),
+            &self.validator.to_account_info(),
+        )?;
+
+        self.challenge.validation_count = self.challenge.validation_count.checked_sub(1).unwrap();
+
+        Ok(ValidationClosed {
+            challenge: self.challenge.key(),
+            candidate_account: self.candidate_account.key(),
+            validator: self.validator.key(),
+        })
+    }
+}
@@ -0,0 +1,101 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{CANDIDATE_SEED, CHALLENGE_SEED, DISCRIMINATOR, VALIDATE_SEED};
+use crate::error::AaasError;
+use crate::{CandidateAccount, Challenge, Validation, VoteCommitted};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct CommitVote<'info> {
+    #[account(mut)]
+    pub validator: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CHALLENGE_SEED, challenge.service.key().as_ref(), challenge.id.key().as_ref()],
+        bump = challenge.bump,
+        constraint = !challenge.cancelled @ AaasError::ChallengeCancelled,
+    )]
+    pub challenge: Account<'info, Challenge>,
+
+    /// validates the validator is also a candidate, and its not voting(validating) for himself
+    #[account(
+        seeds = [CANDIDATE_SEED, challenge.service.key().as_ref(), challenge.key().as_ref(), validator.key().as_ref()],
+        bump = validator_account.bump,
+        has_one = challenge,
+        constraint = validator_account.candidate.key() == validator.key(),
+        constraint = validator.key() != candidate_account.candidate.key(),
+    )]
+    pub validator_account: Account<'info, CandidateAccount>,
+
+    /// validates the candidate for which the validator is voting
+    #[account(
+        seeds = [CANDIDATE_SEED, challenge.service.key().as_ref(), challenge.key().as_ref(), candidate_account.candidate.key().as_ref()],
+        bump = candidate_account.bump,
+        has_one = challenge,
+    )]
+    pub candidate_account: Account<'info, CandidateAccount>,
+
+    /// holds the commitment till it is revealed, one per validator and candidate
+    #[account(
+        init,
+        payer = validator,
+        seeds = [VALIDATE_SEED, challenge.service.key().as_ref(), challenge.key().as_ref(), candidate_account.key().as_ref(), validator.key().as_ref()],
+        bump,
+        space = DISCRIMINATOR + Validation::INIT_SPACE
+    )]
+    pub validation: Account<'info, Validation>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CommitVote<'info> {
+    pub fn handler(
+        &mut self,
+        commitment: [u8; 32],
+        proof_hash: [u8; 32],
+        bump: u8,
+    ) -> Result<VoteCommitted> {
+        require!(self.challenge.commit_reveal(), AaasError::OpenVoting);
+
+        //commits are allowed from the challenge start till the commit end
+        let now = Clock::get()?.unix_timestamp as u64;
+        require!(
+            now > self.challenge.start_time,
+            AaasError::ChallengeNotStarted
+        );
+        require!(now < self.challenge.commit_end, AaasError::CommitPhaseEnded);
+
+        //check if the candidate has proof submitted
+        require!(self.candidate_account.has_proof(), AaasError::RequiredProof);
+        //the vote is tied to the evidence the validator reviewed
+        require!(
+            proof_hash == self.candidate_account.proof_hash,
+            AaasError::ProofHashMismatch
+        );
+
+        //validation accounts are counted, so the challenge isn't closed before them
+        self.challenge.validation_count = self.challenge.validation_count.checked_add(1).unwrap();
+        if self.validator_account.profiled {
+            self.challenge.profiled_votes = self.challenge.profiled_votes.checked_add(1).unwrap();
+        }
+
+        //the vote stays hidden till it is revealed
+        self.validation.set_inner(Validation {
+            validator: self.validator.key(),
+            candidate_account: self.candidate_account.key(),
+            vote: None,
+            commitment,
+            proof_hash,
+            tallied: false,
+            bump,
+        });
+
+        Ok(VoteCommitted {
+            challenge: self.challenge.key(),
+            candidate: self.candidate_account.candidate,
+            validator: self.validator.key(),
+            commitment,
+        })
+    }
+}
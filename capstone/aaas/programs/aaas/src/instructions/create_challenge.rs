@@ -2,10 +2,13 @@ use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token_interface;
 
-use crate::constants::{CHALLENGE_SEED, DISCRIMINATOR, SERVICE_SEED};
+use crate::constants::{
+    BASIS_POINTS, CHALLENGE_SEED, DISCRIMINATOR, MAX_CHALLENGE_PROOF_LEN, SERVICE_SEED,
+};
 use crate::error::AaasError;
-use crate::{usdc_mint_value, Challenge, Service};
+use crate::{Challenge, ChallengeCreated, Service, ValidatorIncentives, VoteWeighting};
 
+#[cfg_attr(feature = "event-cpi", event_cpi)]
 #[derive(Accounts)]
 #[instruction(id:Pubkey)]
 pub struct CreateChallenge<'info> {
@@ -13,8 +16,10 @@ pub struct CreateChallenge<'info> {
     pub creator: Signer<'info>,
 
     #[account(
+        mut,
         seeds = [SERVICE_SEED, service.id.key().as_ref()],
         bump = service.bump,
+        constraint = !service.paused @ AaasError::ServicePaused,
     )]
     pub service: Account<'info, Service>,
 
@@ -28,15 +33,14 @@ pub struct CreateChallenge<'info> {
     pub challenge: Account<'info, Challenge>,
 
     #[account(
-        constraint = usdc_mint_value().map_or(true, |expected| expected == usdc_mint.key()) 
-        @ AaasError::InvalidUSDC
+        constraint = service.mints.contains(&mint.key()) @ AaasError::MintNotAllowed
     )]
-    pub usdc_mint: InterfaceAccount<'info, token_interface::Mint>,
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
 
     #[account(
         init,
         payer = creator,
-        associated_token::mint = usdc_mint,
+        associated_token::mint = mint,
         associated_token::authority = challenge,
         associated_token::token_program = token_program
     )]
@@ -48,29 +52,156 @@ pub struct CreateChallenge<'info> {
 }
 
 impl<'info> CreateChallenge<'info> {
+    #[allow(clippy::too_many_arguments)]
     pub fn handler(
         &mut self,
         id: Pubkey,
         start_time: u64,
         end_time: u64,
         stake_amnt: u64,
+        validation_period: u64,
         proof: String,
         service_key: Pubkey,
         winning_threshold: u16,
-        bumps: CreateChallengeBumps,
-    ) -> Result<()> {
+        quorum: u16,
+        weighting: VoteWeighting,
+        incentives: ValidatorIncentives,
+        reveal_period: u64,
+        milestones: Vec<u64>,
+        min_milestones: u8,
+        max_candidates: u32,
+        allowlist_root: Option<[u8; 32]>,
+        bumps: &CreateChallengeBumps,
+    ) -> Result<ChallengeCreated> {
+        //challenge can only be joined before it starts, so it has to start in the future
+        let now = Clock::get()?.unix_timestamp as u64;
+        require!(start_time > now, AaasError::InvalidStartTime);
+        require!(end_time > start_time, AaasError::InvalidEndTime);
+
+        //duration has to be within the service limits
+        let duration = end_time - start_time;
+        let limits = &self.service.limits;
+        require!(
+            duration >= limits.min_duration,
+            AaasError::ChallengeTooShort
+        );
+        require!(duration <= limits.max_duration, AaasError::ChallengeTooLong);
+        require!(
+            validation_period >= limits.min_validation_period,
+            AaasError::ValidationPeriodTooShort
+        );
+        require!(
+            validation_period <= limits.max_validation_period,
+            AaasError::ValidationPeriodTooLong
+        );
+
+        require!(stake_amnt > 0, AaasError::InvalidStakeAmount);
+        require!(max_candidates > 0, AaasError::InvalidMaxCandidates);
+        require!(
+            winning_threshold > 0 && winning_threshold as u64 <= BASIS_POINTS,
+            AaasError::InvalidWinningThreshold
+        );
+        require!(
+            quorum > 0 && quorum as u64 <= BASIS_POINTS,
+            AaasError::InvalidQuorum
+        );
+        //commit-reveal voting reveals in the last reveal_period seconds of the validation period,
+        //and commits from the challenge start till then
+        let validation_end = end_time.checked_add(validation_period).unwrap();
+        let (commit_end, reveal_end) = if reveal_period > 0 {
+            require!(
+                reveal_period < validation_end - start_time,
+                AaasError::InvalidRevealPeriod
+            );
+            (validation_end - reveal_period, validation_end)
+        } else {
+            (0, 0)
+        };
+
+        //milestone proofs are voted openly, and aren't tallied for validator incentives
+        Challenge::validate_milestones(&milestones, min_milestones, start_time, end_time)?;
+        require!(
+            milestones.is_empty() || (reveal_period == 0 && !incentives.enabled()),
+            AaasError::MilestonesUnsupported
+        );
+
+        //attestations replace peer votes, so there is nothing to commit, tally nor vote per milestone
+        let attestor = self.service.attestor;
+        require!(
+            attestor.is_none()
+                || (reveal_period == 0 && !incentives.enabled() && milestones.is_empty()),
+            AaasError::AttestorUnsupported
+        );
+
+        //penalties are taken from the stake along with the forfeit fee, so both must fit in it
+        incentives.validate(self.service.forfeit_fee)?;
+        require!(
+            proof.len() <= MAX_CHALLENGE_PROOF_LEN,
+            AaasError::ProofTooLong
+        );
+
+        self.service.challenge_count = self.service.challenge_count.checked_add(1).unwrap();
         self.challenge.set_inner(Challenge {
             id,
+            creator: self.creator.key(),
+            mint: self.mint.key(),
             start_time,
             end_time,
             stake_amnt,
+            weighting,
+            commit_end,
+            reveal_end,
+            total_weight: 0,
+            total_stake: 0,
+            winner_stake: 0,
+            validation_period,
             proof,
             service: service_key,
             bump: bumps.challenge,
             candidate_count: 0,
+            max_candidates,
             winner_count: 0,
+            finalized_count: 0,
+            finalized: false,
+            rewarded_count: 0,
+            distributed: 0,
+            settled: false,
+            validation_count: 0,
+            closed_count: 0,
             winning_threshold,
+            quorum,
+            fee: self.service.fee,
+            forfeit_fee: self.service.forfeit_fee,
+            refunded_count: 0,
+            incentives,
+            tallied_count: 0,
+            profiled_votes: 0,
+            penalty_pool: 0,
+            eligible_count: 0,
+            validator_rewarded_count: 0,
+            validator_distributed: 0,
+            milestones,
+            min_milestones,
+            milestone_count: 0,
+            attestor,
+            allowlist_root,
+            bonus_pool: 0,
+            sponsor_count: 0,
+            sponsor_refunded_count: 0,
+            cancelled: false,
         });
-        Ok(())
+        Ok(ChallengeCreated {
+            challenge: self.challenge.key(),
+            service: service_key,
+            creator: self.creator.key(),
+            mint: self.mint.key(),
+            start_time,
+            end_time,
+            stake_amnt,
+            validation_period,
+            winning_threshold,
+            quorum,
+            max_candidates,
+        })
     }
 }
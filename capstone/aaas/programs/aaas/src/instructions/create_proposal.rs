@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{CONFIG_SEED, DISCRIMINATOR, PROPOSAL_LIFETIME, PROPOSAL_SEED};
+use crate::error::AaasError;
+use crate::{AaasConfig, AdminAction, Proposal, ProposalCreated};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(id: Pubkey)]
+pub struct CreateProposal<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.signers.contains(&proposer.key()) @ AaasError::NotASigner,
+    )]
+    pub config: Account<'info, AaasConfig>,
+
+    #[account(
+        init,
+        payer = proposer,
+        seeds = [PROPOSAL_SEED, id.key().as_ref()],
+        bump,
+        space = DISCRIMINATOR + Proposal::INIT_SPACE,
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateProposal<'info> {
+    pub fn handler(
+        &mut self,
+        id: Pubkey,
+        action: AdminAction,
+        bumps: &CreateProposalBumps,
+    ) -> Result<ProposalCreated> {
+        //proposer approves its own proposal
+        let now = Clock::get()?.unix_timestamp as u64;
+        self.proposal.set_inner(Proposal {
+            id,
+            proposer: self.proposer.key(),
+            action: action.clone(),
+            approvals: vec![self.proposer.key()],
+            expires_at: now + PROPOSAL_LIFETIME,
+            bump: bumps.proposal,
+        });
+        Ok(ProposalCreated {
+            proposal: self.proposal.key(),
+            id,
+            proposer: self.proposer.key(),
+            action,
+            expires_at: self.proposal.expires_at,
+        })
+    }
+}
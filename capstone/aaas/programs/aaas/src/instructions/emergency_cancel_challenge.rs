@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::CHALLENGE_SEED;
+use crate::error::AaasError;
+use crate::instructions::execute_proposal::*;
+use crate::{AdminAction, Challenge, ChallengeCancelled};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct EmergencyCancelChallenge<'info> {
+    pub execution: ExecuteProposal<'info>,
+
+    #[account(
+        mut,
+        seeds = [CHALLENGE_SEED, challenge.service.key().as_ref(), challenge.id.key().as_ref()],
+        bump = challenge.bump,
+    )]
+    pub challenge: Account<'info, Challenge>,
+}
+
+impl<'info> EmergencyCancelChallenge<'info> {
+    pub fn handler(&mut self) -> Result<ChallengeCancelled> {
+        let AdminAction::CancelChallenge { challenge } = self.execution.approved_action()? else {
+            return err!(AaasError::ProposalActionMismatch);
+        };
+        require_keys_eq!(
+            challenge,
+            self.challenge.key(),
+            AaasError::ProposalActionMismatch
+        );
+
+        //the multisig can cancel at any time, till the first payout
+        self.challenge.cancel()?;
+
+        Ok(ChallengeCancelled {
+            challenge: self.challenge.key(),
+            emergency: true,
+        })
+    }
+}
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{CONFIG_SEED, PROPOSAL_SEED};
+use crate::{AaasConfig, AdminAction, Proposal};
+
+///accounts shared by every instruction executing a proposal, nested as `execution`
+#[derive(Accounts)]
+pub struct ExecuteProposal<'info> {
+    #[account(mut)]
+    pub executor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, AaasConfig>,
+
+    /// the executed proposal is closed, and its rent goes back to the proposer
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, proposal.id.key().as_ref()],
+        bump = proposal.bump,
+        has_one = proposer,
+        close = proposer,
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(mut)]
+    pub proposer: SystemAccount<'info>,
+}
+
+impl<'info> ExecuteProposal<'info> {
+    ///the stored action, once the proposal reached the threshold and hasn't expired
+    pub fn approved_action(&self) -> Result<AdminAction> {
+        let now = Clock::get()?.unix_timestamp as u64;
+        self.proposal.require_approved(&self.config, now)?;
+        Ok(self.proposal.action.clone())
+    }
+}
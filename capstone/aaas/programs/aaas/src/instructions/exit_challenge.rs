@@ -1,13 +1,12 @@
-use std::ops::SubAssign;
-
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token_interface;
 
-use crate::constants::{CANDIDATE_SEED, CHALLENGE_SEED};
+use crate::constants::{CANDIDATE_SEED, CHALLENGE_SEED, PROFILE_SEED};
 use crate::error::AaasError;
-use crate::{usdc_mint_value, CandidateAccount, Challenge};
+use crate::{CandidateAccount, CandidateExited, Challenge, Profile};
 
+#[cfg_attr(feature = "event-cpi", event_cpi)]
 #[derive(Accounts)]
 pub struct ExitChallenge<'info> {
     #[account(mut)]
@@ -31,23 +30,30 @@ pub struct ExitChallenge<'info> {
         close = candidate,
     )]
     pub candidate_account: Account<'info, CandidateAccount>,
-    
+
+    ///candidate history, required when the candidate joined with it, left untouched otherwise
+    #[account(
+        mut,
+        seeds = [PROFILE_SEED, candidate.key().as_ref()],
+        bump = profile.bump,
+    )]
+    pub profile: Option<Account<'info, Profile>>,
+
     #[account(
-        constraint = usdc_mint_value().map_or(true, |expected| expected == usdc_mint.key()) 
-        @ AaasError::InvalidUSDC
+        address = challenge.mint @ AaasError::InvalidMint
     )]
-    pub usdc_mint: InterfaceAccount<'info, token_interface::Mint>,
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
 
     #[account(
         mut,
-        associated_token::mint = usdc_mint,
+        associated_token::mint = mint,
         associated_token::authority = challenge,
     )]
     pub vault: InterfaceAccount<'info, token_interface::TokenAccount>,
 
     #[account(
         mut,
-        associated_token::mint = usdc_mint,
+        associated_token::mint = mint,
         associated_token::authority = candidate,
     )]
     pub candidate_ata: InterfaceAccount<'info, token_interface::TokenAccount>,
@@ -58,18 +64,24 @@ pub struct ExitChallenge<'info> {
 }
 
 impl<'info> ExitChallenge<'info> {
-    pub fn handler(&mut self) -> Result<()> {
+    pub fn handler(&mut self) -> Result<CandidateExited> {
         //check if the challenge has started
         let now = Clock::get()?.unix_timestamp as u64;
         require!(now < self.challenge.start_time, AaasError::ChallengeStarted);
+        //a cancelled challenge is only left with refund_stake, so the stake isn't paid twice
+        require!(!self.challenge.cancelled, AaasError::ChallengeCancelled);
+        require!(
+            !self.candidate_account.profiled || self.profile.is_some(),
+            AaasError::ProfileRequired
+        );
 
-        //transfer back the stake amnt
+        //transfer back the stake
         token_interface::transfer_checked(
             CpiContext::new_with_signer(
                 self.token_program.to_account_info(),
                 token_interface::TransferChecked {
                     from: self.vault.to_account_info(),
-                    mint: self.usdc_mint.to_account_info(),
+                    mint: self.mint.to_account_info(),
                     to: self.candidate_ata.to_account_info(),
                     authority: self.challenge.to_account_info(),
                 },
@@ -80,13 +92,35 @@ impl<'info> ExitChallenge<'info> {
                     &[self.challenge.bump],
                 ]],
             ),
-            self.challenge.stake_amnt,
-            self.usdc_mint.decimals,
+            self.candidate_account.stake,
+            self.mint.decimals,
         )?;
 
         //update candidate_count in challenge
-        self.challenge.candidate_count.sub_assign(1);
+        self.challenge.candidate_count = self.challenge.candidate_count.checked_sub(1).unwrap();
+        self.challenge.total_weight = self
+            .challenge
+            .total_weight
+            .checked_sub(self.candidate_account.weight)
+            .unwrap();
+        self.challenge.total_stake = self
+            .challenge
+            .total_stake
+            .checked_sub(self.candidate_account.stake)
+            .unwrap();
+        if let Some(profile) = self
+            .profile
+            .as_mut()
+            .filter(|_| self.candidate_account.profiled)
+        {
+            profile.record_exit();
+        }
 
-        Ok(())
+        Ok(CandidateExited {
+            challenge: self.challenge.key(),
+            candidate: self.candidate.key(),
+            stake: self.candidate_account.stake,
+            candidate_count: self.challenge.candidate_count,
+        })
     }
 }
@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::CHALLENGE_SEED;
+use crate::error::AaasError;
+use crate::{CandidateAccount, Challenge, ChallengeFinalized};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct FinalizeChallenge<'info> {
+    ///anyone can crank the finalization
+    pub cranker: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CHALLENGE_SEED, challenge.service.key().as_ref(), challenge.id.key().as_ref()],
+        bump = challenge.bump,
+        constraint = !challenge.finalized @ AaasError::ChallengeFinalized,
+        constraint = !challenge.cancelled @ AaasError::ChallengeCancelled,
+    )]
+    pub challenge: Account<'info, Challenge>,
+}
+
+impl<'info> FinalizeChallenge<'info> {
+    /// records the final result of the candidate accounts passed as remaining accounts,
+    /// can be cranked in batches till every candidate is finalized
+    pub fn handler(
+        &mut self,
+        candidate_accounts: &[AccountInfo],
+    ) -> Result<Option<ChallengeFinalized>> {
+        //votes are only final once the validation period is over
+        let now = Clock::get()?.unix_timestamp as u64;
+        require!(
+            now > self.challenge.validation_end(),
+            AaasError::ValidationPeriod
+        );
+
+        for acc in candidate_accounts {
+            require!(
+                acc.owner == &crate::ID && acc.is_writable,
+                AaasError::InvalidCandidateAccount
+            );
+            let mut data = acc.try_borrow_mut_data()?;
+            let mut candidate = CandidateAccount::try_deserialize(&mut &data[..])?;
+            require!(
+                candidate.challenge == self.challenge.key(),
+                AaasError::InvalidCandidateAccount
+            );
+            require!(!candidate.finalized, AaasError::CandidateFinalized);
+
+            //candidate count is frozen once the challenge started, so the tallies are final
+            candidate.winner = self.challenge.candidate_wins(&candidate)?;
+            candidate.finalized = true;
+            if candidate.winner {
+                self.challenge.winner_count = self.challenge.winner_count.checked_add(1).unwrap();
+                self.challenge.winner_stake = self
+                    .challenge
+                    .winner_stake
+                    .checked_add(candidate.stake)
+                    .unwrap();
+            }
+            candidate.try_serialize(&mut &mut data[..])?;
+
+            self.challenge.finalized_count = self.challenge.finalized_count.checked_add(1).unwrap();
+        }
+
+        //the winner set is final once every candidate is accounted for
+        self.challenge.finalized = self.challenge.finalized_count == self.challenge.candidate_count;
+
+        Ok(self.challenge.finalized.then(|| ChallengeFinalized {
+            challenge: self.challenge.key(),
+            winner_count: self.challenge.winner_count,
+            winner_stake: self.challenge.winner_stake,
+        }))
+    }
+}
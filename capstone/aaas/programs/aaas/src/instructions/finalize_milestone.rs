@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{CANDIDATE_SEED, CHALLENGE_SEED, MILESTONE_SEED};
+use crate::error::AaasError;
+use crate::{CandidateAccount, Challenge, MilestoneFinalized, MilestoneProof};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct FinalizeMilestone<'info> {
+    ///anyone can crank the finalization
+    pub cranker: Signer<'info>,
+
+    #[account(
+        seeds = [CHALLENGE_SEED, challenge.service.key().as_ref(), challenge.id.key().as_ref()],
+        bump = challenge.bump,
+        constraint = !challenge.cancelled @ AaasError::ChallengeCancelled,
+    )]
+    pub challenge: Account<'info, Challenge>,
+
+    #[account(
+        mut,
+        seeds = [CANDIDATE_SEED, challenge.service.key().as_ref(), challenge.key().as_ref(), candidate_account.candidate.key().as_ref()],
+        bump = candidate_account.bump,
+        has_one = challenge,
+    )]
+    pub candidate_account: Account<'info, CandidateAccount>,
+
+    #[account(
+        mut,
+        seeds = [MILESTONE_SEED, challenge.key().as_ref(), candidate_account.key().as_ref(), &[milestone_proof.index]],
+        bump = milestone_proof.bump,
+        has_one = candidate_account,
+        constraint = !milestone_proof.finalized @ AaasError::MilestoneFinalized,
+    )]
+    pub milestone_proof: Account<'info, MilestoneProof>,
+}
+
+impl<'info> FinalizeMilestone<'info> {
+    pub fn handler(&mut self) -> Result<MilestoneFinalized> {
+        //votes on a milestone are final once its validation period is over
+        let (_, end) = self
+            .challenge
+            .milestone_window(self.milestone_proof.index)?;
+        let now = Clock::get()?.unix_timestamp as u64;
+        require!(
+            now > end.checked_add(self.challenge.validation_period).unwrap(),
+            AaasError::ValidationPeriod
+        );
+
+        //a milestone passes with the same threshold and quorum as a proof
+        let passed = self.challenge.is_winning(
+            self.candidate_account.weight,
+            self.milestone_proof.acceptance,
+            self.milestone_proof.rejections,
+        );
+        self.milestone_proof.passed = passed;
+        self.milestone_proof.finalized = true;
+
+        let candidate = &mut self.candidate_account;
+        candidate.milestones_finalized = candidate.milestones_finalized.checked_add(1).unwrap();
+        if passed {
+            candidate.milestones_passed = candidate.milestones_passed.checked_add(1).unwrap();
+        }
+
+        Ok(MilestoneFinalized {
+            challenge: self.challenge.key(),
+            candidate: candidate.candidate,
+            index: self.milestone_proof.index,
+            passed,
+            milestones_passed: candidate.milestones_passed,
+        })
+    }
+}
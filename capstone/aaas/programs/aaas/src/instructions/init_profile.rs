@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{DISCRIMINATOR, PROFILE_SEED};
+use crate::{Profile, ProfileInitialized};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct InitProfile<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        init,
+        payer = owner,
+        seeds = [PROFILE_SEED, owner.key().as_ref()],
+        bump,
+        space = DISCRIMINATOR + Profile::INIT_SPACE,
+    )]
+    pub profile: Account<'info, Profile>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitProfile<'info> {
+    pub fn handler(&mut self, bumps: &InitProfileBumps) -> Result<ProfileInitialized> {
+        self.profile.set_inner(Profile {
+            owner: self.owner.key(),
+            reputation: 0,
+            challenges_joined: 0,
+            challenges_won: 0,
+            challenges_lost: 0,
+            proofs_validated: 0,
+            votes_agreed: 0,
+            bump: bumps.profile,
+        });
+        Ok(ProfileInitialized {
+            profile: self.profile.key(),
+            owner: self.owner.key(),
+        })
+    }
+}
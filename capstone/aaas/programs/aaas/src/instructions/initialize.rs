@@ -1,13 +1,11 @@
-use std::ops::AddAssign;
-
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token_interface;
 
 use crate::constants::{CONFIG_SEED, DISCRIMINATOR};
-use crate::error::AaasError;
-use crate::{usdc_mint_value, AaasConfig};
+use crate::{AaasConfig, ConfigInitialized};
 
+#[cfg_attr(feature = "event-cpi", event_cpi)]
 #[derive(Accounts)]
 ///Global config to govern all the services
 pub struct Initialize<'info> {
@@ -26,16 +24,12 @@ pub struct Initialize<'info> {
     #[account(
         init_if_needed,
         payer = admin,
-        associated_token::mint = usdc_mint,
+        associated_token::mint = mint,
         associated_token::authority = admin,
     )]
     pub treasury: InterfaceAccount<'info, token_interface::TokenAccount>,
 
-    #[account(
-        constraint = usdc_mint_value().map_or(true, |expected| expected == usdc_mint.key()) 
-        @ AaasError::InvalidUSDC
-    )]
-    pub usdc_mint: InterfaceAccount<'info, token_interface::Mint>,
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
 
     pub system_program: Program<'info, System>,
     pub token_program: Interface<'info, token_interface::TokenInterface>,
@@ -47,29 +41,23 @@ impl<'info> Initialize<'info> {
         &mut self,
         signers: Vec<Pubkey>,
         threshold: u8,
-        bumps: InitializeBumps,
-        remaining_accounts: &[AccountInfo],
-    ) -> Result<()> {
+        bumps: &InitializeBumps,
+    ) -> Result<ConfigInitialized> {
         msg!("Welcome to Aaas!");
 
-        //verify the multi sig
-        let mut sign_cnt = 0u8;
-        for acc in remaining_accounts {
-            msg!("is_signer:{}", acc.is_signer);
-            if acc.is_signer && signers.contains(&acc.key()) {
-                sign_cnt.add_assign(1);
-            }
-        }
-        //check for signer threshold
-        require!(sign_cnt >= threshold, AaasError::MutliSignerThreshold);
-        msg!("sign_cnt: {}, threshold: {}", sign_cnt, threshold);
+        //signers don't co-sign here, later admin actions go through proposals
+        AaasConfig::validate_signers(&signers, threshold)?;
 
         self.config.set_inner(AaasConfig {
-            signers,
+            signers: signers.clone(),
             threshold,
             bump: bumps.config,
             admin: self.admin.key(),
         });
-        Ok(())
+        Ok(ConfigInitialized {
+            admin: self.admin.key(),
+            signers,
+            threshold,
+        })
     }
 }
@@ -1,63 +1,71 @@
-use std::ops::AddAssign;
-
 use anchor_lang::prelude::*;
 
-use crate::constants::{CONFIG_SEED, SERVICE_SEED};
+use crate::constants::SERVICE_SEED;
 use crate::error::AaasError;
-use crate::{AaasConfig, Service};
+use crate::instructions::execute_proposal::*;
+use crate::{AdminAction, Service, ServiceInitialized};
 
+#[cfg_attr(feature = "event-cpi", event_cpi)]
 #[derive(Accounts)]
 #[instruction(id: Pubkey)]
 pub struct InitService<'info> {
-    #[account(mut)]
-    pub initializer: Signer<'info>,
+    pub execution: ExecuteProposal<'info>,
 
+    /// `id` only derives the service address, it has to match the proposed one
     #[account(
         init,
-        payer = initializer,
+        payer = execution.executor,
         seeds = [SERVICE_SEED, id.key().as_ref()],
         bump,
         space = 8 + Service::INIT_SPACE,
     )]
     pub service: Account<'info, Service>,
 
-    #[account(
-        seeds = [CONFIG_SEED],
-        bump = config.bump
-    )]
-    pub config: Account<'info, AaasConfig>,
-
     pub system_program: Program<'info, System>,
 }
 
 impl<'info> InitService<'info> {
     pub fn handler(
         &mut self,
-        id: Pubkey,
-        fee: u16,
-        bumps: InitServiceBumps,
-        remaining_accounts: &[AccountInfo],
-    ) -> Result<()> {
-        //confirm the multi sig
-        //count the signers
-        let mut signer_cnt = 0u8;
-        for acc in remaining_accounts {
-            if acc.is_signer && self.config.signers.contains(&acc.key()) {
-                signer_cnt.add_assign(1);
-            }
-        }
-        //check if signer reache threshold
-        require!(
-            signer_cnt >= self.config.threshold,
-            AaasError::MutliSignerThreshold
-        );
+        service_id: Pubkey,
+        bumps: &InitServiceBumps,
+    ) -> Result<ServiceInitialized> {
+        //confirm the multi sig, the service is created from the proposed values
+        let AdminAction::CreateService {
+            id,
+            fee,
+            forfeit_fee,
+            limits,
+            mints,
+        } = self.execution.approved_action()?
+        else {
+            return err!(AaasError::ProposalActionMismatch);
+        };
+        require_keys_eq!(id, service_id, AaasError::ProposalActionMismatch);
+
+        Service::validate_fee(fee)?;
+        Service::validate_fee(forfeit_fee)?;
+        limits.validate()?;
+        Service::validate_mints(&mints)?;
 
         //set the service account
         self.service.set_inner(Service {
             id,
             bump: bumps.service,
             fee,
+            forfeit_fee,
+            paused: false,
+            limits,
+            mints: mints.clone(),
+            attestor: None,
+            challenge_count: 0,
         });
-        Ok(())
+        Ok(ServiceInitialized {
+            service: self.service.key(),
+            id,
+            fee,
+            forfeit_fee,
+            mints,
+        })
     }
 }
@@ -1,12 +1,14 @@
-use std::ops::AddAssign;
-
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface;
 
-use crate::constants::{CANDIDATE_SEED, CHALLENGE_SEED, DISCRIMINATOR};
+use crate::constants::{CANDIDATE_SEED, CHALLENGE_SEED, DISCRIMINATOR, PROFILE_SEED, SERVICE_SEED};
 use crate::error::AaasError;
-use crate::{usdc_mint_value, CandidateAccount, Challenge};
+use crate::{
+    gross_up_transfer_fee, verify_allowlist_proof, CandidateAccount, CandidateJoined, Challenge,
+    Profile, Service, VoteWeighting,
+};
 
+#[cfg_attr(feature = "event-cpi", event_cpi)]
 #[derive(Accounts)]
 pub struct JoinChallenge<'info> {
     #[account(mut)]
@@ -16,10 +18,19 @@ pub struct JoinChallenge<'info> {
     #[account(
         mut,
         seeds = [CHALLENGE_SEED, challenge.service.key().as_ref(), challenge.id.key().as_ref()],
-        bump = challenge.bump
+        bump = challenge.bump,
+        has_one = service,
+        constraint = !challenge.cancelled @ AaasError::ChallengeCancelled,
     )]
     pub challenge: Account<'info, Challenge>,
 
+    #[account(
+        seeds = [SERVICE_SEED, service.id.key().as_ref()],
+        bump = service.bump,
+        constraint = !service.paused @ AaasError::ServicePaused,
+    )]
+    pub service: Account<'info, Service>,
+
     ///anchor can't auto derive the pda, as this will be done in depth 3
     #[account(
         init,
@@ -30,15 +41,22 @@ pub struct JoinChallenge<'info> {
     )]
     pub candidate_account: Account<'info, CandidateAccount>,
 
+    ///candidate history, its reputation weighs votes in reputation weighted challenges
     #[account(
-        constraint = usdc_mint_value().map_or(true, |expected| expected == usdc_mint.key()) 
-        @ AaasError::InvalidUSDC
+        mut,
+        seeds = [PROFILE_SEED, candidate.key().as_ref()],
+        bump = profile.bump,
     )]
-    pub usdc_mint: InterfaceAccount<'info, token_interface::Mint>,
+    pub profile: Option<Account<'info, Profile>>,
+
+    #[account(
+        address = challenge.mint @ AaasError::InvalidMint
+    )]
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
 
     #[account(
         mut,
-        associated_token::mint = usdc_mint,
+        associated_token::mint = mint,
         associated_token::authority = challenge,
         associated_token::token_program = token_program
     )]
@@ -46,7 +64,7 @@ pub struct JoinChallenge<'info> {
 
     #[account(
         mut,
-        associated_token::mint = usdc_mint,
+        associated_token::mint = mint,
         associated_token::authority = candidate,
         associated_token::token_program = token_program
     )]
@@ -57,37 +75,107 @@ pub struct JoinChallenge<'info> {
 }
 
 impl<'info> JoinChallenge<'info> {
-    pub fn handler(&mut self, bump: u8) -> Result<()> {
+    pub fn handler(
+        &mut self,
+        stake: u64,
+        allowlist_proof: Vec<[u8; 32]>,
+        bump: u8,
+    ) -> Result<CandidateJoined> {
         //check if challenge already started
         let now = Clock::get()?.unix_timestamp as u64;
         require!(now < self.challenge.start_time, AaasError::ChallengeStarted);
-        //transfer stake amnt from candidate ata to vault
+        require!(
+            self.challenge.candidate_count < self.challenge.max_candidates,
+            AaasError::ChallengeFull
+        );
+        //private challenges only accept the candidates in the allowlist
+        if let Some(root) = self.challenge.allowlist_root {
+            require!(
+                verify_allowlist_proof(&allowlist_proof, root, &self.candidate.key()),
+                AaasError::NotAllowlisted
+            );
+        }
+        //only stake weighted challenges accept more than the stake amnt
+        match self.challenge.weighting {
+            VoteWeighting::Stake => require!(
+                stake >= self.challenge.stake_amnt,
+                AaasError::InvalidStakeAmount
+            ),
+            _ => require!(
+                stake == self.challenge.stake_amnt,
+                AaasError::InvalidStakeAmount
+            ),
+        }
+        //transfer stake from candidate ata to vault, candidate pays the transfer fee if the mint has one
+        let vault_before = self.vault.amount;
         token_interface::transfer_checked(
             CpiContext::new(
                 self.token_program.to_account_info(),
                 token_interface::TransferChecked {
                     from: self.candidate_ata.to_account_info(),
-                    mint: self.usdc_mint.to_account_info(),
+                    mint: self.mint.to_account_info(),
                     to: self.vault.to_account_info(),
                     authority: self.candidate.to_account_info(),
                 },
             ),
-            self.challenge.stake_amnt,
-            self.usdc_mint.decimals,
+            gross_up_transfer_fee(&self.mint, stake)?,
+            self.mint.decimals,
         )?;
+        //the fee is rounded up, so the vault may get a bit more, payouts are accounted with what it got
+        self.vault.reload()?;
+        let received = self.vault.amount.checked_sub(vault_before).unwrap();
+        require!(received >= stake, AaasError::UnexpectedVaultAmount);
+        let stake = received;
+        //a missing profile counts as no reputation
+        let reputation = self.profile.as_ref().map_or(0, |p| p.reputation);
+        require!(
+            reputation >= self.service.limits.min_reputation,
+            AaasError::InsufficientReputation
+        );
+        let weight = self.challenge.candidate_weight(stake, reputation);
+
         //initialize candidate account
         self.candidate_account.set_inner(CandidateAccount {
             challenge: self.challenge.key(),
             proof: "".to_string(),
+            proof_hash: [0u8; 32],
             candidate: self.candidate.key(),
-            acceptance: 0u16,
+            acceptance: 0u64,
+            rejections: 0u64,
+            stake,
+            weight,
+            finalized: false,
+            winner: false,
+            votes_cast: 0,
+            votes_tallied: 0,
+            votes_agreed: 0,
+            penalized: false,
+            eligible: false,
+            validator_rewarded: false,
+            milestones_submitted: 0,
+            milestones_finalized: 0,
+            milestones_passed: 0,
             bump,
             rewarded: false,
+            refunded: false,
+            attestation: None,
+            profiled: self.profile.is_some(),
         });
+        if let Some(profile) = self.profile.as_mut() {
+            profile.record_join();
+        }
 
         //update candidate count in challenge
-        self.challenge.candidate_count.add_assign(1);
+        self.challenge.candidate_count = self.challenge.candidate_count.checked_add(1).unwrap();
+        self.challenge.total_weight = self.challenge.total_weight.checked_add(weight).unwrap();
+        self.challenge.total_stake = self.challenge.total_stake.checked_add(stake).unwrap();
 
-        Ok(())
+        Ok(CandidateJoined {
+            challenge: self.challenge.key(),
+            candidate: self.candidate.key(),
+            stake,
+            weight,
+            candidate_count: self.challenge.candidate_count,
+        })
     }
 }
@@ -1,17 +1,91 @@
+pub mod add_signer;
+pub mod approve_proposal;
+pub mod attest_result;
+pub mod cancel_challenge;
+pub mod cancel_proposal;
+pub mod claim_badge;
+pub mod claim_validator_reward;
+pub mod close_candidate;
+pub mod close_challenge;
+pub mod close_milestone;
+pub mod close_service;
+pub mod close_sponsorship;
+pub mod close_validation;
+pub mod commit_vote;
 pub mod create_challenge;
+pub mod create_proposal;
+pub mod emergency_cancel_challenge;
+pub mod execute_proposal;
 pub mod initialize;
+pub mod init_profile;
+pub mod finalize_challenge;
+pub mod finalize_milestone;
 pub mod initialize_service;
 pub mod join_challenge;
+pub mod pause_service;
+pub mod refund_sponsorship;
+pub mod refund_stake;
+pub mod remove_signer;
+pub mod reveal_vote;
+pub mod revoke_approval;
+pub mod rotate_signers;
 pub mod exit_challenge;
+pub mod set_service_attestor;
+pub mod set_service_mints;
+pub mod set_threshold;
+pub mod sponsor_challenge;
+pub mod submit_milestone;
 pub mod submit_proof;
+pub mod tally_vote;
+pub mod transfer_admin;
+pub mod update_service_fee;
+pub mod update_service_limits;
+pub mod validate_milestone;
 pub mod validate_proof;
 pub mod withdraw_reward;
 
 pub use initialize::*;
 pub use initialize_service::*;
 pub use create_challenge::*;
+pub use init_profile::*;
 pub use join_challenge::*;
 pub use exit_challenge::*;
+pub use sponsor_challenge::*;
+pub use cancel_challenge::*;
+pub use emergency_cancel_challenge::*;
 pub use submit_proof::*;
 pub use validate_proof::*;
+pub use commit_vote::*;
+pub use reveal_vote::*;
+pub use attest_result::*;
+pub use submit_milestone::*;
+pub use validate_milestone::*;
+pub use finalize_milestone::*;
+pub use finalize_challenge::*;
+pub use tally_vote::*;
+pub use claim_validator_reward::*;
 pub use withdraw_reward::*;
+pub use claim_badge::*;
+pub use refund_stake::*;
+pub use refund_sponsorship::*;
+pub use close_validation::*;
+pub use close_candidate::*;
+pub use close_milestone::*;
+pub use close_sponsorship::*;
+pub use close_challenge::*;
+pub use create_proposal::*;
+pub use approve_proposal::*;
+pub use revoke_approval::*;
+pub use cancel_proposal::*;
+pub use execute_proposal::*;
+pub use update_service_fee::*;
+pub use update_service_limits::*;
+pub use set_service_mints::*;
+pub use set_service_attestor::*;
+pub use rotate_signers::*;
+pub use add_signer::*;
+pub use remove_signer::*;
+pub use set_threshold::*;
+pub use transfer_admin::*;
+pub use pause_service::*;
+pub use close_service::*;
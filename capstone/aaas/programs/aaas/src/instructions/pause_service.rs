@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::SERVICE_SEED;
+use crate::error::AaasError;
+use crate::instructions::execute_proposal::*;
+use crate::{AdminAction, Service, ServicePaused};
+
+///used by both pause_service and resume_service
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct PauseService<'info> {
+    pub execution: ExecuteProposal<'info>,
+
+    #[account(
+        mut,
+        seeds = [SERVICE_SEED, service.id.key().as_ref()],
+        bump = service.bump,
+    )]
+    pub service: Account<'info, Service>,
+}
+
+impl<'info> PauseService<'info> {
+    pub fn handler(&mut self, paused: bool) -> Result<ServicePaused> {
+        let id = match self.execution.approved_action()? {
+            AdminAction::PauseService { id } if paused => id,
+            AdminAction::ResumeService { id } if !paused => id,
+            _ => return err!(AaasError::ProposalActionMismatch),
+        };
+        require_keys_eq!(id, self.service.id, AaasError::ProposalActionMismatch);
+
+        require!(
+            self.service.paused != paused,
+            AaasError::ServiceStateUnchanged
+        );
+
+        //running challenges aren't affected, only creating and joining are blocked
+        self.service.paused = paused;
+        Ok(ServicePaused {
+            service: self.service.key(),
+            paused,
+        })
+    }
+}
@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface;
+
+use crate::constants::{CHALLENGE_SEED, SPONSORSHIP_SEED};
+use crate::error::AaasError;
+use crate::{Challenge, Sponsorship, SponsorshipRefunded};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct RefundSponsorship<'info> {
+    pub sponsor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SPONSORSHIP_SEED, challenge.key().as_ref(), sponsor.key().as_ref()],
+        bump = sponsorship.bump,
+        has_one = challenge,
+        has_one = sponsor,
+    )]
+    pub sponsorship: Account<'info, Sponsorship>,
+
+    #[account(
+        mut,
+        seeds = [CHALLENGE_SEED, challenge.service.as_ref(), challenge.id.key().as_ref()],
+        bump = challenge.bump,
+    )]
+    pub challenge: Account<'info, Challenge>,
+
+    #[account(
+        address = challenge.mint @ AaasError::InvalidMint
+    )]
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = challenge
+    )]
+    pub vault: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = sponsor,
+    )]
+    pub sponsor_ata: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    pub token_program: Interface<'info, token_interface::TokenInterface>,
+}
+
+impl<'info> RefundSponsorship<'info> {
+    pub fn handler(&mut self) -> Result<SponsorshipRefunded> {
+        //the bonus only goes back when nobody won, otherwise it's shared by the winners
+        if !self.challenge.cancelled {
+            require!(self.challenge.finalized, AaasError::ChallengeNotFinalized);
+            require!(
+                self.challenge.winner_count == 0,
+                AaasError::ChallengeHasWinners
+            );
+        }
+        require!(!self.sponsorship.refunded, AaasError::AlreadyRefunded);
+
+        //no fee is taken from the bonus
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: self.vault.to_account_info(),
+                    mint: self.mint.to_account_info(),
+                    to: self.sponsor_ata.to_account_info(),
+                    authority: self.challenge.to_account_info(),
+                },
+                &[&[
+                    CHALLENGE_SEED,
+                    self.challenge.service.key().as_ref(),
+                    self.challenge.id.key().as_ref(),
+                    &[self.challenge.bump],
+                ]],
+            ),
+            self.sponsorship.amount,
+            self.mint.decimals,
+        )?;
+
+        self.sponsorship.refunded = true;
+
+        //update settlement in challenge
+        self.challenge.sponsor_refunded_count = self
+            .challenge
+            .sponsor_refunded_count
+            .checked_add(1)
+            .unwrap();
+        self.challenge.settled = self.challenge.is_settled();
+
+        Ok(SponsorshipRefunded {
+            challenge: self.challenge.key(),
+            sponsor: self.sponsor.key(),
+            amount: self.sponsorship.amount,
+        })
+    }
+}
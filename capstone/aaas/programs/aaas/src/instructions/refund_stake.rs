@@ -0,0 +1,198 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface;
+
+use crate::constants::{BASIS_POINTS, CANDIDATE_SEED, CHALLENGE_SEED, CONFIG_SEED, PROFILE_SEED};
+use crate::error::AaasError;
+use crate::{AaasConfig, CandidateAccount, Challenge, Profile, StakeRefunded};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct RefundStake<'info> {
+    #[account(mut)]
+    pub candidate: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CANDIDATE_SEED, challenge.service.as_ref(), challenge.key().as_ref(), candidate.key().as_ref()],
+        bump = candidate_account.bump,
+        has_one = challenge,
+        has_one = candidate,
+    )]
+    pub candidate_account: Account<'info, CandidateAccount>,
+
+    ///candidate history, required when the candidate joined with it, left untouched otherwise
+    #[account(
+        mut,
+        seeds = [PROFILE_SEED, candidate.key().as_ref()],
+        bump = profile.bump,
+    )]
+    pub profile: Option<Account<'info, Profile>>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, AaasConfig>,
+
+    ///config admin, owns the treasury
+    #[account(address = config.admin)]
+    pub admin: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [CHALLENGE_SEED, challenge.service.as_ref(), challenge.id.key().as_ref()],
+        bump = challenge.bump,
+    )]
+    pub challenge: Account<'info, Challenge>,
+
+    /// treasury token account of the challenge mint, created on first use
+    #[account(
+        init_if_needed,
+        payer = candidate,
+        associated_token::mint = mint,
+        associated_token::authority = admin,
+        associated_token::token_program = token_program
+    )]
+    pub treasury: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    #[account(
+        address = challenge.mint @ AaasError::InvalidMint
+    )]
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = challenge
+    )]
+    pub vault: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = candidate,
+    )]
+    pub candidate_ata: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, token_interface::TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> RefundStake<'info> {
+    pub fn handler(&mut self) -> Result<StakeRefunded> {
+        //a cancelled challenge is refunded right away
+        if !self.challenge.cancelled {
+            //check if the validate period is over
+            let now = Clock::get()?.unix_timestamp as u64;
+            require!(
+                now > self.challenge.validation_end(),
+                AaasError::ValidationPeriod
+            );
+
+            //refund is only for challenges nobody won, otherwise the stake goes to the winners
+            require!(self.challenge.finalized, AaasError::ChallengeNotFinalized);
+            require!(self.challenge.tallied(), AaasError::VotesNotTallied);
+            require!(
+                self.challenge.winner_count == 0,
+                AaasError::ChallengeHasWinners
+            );
+        }
+        require!(!self.candidate_account.refunded, AaasError::AlreadyRefunded);
+        require!(
+            !self.candidate_account.profiled || self.profile.is_some(),
+            AaasError::ProfileRequired
+        );
+
+        // Forfeit calculation, forfeit fee is in basis point, there is no fee on cancellation
+        let forfeit = if self.challenge.cancelled {
+            0
+        } else {
+            self.candidate_account
+                .stake
+                .checked_mul(self.challenge.forfeit_fee as u64)
+                .unwrap()
+                .checked_div(BASIS_POINTS)
+                .unwrap()
+        };
+        //penalty stays in the vault, for the validator pool, a cancelled challenge has no validator pool,
+        //nor does a challenge without eligible validators
+        let penalty = if self.candidate_account.penalized
+            && !self.challenge.cancelled
+            && self.challenge.eligible_count > 0
+        {
+            self.challenge.penalty_amount(self.candidate_account.stake)
+        } else {
+            0
+        };
+
+        //transfer forfeit
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: self.vault.to_account_info(),
+                    mint: self.mint.to_account_info(),
+                    to: self.treasury.to_account_info(),
+                    authority: self.challenge.to_account_info(),
+                },
+                &[&[
+                    CHALLENGE_SEED,
+                    self.challenge.service.key().as_ref(),
+                    self.challenge.id.key().as_ref(),
+                    &[self.challenge.bump],
+                ]],
+            ),
+            forfeit,
+            self.mint.decimals,
+        )?;
+
+        //transfer back the rest of the stake
+        let amount = self.candidate_account.stake - forfeit - penalty;
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: self.vault.to_account_info(),
+                    mint: self.mint.to_account_info(),
+                    to: self.candidate_ata.to_account_info(),
+                    authority: self.challenge.to_account_info(),
+                },
+                &[&[
+                    CHALLENGE_SEED,
+                    self.challenge.service.key().as_ref(),
+                    self.challenge.id.key().as_ref(),
+                    &[self.challenge.bump],
+                ]],
+            ),
+            amount,
+            self.mint.decimals,
+        )?;
+
+        //update candidate account
+        self.candidate_account.refunded = true;
+        //nobody won, a cancelled challenge isn't lost though
+        if let Some(profile) = self
+            .profile
+            .as_mut()
+            .filter(|_| self.candidate_account.profiled)
+        {
+            if !self.challenge.cancelled {
+                profile.record_loss();
+            }
+        }
+
+        //update settlement in challenge
+        self.challenge.refunded_count = self.challenge.refunded_count.checked_add(1).unwrap();
+        self.challenge.settled = self.challenge.is_settled();
+
+        Ok(StakeRefunded {
+            challenge: self.challenge.key(),
+            candidate: self.candidate.key(),
+            amount,
+            forfeit,
+            penalty,
+        })
+    }
+}
@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AaasError;
+use crate::instructions::execute_proposal::*;
+use crate::{AaasConfig, AdminAction, SignersUpdated};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct RemoveSigner<'info> {
+    pub execution: ExecuteProposal<'info>,
+}
+
+impl<'info> RemoveSigner<'info> {
+    pub fn handler(&mut self) -> Result<SignersUpdated> {
+        let AdminAction::RemoveSigner { signer } = self.execution.approved_action()? else {
+            return err!(AaasError::ProposalActionMismatch);
+        };
+
+        let config = &mut self.execution.config;
+        require!(config.signers.contains(&signer), AaasError::SignerNotFound);
+
+        //threshold has to be lowered first, if removing would leave less signers than threshold
+        let mut signers = config.signers.clone();
+        signers.retain(|s| *s != signer);
+        AaasConfig::validate_signers(&signers, config.threshold)?;
+
+        config.signers = signers.clone();
+        Ok(SignersUpdated {
+            signers,
+            threshold: config.threshold,
+        })
+    }
+}
@@ -0,0 +1,125 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{CANDIDATE_SEED, CHALLENGE_SEED, PROFILE_SEED, VALIDATE_SEED};
+use crate::error::AaasError;
+use crate::{
+    CandidateAccount, Challenge, Profile, ProofValidated, Validation, Vote, WinnerThresholdReached,
+};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct RevealVote<'info> {
+    pub validator: Signer<'info>,
+
+    #[account(
+        seeds = [CHALLENGE_SEED, challenge.service.key().as_ref(), challenge.id.key().as_ref()],
+        bump = challenge.bump,
+        constraint = !challenge.cancelled @ AaasError::ChallengeCancelled,
+    )]
+    pub challenge: Account<'info, Challenge>,
+
+    #[account(
+        mut,
+        seeds = [CANDIDATE_SEED, challenge.service.key().as_ref(), challenge.key().as_ref(), validator.key().as_ref()],
+        bump = validator_account.bump,
+        has_one = challenge,
+        constraint = validator_account.candidate.key() == validator.key(),
+    )]
+    pub validator_account: Account<'info, CandidateAccount>,
+
+    ///validator history, required when the validator joined with it, left untouched otherwise
+    #[account(
+        mut,
+        seeds = [PROFILE_SEED, validator.key().as_ref()],
+        bump = profile.bump,
+    )]
+    pub profile: Option<Account<'info, Profile>>,
+
+    #[account(
+        mut,
+        seeds = [CANDIDATE_SEED, challenge.service.key().as_ref(), challenge.key().as_ref(), candidate_account.candidate.key().as_ref()],
+        bump = candidate_account.bump,
+        has_one = challenge,
+    )]
+    pub candidate_account: Account<'info, CandidateAccount>,
+
+    #[account(
+        mut,
+        seeds = [VALIDATE_SEED, challenge.service.key().as_ref(), challenge.key().as_ref(), candidate_account.key().as_ref(), validator.key().as_ref()],
+        bump = validation.bump,
+        constraint = validation.vote.is_none() @ AaasError::AlreadyRevealed,
+    )]
+    pub validation: Account<'info, Validation>,
+}
+
+impl<'info> RevealVote<'info> {
+    pub fn handler(
+        &mut self,
+        vote: Vote,
+        salt: [u8; 32],
+    ) -> Result<(ProofValidated, Option<WinnerThresholdReached>)> {
+        require!(self.challenge.commit_reveal(), AaasError::OpenVoting);
+
+        //reveals are allowed from the commit end till the reveal end
+        let now = Clock::get()?.unix_timestamp as u64;
+        require!(
+            now >= self.challenge.commit_end && now < self.challenge.reveal_end,
+            AaasError::NotRevealPhase
+        );
+
+        //a commitment copied from another validator or proof doesn't match
+        let commitment = vote.commitment(
+            &salt,
+            &self.validator.key(),
+            &self.candidate_account.key(),
+        );
+        require!(
+            commitment == self.validation.commitment,
+            AaasError::InvalidReveal
+        );
+        require!(
+            !self.validator_account.profiled || self.profile.is_some(),
+            AaasError::ProfileRequired
+        );
+
+        let was_winning = self.challenge.is_winning(
+            self.candidate_account.weight,
+            self.candidate_account.acceptance,
+            self.candidate_account.rejections,
+        );
+        //only revealed votes count, same as an open vote
+        self.candidate_account
+            .add_vote(vote, self.validator_account.weight);
+        self.validator_account.votes_cast =
+            self.validator_account.votes_cast.checked_add(1).unwrap();
+        self.validation.vote = Some(vote);
+        if let Some(profile) = self
+            .profile
+            .as_mut()
+            .filter(|_| self.validator_account.profiled)
+        {
+            profile.record_validation();
+        }
+
+        let candidate = &self.candidate_account;
+        let validated = ProofValidated {
+            challenge: self.challenge.key(),
+            candidate: candidate.candidate,
+            validator: self.validator.key(),
+            vote,
+            weight: self.validator_account.weight,
+            acceptance: candidate.acceptance,
+            rejections: candidate.rejections,
+        };
+        //reported once, when the vote gets the proof over the quorum and winning threshold
+        let reached = (!was_winning
+            && self.challenge.is_winning(
+                candidate.weight,
+                candidate.acceptance,
+                candidate.rejections,
+            ))
+        .then(|| candidate.threshold_reached());
+
+        Ok((validated, reached))
+    }
+}
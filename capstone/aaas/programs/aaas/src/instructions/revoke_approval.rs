@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::PROPOSAL_SEED;
+use crate::error::AaasError;
+use crate::{ApprovalRevoked, Proposal};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct RevokeApproval<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, proposal.id.key().as_ref()],
+        bump = proposal.bump,
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+impl<'info> RevokeApproval<'info> {
+    pub fn handler(&mut self) -> Result<ApprovalRevoked> {
+        let signer = self.signer.key();
+        require!(
+            self.proposal.approvals.contains(&signer),
+            AaasError::NotApproved
+        );
+
+        self.proposal.approvals.retain(|approver| *approver != signer);
+
+        Ok(ApprovalRevoked {
+            proposal: self.proposal.key(),
+            signer,
+            approvals: self.proposal.approvals.len() as u8,
+        })
+    }
+}
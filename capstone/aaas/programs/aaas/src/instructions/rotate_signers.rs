@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AaasError;
+use crate::instructions::execute_proposal::*;
+use crate::{AaasConfig, AdminAction, SignersUpdated};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct RotateSigners<'info> {
+    pub execution: ExecuteProposal<'info>,
+}
+
+impl<'info> RotateSigners<'info> {
+    pub fn handler(&mut self) -> Result<SignersUpdated> {
+        //approvals are counted against the current signers, before rotating
+        let AdminAction::RotateSigners { signers, threshold } = self.execution.approved_action()?
+        else {
+            return err!(AaasError::ProposalActionMismatch);
+        };
+
+        AaasConfig::validate_signers(&signers, threshold)?;
+
+        let config = &mut self.execution.config;
+        config.signers = signers.clone();
+        config.threshold = threshold;
+        Ok(SignersUpdated { signers, threshold })
+    }
+}
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::SERVICE_SEED;
+use crate::error::AaasError;
+use crate::instructions::execute_proposal::*;
+use crate::{AdminAction, Service, ServiceAttestorSet};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SetServiceAttestor<'info> {
+    pub execution: ExecuteProposal<'info>,
+
+    #[account(
+        mut,
+        seeds = [SERVICE_SEED, service.id.key().as_ref()],
+        bump = service.bump,
+    )]
+    pub service: Account<'info, Service>,
+}
+
+impl<'info> SetServiceAttestor<'info> {
+    pub fn handler(&mut self) -> Result<ServiceAttestorSet> {
+        let AdminAction::SetServiceAttestor { id, attestor } = self.execution.approved_action()?
+        else {
+            return err!(AaasError::ProposalActionMismatch);
+        };
+        require_keys_eq!(id, self.service.id, AaasError::ProposalActionMismatch);
+
+        //running challenges keep the attestor they were created with
+        self.service.attestor = attestor;
+        Ok(ServiceAttestorSet {
+            service: self.service.key(),
+            attestor,
+        })
+    }
+}
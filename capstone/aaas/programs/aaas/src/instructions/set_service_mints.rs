@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::SERVICE_SEED;
+use crate::error::AaasError;
+use crate::instructions::execute_proposal::*;
+use crate::{AdminAction, Service, ServiceMintsSet};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SetServiceMints<'info> {
+    pub execution: ExecuteProposal<'info>,
+
+    #[account(
+        mut,
+        seeds = [SERVICE_SEED, service.id.key().as_ref()],
+        bump = service.bump,
+    )]
+    pub service: Account<'info, Service>,
+}
+
+impl<'info> SetServiceMints<'info> {
+    pub fn handler(&mut self) -> Result<ServiceMintsSet> {
+        let AdminAction::SetServiceMints { id, mints } = self.execution.approved_action()? else {
+            return err!(AaasError::ProposalActionMismatch);
+        };
+        require_keys_eq!(id, self.service.id, AaasError::ProposalActionMismatch);
+
+        Service::validate_mints(&mints)?;
+
+        //running challenges keep the mint they were created with
+        self.service.mints = mints.clone();
+        Ok(ServiceMintsSet {
+            service: self.service.key(),
+            mints,
+        })
+    }
+}
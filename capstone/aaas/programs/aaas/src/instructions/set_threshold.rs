@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AaasError;
+use crate::instructions::execute_proposal::*;
+use crate::{AaasConfig, AdminAction, SignersUpdated};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SetThreshold<'info> {
+    pub execution: ExecuteProposal<'info>,
+}
+
+impl<'info> SetThreshold<'info> {
+    pub fn handler(&mut self) -> Result<SignersUpdated> {
+        let AdminAction::SetThreshold { threshold } = self.execution.approved_action()? else {
+            return err!(AaasError::ProposalActionMismatch);
+        };
+
+        let config = &mut self.execution.config;
+        AaasConfig::validate_signers(&config.signers, threshold)?;
+
+        config.threshold = threshold;
+        Ok(SignersUpdated {
+            signers: config.signers.clone(),
+            threshold,
+        })
+    }
+}
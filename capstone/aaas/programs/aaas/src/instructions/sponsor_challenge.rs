@@ -0,0 +1,107 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface;
+
+use crate::constants::{CHALLENGE_SEED, DISCRIMINATOR, SPONSORSHIP_SEED};
+use crate::error::AaasError;
+use crate::{gross_up_transfer_fee, Challenge, ChallengeSponsored, Sponsorship};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SponsorChallenge<'info> {
+    #[account(mut)]
+    pub sponsor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CHALLENGE_SEED, challenge.service.key().as_ref(), challenge.id.key().as_ref()],
+        bump = challenge.bump,
+        constraint = !challenge.cancelled @ AaasError::ChallengeCancelled,
+    )]
+    pub challenge: Account<'info, Challenge>,
+
+    ///created on the first deposit, later deposits add to it
+    #[account(
+        init_if_needed,
+        payer = sponsor,
+        seeds = [SPONSORSHIP_SEED, challenge.key().as_ref(), sponsor.key().as_ref()],
+        bump,
+        space = DISCRIMINATOR + Sponsorship::INIT_SPACE
+    )]
+    pub sponsorship: Account<'info, Sponsorship>,
+
+    #[account(
+        address = challenge.mint @ AaasError::InvalidMint
+    )]
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = challenge,
+        associated_token::token_program = token_program
+    )]
+    pub vault: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = sponsor,
+        associated_token::token_program = token_program
+    )]
+    pub sponsor_ata: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, token_interface::TokenInterface>,
+}
+
+impl<'info> SponsorChallenge<'info> {
+    pub fn handler(&mut self, amount: u64, bump: u8) -> Result<ChallengeSponsored> {
+        //the bonus is set before anyone can know the results
+        let now = Clock::get()?.unix_timestamp as u64;
+        require!(now < self.challenge.end_time, AaasError::ChallengeEnded);
+        require!(amount > 0, AaasError::InvalidSponsorAmount);
+
+        //transfer bonus from sponsor ata to vault, sponsor pays the transfer fee if the mint has one
+        let vault_before = self.vault.amount;
+        token_interface::transfer_checked(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: self.sponsor_ata.to_account_info(),
+                    mint: self.mint.to_account_info(),
+                    to: self.vault.to_account_info(),
+                    authority: self.sponsor.to_account_info(),
+                },
+            ),
+            gross_up_transfer_fee(&self.mint, amount)?,
+            self.mint.decimals,
+        )?;
+        //the fee is rounded up, so the vault may get a bit more, payouts are accounted with what it got
+        self.vault.reload()?;
+        let received = self.vault.amount.checked_sub(vault_before).unwrap();
+        require!(received >= amount, AaasError::UnexpectedVaultAmount);
+        let amount = received;
+
+        //first deposit of the sponsor
+        if self.sponsorship.amount == 0 {
+            self.sponsorship.set_inner(Sponsorship {
+                challenge: self.challenge.key(),
+                sponsor: self.sponsor.key(),
+                amount: 0,
+                refunded: false,
+                bump,
+            });
+            self.challenge.sponsor_count = self.challenge.sponsor_count.checked_add(1).unwrap();
+        }
+
+        self.sponsorship.amount = self.sponsorship.amount.checked_add(amount).unwrap();
+        self.challenge.bonus_pool = self.challenge.bonus_pool.checked_add(amount).unwrap();
+
+        Ok(ChallengeSponsored {
+            challenge: self.challenge.key(),
+            sponsor: self.sponsor.key(),
+            amount,
+            bonus_pool: self.challenge.bonus_pool,
+        })
+    }
+}
@@ -0,0 +1,99 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{
+    CANDIDATE_SEED, CHALLENGE_SEED, DISCRIMINATOR, MAX_CANDIDATE_PROOF_LEN, MILESTONE_SEED,
+};
+use crate::error::AaasError;
+use crate::{CandidateAccount, Challenge, MilestoneProof, MilestoneSubmitted};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(index: u8)]
+pub struct SubmitMilestone<'info> {
+    #[account(mut)]
+    pub candidate: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CHALLENGE_SEED, challenge.service.key().as_ref(), challenge.id.key().as_ref()],
+        bump = challenge.bump,
+        constraint = !challenge.cancelled @ AaasError::ChallengeCancelled,
+    )]
+    pub challenge: Account<'info, Challenge>,
+
+    #[account(
+        mut,
+        seeds = [CANDIDATE_SEED, challenge.service.key().as_ref(), challenge.key().as_ref(), candidate.key().as_ref()],
+        bump = candidate_account.bump,
+        has_one = challenge,
+        has_one = candidate,
+    )]
+    pub candidate_account: Account<'info, CandidateAccount>,
+
+    ///one proof per milestone, so it can't be resubmitted
+    #[account(
+        init,
+        payer = candidate,
+        seeds = [MILESTONE_SEED, challenge.key().as_ref(), candidate_account.key().as_ref(), &[index]],
+        bump,
+        space = DISCRIMINATOR + MilestoneProof::INIT_SPACE
+    )]
+    pub milestone_proof: Account<'info, MilestoneProof>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SubmitMilestone<'info> {
+    pub fn handler(
+        &mut self,
+        index: u8,
+        proof_hash: [u8; 32],
+        proof: Option<String>,
+        bump: u8,
+    ) -> Result<MilestoneSubmitted> {
+        require!(
+            !self.challenge.milestones.is_empty(),
+            AaasError::NotMilestoneChallenge
+        );
+
+        //each milestone has its own submission window
+        let (start, end) = self.challenge.milestone_window(index)?;
+        let now = Clock::get()?.unix_timestamp as u64;
+        require!(now >= start && now < end, AaasError::MilestoneNotOpen);
+
+        require!(proof_hash != [0u8; 32], AaasError::InvalidProofHash);
+        let proof = proof.unwrap_or_default();
+        require!(
+            proof.len() <= MAX_CANDIDATE_PROOF_LEN,
+            AaasError::ProofTooLong
+        );
+
+        self.milestone_proof.set_inner(MilestoneProof {
+            candidate_account: self.candidate_account.key(),
+            index,
+            proof: proof.clone(),
+            proof_hash,
+            acceptance: 0,
+            rejections: 0,
+            finalized: false,
+            passed: false,
+            bump,
+        });
+
+        self.candidate_account.milestones_submitted = self
+            .candidate_account
+            .milestones_submitted
+            .checked_add(1)
+            .unwrap();
+        //milestone proofs are counted, so the challenge isn't closed before them
+        self.challenge.milestone_count = self.challenge.milestone_count.checked_add(1).unwrap();
+
+        Ok(MilestoneSubmitted {
+            challenge: self.challenge.key(),
+            candidate: self.candidate.key(),
+            index,
+            proof_hash,
+            proof,
+        })
+    }
+}
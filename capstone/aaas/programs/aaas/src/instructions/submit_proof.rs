@@ -1,9 +1,10 @@
 use anchor_lang::prelude::*;
 
-use crate::constants::{CANDIDATE_SEED, CHALLENGE_SEED};
+use crate::constants::{CANDIDATE_SEED, CHALLENGE_SEED, MAX_CANDIDATE_PROOF_LEN};
 use crate::error::AaasError;
-use crate::{CandidateAccount, Challenge};
+use crate::{CandidateAccount, Challenge, ProofSubmitted};
 
+#[cfg_attr(feature = "event-cpi", event_cpi)]
 #[derive(Accounts)]
 pub struct SubmitProof<'info> {
     #[account(mut)]
@@ -12,6 +13,7 @@ pub struct SubmitProof<'info> {
     #[account(
         seeds = [CHALLENGE_SEED, challenge.service.key().as_ref(), challenge.id.key().as_ref()],
         bump = challenge.bump,
+        constraint = !challenge.cancelled @ AaasError::ChallengeCancelled,
     )]
     pub challenge: Account<'info, Challenge>,
 
@@ -26,7 +28,11 @@ pub struct SubmitProof<'info> {
 }
 
 impl<'info> SubmitProof<'info> {
-    pub fn handler(&mut self, proof: String) -> Result<()> {
+    pub fn handler(
+        &mut self,
+        proof_hash: [u8; 32],
+        proof: Option<String>,
+    ) -> Result<ProofSubmitted> {
         //check if the challenge has started
         let now = Clock::get()?.unix_timestamp as u64;
         require!(
@@ -37,16 +43,36 @@ impl<'info> SubmitProof<'info> {
         //check if challenge has ended, can't submit after challenge ends
         require!(now < self.challenge.end_time, AaasError::ChallengeEnded);
 
+        //milestone challenges take a proof per milestone instead
+        require!(
+            self.challenge.milestones.is_empty(),
+            AaasError::MilestoneChallenge
+        );
+
         //shouldn't allow resubmit
         require!(
-            self.candidate_account.proof.is_empty(),
+            !self.candidate_account.has_proof(),
             AaasError::DuplicateProof
         );
 
-        //save the proof, and initialized the acceptance at 0
-        self.candidate_account.proof = proof;
-        self.candidate_account.acceptance = 0u16;
+        require!(proof_hash != [0u8; 32], AaasError::InvalidProofHash);
+        let proof = proof.unwrap_or_default();
+        require!(
+            proof.len() <= MAX_CANDIDATE_PROOF_LEN,
+            AaasError::ProofTooLong
+        );
+
+        //save the proof, and initialized the votes at 0
+        self.candidate_account.proof = proof.clone();
+        self.candidate_account.proof_hash = proof_hash;
+        self.candidate_account.acceptance = 0u64;
+        self.candidate_account.rejections = 0u64;
 
-        Ok(())
+        Ok(ProofSubmitted {
+            challenge: self.challenge.key(),
+            candidate: self.candidate_account.candidate,
+            proof_hash,
+            proof,
+        })
     }
 }
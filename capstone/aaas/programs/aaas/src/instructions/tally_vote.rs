@@ -0,0 +1,122 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{BASIS_POINTS, CANDIDATE_SEED, CHALLENGE_SEED, PROFILE_SEED, VALIDATE_SEED};
+use crate::error::AaasError;
+use crate::{CandidateAccount, Challenge, Profile, Validation, Vote, VoteTallied};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct TallyVote<'info> {
+    ///anyone can crank the tally
+    pub cranker: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CHALLENGE_SEED, challenge.service.key().as_ref(), challenge.id.key().as_ref()],
+        bump = challenge.bump,
+        constraint = challenge.finalized @ AaasError::ChallengeNotFinalized,
+        constraint = !challenge.cancelled @ AaasError::ChallengeCancelled,
+    )]
+    pub challenge: Account<'info, Challenge>,
+
+    ///candidate the vote was cast on
+    #[account(
+        seeds = [CANDIDATE_SEED, challenge.service.key().as_ref(), challenge.key().as_ref(), candidate_account.candidate.key().as_ref()],
+        bump = candidate_account.bump,
+        has_one = challenge,
+    )]
+    pub candidate_account: Account<'info, CandidateAccount>,
+
+    ///candidate who cast the vote
+    #[account(
+        mut,
+        seeds = [CANDIDATE_SEED, challenge.service.key().as_ref(), challenge.key().as_ref(), validation.validator.key().as_ref()],
+        bump = validator_account.bump,
+        has_one = challenge,
+    )]
+    pub validator_account: Account<'info, CandidateAccount>,
+
+    #[account(
+        mut,
+        seeds = [VALIDATE_SEED, challenge.service.key().as_ref(), challenge.key().as_ref(), candidate_account.key().as_ref(), validation.validator.key().as_ref()],
+        bump = validation.bump,
+        constraint = !validation.tallied @ AaasError::VoteTallied,
+    )]
+    pub validation: Account<'info, Validation>,
+
+    ///validator history, required when the validator joined with it, left untouched otherwise
+    #[account(
+        mut,
+        seeds = [PROFILE_SEED, validation.validator.key().as_ref()],
+        bump = profile.bump,
+    )]
+    pub profile: Option<Account<'info, Profile>>,
+}
+
+impl<'info> TallyVote<'info> {
+    pub fn handler(&mut self) -> Result<VoteTallied> {
+        require!(
+            !self.validator_account.profiled || self.profile.is_some(),
+            AaasError::ProfileRequired
+        );
+        self.validation.tallied = true;
+        self.challenge.tallied_count = self.challenge.tallied_count.checked_add(1).unwrap();
+        if self.validator_account.profiled {
+            self.challenge.profiled_votes = self.challenge.profiled_votes.checked_sub(1).unwrap();
+        }
+
+        //unrevealed votes are abstentions, they weren't counted as cast either
+        let Some(vote) = self.validation.vote else {
+            return Ok(self.tallied(false));
+        };
+
+        //a vote agrees when it approved a winner, or rejected a loser
+        let agreed = (vote == Vote::Approve) == self.candidate_account.winner;
+
+        let validator = &mut self.validator_account;
+        validator.votes_tallied = validator.votes_tallied.checked_add(1).unwrap();
+        if agreed {
+            validator.votes_agreed = validator.votes_agreed.checked_add(1).unwrap();
+            if let Some(profile) = self.profile.as_mut().filter(|_| validator.profiled) {
+                profile.record_agreed_vote();
+            }
+        }
+
+        //the validator's standing is decided with its last vote, when votes are rewarded or penalized
+        if !self.challenge.incentives.enabled() || validator.votes_tallied < validator.votes_cast {
+            return Ok(self.tallied(agreed));
+        }
+        let agreement = (validator.votes_agreed as u64)
+            .checked_mul(BASIS_POINTS)
+            .unwrap()
+            .checked_div(validator.votes_cast as u64)
+            .unwrap();
+        let incentives = &self.challenge.incentives;
+        if incentives.penalty > 0 && agreement < incentives.min_agreement as u64 {
+            validator.penalized = true;
+            //losers stake already goes to the winners, so only a stake that is paid back can be penalized
+            if validator.winner || self.challenge.winner_count == 0 {
+                let penalty = self.challenge.penalty_amount(validator.stake);
+                self.challenge.penalty_pool =
+                    self.challenge.penalty_pool.checked_add(penalty).unwrap();
+            }
+        } else if validator.votes_cast >= incentives.min_votes as u32 {
+            validator.eligible = true;
+            self.challenge.eligible_count = self.challenge.eligible_count.checked_add(1).unwrap();
+        }
+
+        Ok(self.tallied(agreed))
+    }
+
+    fn tallied(&self, agreed: bool) -> VoteTallied {
+        VoteTallied {
+            challenge: self.challenge.key(),
+            candidate: self.candidate_account.candidate,
+            validator: self.validator_account.candidate,
+            vote: self.validation.vote,
+            agreed,
+            eligible: self.validator_account.eligible,
+            penalized: self.validator_account.penalized,
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface;
+
+use crate::error::AaasError;
+use crate::instructions::execute_proposal::*;
+use crate::{AdminAction, AdminTransferred};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct TransferAdmin<'info> {
+    pub execution: ExecuteProposal<'info>,
+
+    pub new_admin: SystemAccount<'info>,
+
+    /// admin owns the treasury, so the new admin's treasury is created if it doesn't exist
+    #[account(
+        init_if_needed,
+        payer = execution.executor,
+        associated_token::mint = mint,
+        associated_token::authority = new_admin,
+        associated_token::token_program = token_program
+    )]
+    pub new_treasury: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, token_interface::TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> TransferAdmin<'info> {
+    pub fn handler(&mut self) -> Result<AdminTransferred> {
+        let AdminAction::TransferAdmin { admin } = self.execution.approved_action()? else {
+            return err!(AaasError::ProposalActionMismatch);
+        };
+        require_keys_eq!(
+            admin,
+            self.new_admin.key(),
+            AaasError::ProposalActionMismatch
+        );
+
+        let previous_admin = self.execution.config.admin;
+        self.execution.config.admin = admin;
+        Ok(AdminTransferred {
+            previous_admin,
+            admin,
+        })
+    }
+}
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::SERVICE_SEED;
+use crate::error::AaasError;
+use crate::instructions::execute_proposal::*;
+use crate::{AdminAction, Service, ServiceFeeUpdated};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct UpdateServiceFee<'info> {
+    pub execution: ExecuteProposal<'info>,
+
+    #[account(
+        mut,
+        seeds = [SERVICE_SEED, service.id.key().as_ref()],
+        bump = service.bump,
+    )]
+    pub service: Account<'info, Service>,
+}
+
+impl<'info> UpdateServiceFee<'info> {
+    pub fn handler(&mut self) -> Result<ServiceFeeUpdated> {
+        let AdminAction::UpdateServiceFee {
+            id,
+            fee,
+            forfeit_fee,
+        } = self.execution.approved_action()?
+        else {
+            return err!(AaasError::ProposalActionMismatch);
+        };
+        require_keys_eq!(id, self.service.id, AaasError::ProposalActionMismatch);
+
+        Service::validate_fee(fee)?;
+        Service::validate_fee(forfeit_fee)?;
+
+        self.service.fee = fee;
+        self.service.forfeit_fee = forfeit_fee;
+        Ok(ServiceFeeUpdated {
+            service: self.service.key(),
+            fee,
+            forfeit_fee,
+        })
+    }
+}
@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::SERVICE_SEED;
+use crate::error::AaasError;
+use crate::instructions::execute_proposal::*;
+use crate::{AdminAction, Service, ServiceLimitsUpdated};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct UpdateServiceLimits<'info> {
+    pub execution: ExecuteProposal<'info>,
+
+    #[account(
+        mut,
+        seeds = [SERVICE_SEED, service.id.key().as_ref()],
+        bump = service.bump,
+    )]
+    pub service: Account<'info, Service>,
+}
+
+impl<'info> UpdateServiceLimits<'info> {
+    pub fn handler(&mut self) -> Result<ServiceLimitsUpdated> {
+        let AdminAction::UpdateServiceLimits { id, limits } = self.execution.approved_action()?
+        else {
+            return err!(AaasError::ProposalActionMismatch);
+        };
+        require_keys_eq!(id, self.service.id, AaasError::ProposalActionMismatch);
+
+        limits.validate()?;
+
+        //applies to challenges created after the change
+        self.service.limits = limits.clone();
+        Ok(ServiceLimitsUpdated {
+            service: self.service.key(),
+            limits,
+        })
+    }
+}
@@ -0,0 +1,137 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{
+    CANDIDATE_SEED, CHALLENGE_SEED, DISCRIMINATOR, MILESTONE_SEED, PROFILE_SEED, VALIDATE_SEED,
+};
+use crate::error::AaasError;
+use crate::{
+    CandidateAccount, Challenge, MilestoneProof, MilestoneValidated, Profile, Validation, Vote,
+};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ValidateMilestone<'info> {
+    #[account(mut)]
+    pub validator: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CHALLENGE_SEED, challenge.service.key().as_ref(), challenge.id.key().as_ref()],
+        bump = challenge.bump,
+        constraint = !challenge.cancelled @ AaasError::ChallengeCancelled,
+    )]
+    pub challenge: Account<'info, Challenge>,
+
+    /// validates the validator is also a candidate, and its not voting(validating) for himself
+    #[account(
+        mut,
+        seeds = [CANDIDATE_SEED, challenge.service.key().as_ref(), challenge.key().as_ref(), validator.key().as_ref()],
+        bump = validator_account.bump,
+        has_one = challenge,
+        constraint = validator_account.candidate.key() == validator.key(),
+        constraint = validator.key() != candidate_account.candidate.key(),
+    )]
+    pub validator_account: Account<'info, CandidateAccount>,
+
+    ///validator history, required when the validator joined with it, left untouched otherwise
+    #[account(
+        mut,
+        seeds = [PROFILE_SEED, validator.key().as_ref()],
+        bump = profile.bump,
+    )]
+    pub profile: Option<Account<'info, Profile>>,
+
+    /// candidate who submitted the milestone proof
+    #[account(
+        seeds = [CANDIDATE_SEED, challenge.service.key().as_ref(), challenge.key().as_ref(), candidate_account.candidate.key().as_ref()],
+        bump = candidate_account.bump,
+        has_one = challenge,
+    )]
+    pub candidate_account: Account<'info, CandidateAccount>,
+
+    #[account(
+        mut,
+        seeds = [MILESTONE_SEED, challenge.key().as_ref(), candidate_account.key().as_ref(), &[milestone_proof.index]],
+        bump = milestone_proof.bump,
+        has_one = candidate_account,
+    )]
+    pub milestone_proof: Account<'info, MilestoneProof>,
+
+    /// records the vote, one per validator and milestone proof
+    #[account(
+        init,
+        payer = validator,
+        seeds = [VALIDATE_SEED, challenge.service.key().as_ref(), challenge.key().as_ref(), milestone_proof.key().as_ref(), validator.key().as_ref()],
+        bump,
+        space = DISCRIMINATOR + Validation::INIT_SPACE
+    )]
+    pub validation: Account<'info, Validation>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ValidateMilestone<'info> {
+    pub fn handler(
+        &mut self,
+        vote: Vote,
+        proof_hash: [u8; 32],
+        bump: u8,
+    ) -> Result<MilestoneValidated> {
+        //a milestone is validated from its window start, till validation_period after its end
+        let (start, end) = self
+            .challenge
+            .milestone_window(self.milestone_proof.index)?;
+        let now = Clock::get()?.unix_timestamp as u64;
+        require!(now >= start, AaasError::MilestoneNotOpen);
+        require!(
+            now < end.checked_add(self.challenge.validation_period).unwrap(),
+            AaasError::ValidationPeriodEnded
+        );
+
+        //the vote is tied to the evidence the validator reviewed
+        require!(
+            proof_hash == self.milestone_proof.proof_hash,
+            AaasError::ProofHashMismatch
+        );
+
+        require!(
+            !self.validator_account.profiled || self.profile.is_some(),
+            AaasError::ProfileRequired
+        );
+        self.milestone_proof
+            .add_vote(vote, self.validator_account.weight);
+
+        //validation accounts are counted, so the challenge isn't closed before them
+        self.challenge.validation_count = self.challenge.validation_count.checked_add(1).unwrap();
+        self.validator_account.votes_cast =
+            self.validator_account.votes_cast.checked_add(1).unwrap();
+        if let Some(profile) = self
+            .profile
+            .as_mut()
+            .filter(|_| self.validator_account.profiled)
+        {
+            profile.record_validation();
+        }
+
+        self.validation.set_inner(Validation {
+            validator: self.validator.key(),
+            candidate_account: self.milestone_proof.key(),
+            vote: Some(vote),
+            commitment: [0u8; 32],
+            proof_hash,
+            tallied: false,
+            bump,
+        });
+
+        Ok(MilestoneValidated {
+            challenge: self.challenge.key(),
+            candidate: self.candidate_account.candidate,
+            validator: self.validator.key(),
+            index: self.milestone_proof.index,
+            vote,
+            weight: self.validator_account.weight,
+            acceptance: self.milestone_proof.acceptance,
+            rejections: self.milestone_proof.rejections,
+        })
+    }
+}
@@ -1,13 +1,14 @@
-use std::ops::{Add, AddAssign};
-
 use anchor_lang::prelude::*;
 
 use crate::constants::{
-    CANDIDATE_SEED, CHALLENGE_SEED, DISCRIMINATOR, VALIDATE_SEED, VALIDATION_PERIOD,
+    CANDIDATE_SEED, CHALLENGE_SEED, DISCRIMINATOR, PROFILE_SEED, VALIDATE_SEED,
 };
 use crate::error::AaasError;
-use crate::{CandidateAccount, Challenge};
+use crate::{
+    CandidateAccount, Challenge, Profile, ProofValidated, Validation, Vote, WinnerThresholdReached,
+};
 
+#[cfg_attr(feature = "event-cpi", event_cpi)]
 #[derive(Accounts)]
 pub struct ValidateProof<'info> {
     #[account(mut)]
@@ -16,12 +17,14 @@ pub struct ValidateProof<'info> {
     #[account(
         mut,
         seeds = [CHALLENGE_SEED, challenge.service.key().as_ref(), challenge.id.key().as_ref()],
-        bump = challenge.bump
+        bump = challenge.bump,
+        constraint = !challenge.cancelled @ AaasError::ChallengeCancelled,
     )]
     pub challenge: Account<'info, Challenge>,
 
     /// validates the validator is also a candidate, and its not voting(validating) for himself
     #[account(
+        mut,
         seeds = [CANDIDATE_SEED, challenge.service.key().as_ref(), challenge.key().as_ref(), validator.key().as_ref()],
         bump = validator_account.bump,
         has_one = challenge,
@@ -30,6 +33,14 @@ pub struct ValidateProof<'info> {
     )]
     pub validator_account: Account<'info, CandidateAccount>,
 
+    ///validator history, required when the validator joined with it, left untouched otherwise
+    #[account(
+        mut,
+        seeds = [PROFILE_SEED, validator.key().as_ref()],
+        bump = profile.bump,
+    )]
+    pub profile: Option<Account<'info, Profile>>,
+
     /// validates the candidate for which the validator is voting
     #[account(
         mut,
@@ -39,66 +50,121 @@ pub struct ValidateProof<'info> {
     )]
     pub candidate_account: Account<'info, CandidateAccount>,
 
-    /// CHECK: this account doesn't need to be validated, as it is used only to validate the authenticity of a voting (validation)
+    /// records the vote, so it can be tallied against the final outcome
     /// if this account is already exist, we don't allow the voting/validation
     #[account(
         init,
         payer = validator,
         seeds = [VALIDATE_SEED, challenge.service.key().as_ref(), challenge.key().as_ref(), candidate_account.key().as_ref(), validator.key().as_ref()],
         bump,
-        space = DISCRIMINATOR
+        space = DISCRIMINATOR + Validation::INIT_SPACE
     )]
-    pub validation: UncheckedAccount<'info>,
+    pub validation: Account<'info, Validation>,
 
     pub system_program: Program<'info, System>,
 }
 
 impl<'info> ValidateProof<'info> {
-    pub fn handler(&mut self) -> Result<()> {
+    pub fn handler(
+        &mut self,
+        vote: Vote,
+        proof_hash: [u8; 32],
+        bump: u8,
+    ) -> Result<(ProofValidated, Option<WinnerThresholdReached>)> {
+        //attestor validated challenges don't take peer votes
+        require!(
+            self.challenge.attestor.is_none(),
+            AaasError::AttestorChallenge
+        );
+
         //authenticity of vote is validate in the accounts, and the validator is also validated in accounts
         //check it is within validation period
         let now = Clock::get()?.unix_timestamp as u64;
         require!(
-            now < self.challenge.end_time.add(VALIDATION_PERIOD),
+            now < self.challenge.validation_end(),
             AaasError::ValidationPeriodEnded
         );
 
-        //validation period is within challenge start, and 24 hrs after challenge end
+        //validation period is within challenge start, and validation_period after challenge end
         require!(
             now > self.challenge.start_time,
             AaasError::ChallengeNotStarted
         );
+        require!(
+            !self.challenge.commit_reveal(),
+            AaasError::CommitRevealVoting
+        );
+        require!(
+            self.challenge.milestones.is_empty(),
+            AaasError::MilestoneChallenge
+        );
 
         //check if the candidate has proof submitted
+        require!(self.candidate_account.has_proof(), AaasError::RequiredProof);
+        //the vote is tied to the evidence the validator reviewed
+        require!(
+            proof_hash == self.candidate_account.proof_hash,
+            AaasError::ProofHashMismatch
+        );
+
         require!(
-            !self.candidate_account.proof.is_empty(),
-            AaasError::RequiredProof
+            !self.validator_account.profiled || self.profile.is_some(),
+            AaasError::ProfileRequired
         );
 
-        //calculate existing acceptance rate
-        let acceptance_rate = (self.candidate_account.acceptance as u64)
-            .checked_mul(10000)
-            .unwrap()
-            .checked_div(self.challenge.candidate_count as u64)
-            .unwrap();
-        //update the acceptance
-        self.candidate_account.acceptance.add_assign(1);
-        //update winner count in challenge, if its winning and not already counted
-        if acceptance_rate >= self.challenge.winning_threshold as u64 {
-            Ok(())
-        } else {
-            //new acceptance rate, after updating acceptance
-            let acceptance_rate = (self.candidate_account.acceptance as u64)
-                .checked_mul(10000)
-                .unwrap()
-                .checked_div(self.challenge.candidate_count as u64)
-                .unwrap();
-            if acceptance_rate >= self.challenge.winning_threshold as u64 {
-                //reached winning threshold for the first time
-                self.challenge.winner_count.add_assign(1);
-            };
-
-            Ok(())
+        let was_winning = self.challenge.is_winning(
+            self.candidate_account.weight,
+            self.candidate_account.acceptance,
+            self.candidate_account.rejections,
+        );
+        //add the validator's vote weight to the approvals or rejections
+        self.candidate_account
+            .add_vote(vote, self.validator_account.weight);
+
+        //validation accounts are counted, so the challenge isn't closed before them
+        self.challenge.validation_count = self.challenge.validation_count.checked_add(1).unwrap();
+        if self.validator_account.profiled {
+            self.challenge.profiled_votes = self.challenge.profiled_votes.checked_add(1).unwrap();
+        }
+        self.validator_account.votes_cast =
+            self.validator_account.votes_cast.checked_add(1).unwrap();
+        if let Some(profile) = self
+            .profile
+            .as_mut()
+            .filter(|_| self.validator_account.profiled)
+        {
+            profile.record_validation();
         }
+
+        self.validation.set_inner(Validation {
+            validator: self.validator.key(),
+            candidate_account: self.candidate_account.key(),
+            vote: Some(vote),
+            commitment: [0u8; 32],
+            proof_hash,
+            tallied: false,
+            bump,
+        });
+
+        let candidate = &self.candidate_account;
+        let validated = ProofValidated {
+            challenge: self.challenge.key(),
+            candidate: candidate.candidate,
+            validator: self.validator.key(),
+            vote,
+            weight: self.validator_account.weight,
+            acceptance: candidate.acceptance,
+            rejections: candidate.rejections,
+        };
+        //reported once, when the vote gets the proof over the quorum and winning threshold
+        let reached = (!was_winning
+            && self.challenge.is_winning(
+                candidate.weight,
+                candidate.acceptance,
+                candidate.rejections,
+            ))
+        .then(|| candidate.threshold_reached());
+
+        Ok((validated, reached))
     }
 }
@@ -1,16 +1,19 @@
 use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token_interface;
 
-use crate::constants::{CANDIDATE_SEED, CHALLENGE_SEED, CONFIG_SEED, SERVICE_SEED};
+use crate::constants::{BASIS_POINTS, CANDIDATE_SEED, CHALLENGE_SEED, CONFIG_SEED, PROFILE_SEED};
 use crate::error::AaasError;
-use crate::{usdc_mint_value, AaasConfig, CandidateAccount, Challenge, Service, VALIDATION_PERIOD};
+use crate::{AaasConfig, CandidateAccount, Challenge, Profile, RewardWithdrawn};
 
+#[cfg_attr(feature = "event-cpi", event_cpi)]
 #[derive(Accounts)]
 pub struct WithdrawReward<'info> {
     #[account(mut)]
     pub winner: Signer<'info>,
 
     #[account(
+        mut,
         seeds = [CANDIDATE_SEED, challenge.service.as_ref(), challenge.key().as_ref(), winner.key().as_ref()],
         bump = winner_account.bump,
         has_one = challenge,
@@ -18,102 +21,120 @@ pub struct WithdrawReward<'info> {
     )]
     pub winner_account: Account<'info, CandidateAccount>,
 
+    ///winner history, required when the winner joined with it, left untouched otherwise
+    #[account(
+        mut,
+        seeds = [PROFILE_SEED, winner.key().as_ref()],
+        bump = profile.bump,
+    )]
+    pub profile: Option<Account<'info, Profile>>,
+
     #[account(
         seeds = [CONFIG_SEED],
         bump = config.bump,
     )]
     pub config: Account<'info, AaasConfig>,
 
-    #[account(
-        seeds = [SERVICE_SEED, service.id.key().as_ref()],
-        bump = service.bump
-    )]
-    pub service: Account<'info, Service>,
+    ///config admin, owns the treasury
+    #[account(address = config.admin)]
+    pub admin: SystemAccount<'info>,
 
     #[account(
+        mut,
         seeds = [CHALLENGE_SEED, challenge.service.as_ref(), challenge.id.key().as_ref()],
         bump = challenge.bump,
-        has_one = service,
+        constraint = !challenge.cancelled @ AaasError::ChallengeCancelled,
     )]
     pub challenge: Account<'info, Challenge>,
 
-    /// this is expected to be already exist Token Account for usdc
+    /// treasury token account of the challenge mint, created on first use
     #[account(
-        mut,
-        associated_token::mint = usdc_mint,
-        associated_token::authority = config.admin,
+        init_if_needed,
+        payer = winner,
+        associated_token::mint = mint,
+        associated_token::authority = admin,
+        associated_token::token_program = token_program
     )]
     pub treasury: InterfaceAccount<'info, token_interface::TokenAccount>,
 
     #[account(
-        constraint = usdc_mint_value().map_or(true, |expected| expected == usdc_mint.key()) 
-        @ AaasError::InvalidUSDC
+        address = challenge.mint @ AaasError::InvalidMint
     )]
-    pub usdc_mint: InterfaceAccount<'info, token_interface::Mint>,
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
 
     #[account(
         mut,
-        associated_token::mint = usdc_mint,
+        associated_token::mint = mint,
         associated_token::authority = challenge
     )]
     pub vault: InterfaceAccount<'info, token_interface::TokenAccount>,
 
     #[account(
         mut,
-        associated_token::mint = usdc_mint,
+        associated_token::mint = mint,
         associated_token::authority = winner,
     )]
     pub winner_ata: InterfaceAccount<'info, token_interface::TokenAccount>,
 
+    pub system_program: Program<'info, System>,
     pub token_program: Interface<'info, token_interface::TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
 }
 
 impl<'info> WithdrawReward<'info> {
-    pub fn handler(&mut self) -> Result<()> {
+    pub fn handler(&mut self) -> Result<RewardWithdrawn> {
         //check if the validate period is over
         let now = Clock::get()?.unix_timestamp as u64;
         require!(
-            now > self
-                .challenge
-                .end_time
-                .checked_add(VALIDATION_PERIOD)
-                .unwrap(),
+            now > self.challenge.validation_end(),
             AaasError::ValidationPeriod
         );
 
-        // Acceptance rate check
-        let acceptance_rate = (self.winner_account.acceptance as u64)
-            .checked_mul(10000)
-            .unwrap()
-            .checked_div(self.challenge.candidate_count as u64)
-            .unwrap();
+        //a winner can only withdraw once
+        require!(!self.winner_account.rewarded, AaasError::AlreadyRewarded);
 
+        //winners are only read from the finalized results
+        require!(self.challenge.finalized, AaasError::ChallengeNotFinalized);
+        require!(self.winner_account.winner, AaasError::WinningThreshold);
+        //penalties and the validator pool depend on every vote
+        require!(self.challenge.tallied(), AaasError::VotesNotTallied);
         require!(
-            acceptance_rate >= self.challenge.winning_threshold as u64,
-            AaasError::WinningThreshold
-        );
-        msg!(
-            "withdraw reward falls short of acceptance rate of {} with {} acceptance",
-            acceptance_rate,
-            self.winner_account.acceptance
+            !self.winner_account.profiled || self.profile.is_some(),
+            AaasError::ProfileRequired
         );
 
-        // Reward calculation
-        let losers = self.challenge.candidate_count - self.challenge.winner_count; //reduce repeated data fetching
-        let pre_tax_reward = self
+        // Reward calculation, the validator cut is set aside from the losers stake, the bonus is shared in full,
+        // along with the penalties nobody is eligible to claim
+        let reward_pool = self
             .challenge
-            .stake_amnt
-            .checked_mul(losers as u64)
+            .total_stake
+            .checked_sub(self.challenge.winner_stake)
+            .unwrap()
+            .checked_sub(self.challenge.validator_cut())
             .unwrap()
-            .checked_div(self.challenge.winner_count as u64)
+            .checked_add(self.challenge.bonus_pool)
+            .unwrap()
+            .checked_add(self.challenge.unclaimed_penalties())
             .unwrap();
-
-        // Fee calculation
-        const PERCENT_DIVISOR: u64 = 100;
+        let is_last_claimant =
+            self.challenge.rewarded_count.checked_add(1).unwrap() == self.challenge.winner_count;
+        let pre_tax_reward = if is_last_claimant {
+            //last winner takes the integer division dust, so the pool is paid out exactly
+            reward_pool.checked_sub(self.challenge.distributed).unwrap()
+        } else {
+            //shared pro-rata to the winner's stake, equal when everyone staked the same
+            (reward_pool as u128)
+                .checked_mul(self.winner_account.stake as u128)
+                .unwrap()
+                .checked_div(self.challenge.winner_stake as u128)
+                .unwrap() as u64
+        };
+
+        // Fee calculation, fee is in basis point
         let tax = pre_tax_reward
-            .checked_mul(self.service.fee as u64)
+            .checked_mul(self.challenge.fee as u64)
             .unwrap()
-            .checked_div(PERCENT_DIVISOR)
+            .checked_div(BASIS_POINTS)
             .unwrap();
 
         //transfer fee
@@ -122,7 +143,7 @@ impl<'info> WithdrawReward<'info> {
                 self.token_program.to_account_info(),
                 token_interface::TransferChecked {
                     from: self.vault.to_account_info(),
-                    mint: self.usdc_mint.to_account_info(),
+                    mint: self.mint.to_account_info(),
                     to: self.treasury.to_account_info(),
                     authority: self.challenge.to_account_info(),
                 },
@@ -134,17 +155,24 @@ impl<'info> WithdrawReward<'info> {
                 ]],
             ),
             tax,
-            self.usdc_mint.decimals,
+            self.mint.decimals,
         )?;
 
-        //transfer reward
-        let reward = pre_tax_reward - tax;
+        //transfer reward, along with the winner's own stake back, less the penalty
+        let penalty = if self.winner_account.penalized {
+            self.challenge.penalty_amount(self.winner_account.stake)
+        } else {
+            0
+        };
+        let reward = (pre_tax_reward - tax)
+            .checked_add(self.winner_account.stake - penalty)
+            .unwrap();
         token_interface::transfer_checked(
             CpiContext::new_with_signer(
                 self.token_program.to_account_info(),
                 token_interface::TransferChecked {
                     from: self.vault.to_account_info(),
-                    mint: self.usdc_mint.to_account_info(),
+                    mint: self.mint.to_account_info(),
                     to: self.winner_ata.to_account_info(),
                     authority: self.challenge.to_account_info(),
                 },
@@ -156,12 +184,34 @@ impl<'info> WithdrawReward<'info> {
                 ]],
             ),
             reward,
-            self.usdc_mint.decimals,
+            self.mint.decimals,
         )?;
 
         //update candiate account
         self.winner_account.rewarded = true;
-
-        Ok(())
+        if let Some(profile) = self
+            .profile
+            .as_mut()
+            .filter(|_| self.winner_account.profiled)
+        {
+            profile.record_win();
+        }
+
+        //update settlement in challenge
+        self.challenge.distributed = self
+            .challenge
+            .distributed
+            .checked_add(pre_tax_reward)
+            .unwrap();
+        self.challenge.rewarded_count = self.challenge.rewarded_count.checked_add(1).unwrap();
+        self.challenge.settled = self.challenge.is_settled();
+
+        Ok(RewardWithdrawn {
+            challenge: self.challenge.key(),
+            winner: self.winner.key(),
+            amount: reward,
+            fee: tax,
+            penalty,
+        })
     }
 }
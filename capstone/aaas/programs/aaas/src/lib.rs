@@ -1,5 +1,6 @@
 pub mod constants;
 pub mod error;
+pub mod events;
 pub mod instructions;
 pub mod state;
 
@@ -8,6 +9,7 @@ pub mod utils;
 use anchor_lang::prelude::*;
 
 pub use constants::*;
+pub use events::*;
 pub use instructions::*;
 pub use state::*;
 pub use utils::*;
@@ -17,55 +19,363 @@ declare_id!("3SjXbrVTBAxCpLT9fdYuSaMJnpHa1fxx7ncBFvoQsnrE");
 #[program]
 pub mod aaas {
     use super::*;
+    use crate::events::emit_event;
 
     pub fn initialize(ctx: Context<Initialize>, signers: Vec<Pubkey>, threshold: u8) -> Result<()> {
-        ctx.accounts
-            .handler(signers, threshold, ctx.bumps, ctx.remaining_accounts)
+        let event = ctx.accounts.handler(signers, threshold, &ctx.bumps)?;
+        emit_event!(ctx, event);
+        Ok(())
     }
 
-    pub fn initialize_service(ctx: Context<InitService>, id: Pubkey, fee: u16) -> Result<()> {
-        ctx.accounts
-            .handler(id, fee, ctx.bumps, ctx.remaining_accounts)
+    pub fn create_proposal(
+        ctx: Context<CreateProposal>,
+        id: Pubkey,
+        action: AdminAction,
+    ) -> Result<()> {
+        let event = ctx.accounts.handler(id, action, &ctx.bumps)?;
+        emit_event!(ctx, event);
+        Ok(())
+    }
+
+    pub fn approve_proposal(ctx: Context<ApproveProposal>) -> Result<()> {
+        let event = ctx.accounts.handler()?;
+        emit_event!(ctx, event);
+        Ok(())
+    }
+
+    pub fn revoke_approval(ctx: Context<RevokeApproval>) -> Result<()> {
+        let event = ctx.accounts.handler()?;
+        emit_event!(ctx, event);
+        Ok(())
+    }
+
+    pub fn cancel_proposal(ctx: Context<CancelProposal>) -> Result<()> {
+        let event = ctx.accounts.handler()?;
+        emit_event!(ctx, event);
+        Ok(())
+    }
+
+    pub fn initialize_service(ctx: Context<InitService>, id: Pubkey) -> Result<()> {
+        let event = ctx.accounts.handler(id, &ctx.bumps)?;
+        emit_event!(ctx, event);
+        Ok(())
+    }
+
+    pub fn update_service_fee(ctx: Context<UpdateServiceFee>) -> Result<()> {
+        let event = ctx.accounts.handler()?;
+        emit_event!(ctx, event);
+        Ok(())
+    }
+
+    pub fn update_service_limits(ctx: Context<UpdateServiceLimits>) -> Result<()> {
+        let event = ctx.accounts.handler()?;
+        emit_event!(ctx, event);
+        Ok(())
+    }
+
+    pub fn set_service_mints(ctx: Context<SetServiceMints>) -> Result<()> {
+        let event = ctx.accounts.handler()?;
+        emit_event!(ctx, event);
+        Ok(())
+    }
+
+    pub fn set_service_attestor(ctx: Context<SetServiceAttestor>) -> Result<()> {
+        let event = ctx.accounts.handler()?;
+        emit_event!(ctx, event);
+        Ok(())
+    }
+
+    pub fn pause_service(ctx: Context<PauseService>) -> Result<()> {
+        let event = ctx.accounts.handler(true)?;
+        emit_event!(ctx, event);
+        Ok(())
+    }
+
+    pub fn resume_service(ctx: Context<PauseService>) -> Result<()> {
+        let event = ctx.accounts.handler(false)?;
+        emit_event!(ctx, event);
+        Ok(())
+    }
+
+    pub fn close_service(ctx: Context<CloseService>) -> Result<()> {
+        let event = ctx.accounts.handler()?;
+        emit_event!(ctx, event);
+        Ok(())
+    }
+
+    pub fn rotate_signers(ctx: Context<RotateSigners>) -> Result<()> {
+        let event = ctx.accounts.handler()?;
+        emit_event!(ctx, event);
+        Ok(())
+    }
+
+    pub fn add_signer(ctx: Context<AddSigner>) -> Result<()> {
+        let event = ctx.accounts.handler()?;
+        emit_event!(ctx, event);
+        Ok(())
+    }
+
+    pub fn remove_signer(ctx: Context<RemoveSigner>) -> Result<()> {
+        let event = ctx.accounts.handler()?;
+        emit_event!(ctx, event);
+        Ok(())
+    }
+
+    pub fn set_threshold(ctx: Context<SetThreshold>) -> Result<()> {
+        let event = ctx.accounts.handler()?;
+        emit_event!(ctx, event);
+        Ok(())
     }
 
+    pub fn transfer_admin(ctx: Context<TransferAdmin>) -> Result<()> {
+        let event = ctx.accounts.handler()?;
+        emit_event!(ctx, event);
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub fn create_challenge(
         ctx: Context<CreateChallenge>,
         id: Pubkey,
         start_time: u64,
         end_time: u64,
         stake_amnt: u64,
+        validation_period: u64,
         proof: String,
         winning_threshold: u16,
+        quorum: u16,
+        weighting: VoteWeighting,
+        incentives: ValidatorIncentives,
+        reveal_period: u64,
+        milestones: Vec<u64>,
+        min_milestones: u8,
+        max_candidates: u32,
+        allowlist_root: Option<[u8; 32]>,
     ) -> Result<()> {
-        ctx.accounts.handler(
+        let event = ctx.accounts.handler(
             id,
             start_time,
             end_time,
             stake_amnt,
+            validation_period,
             proof,
             ctx.accounts.service.key(),
             winning_threshold,
-            ctx.bumps,
-        )
+            quorum,
+            weighting,
+            incentives,
+            reveal_period,
+            milestones,
+            min_milestones,
+            max_candidates,
+            allowlist_root,
+            &ctx.bumps,
+        )?;
+        emit_event!(ctx, event);
+        Ok(())
     }
 
-    pub fn join_challenge(ctx: Context<JoinChallenge>) -> Result<()> {
-        ctx.accounts.handler(ctx.bumps.candidate_account)
+    pub fn init_profile(ctx: Context<InitProfile>) -> Result<()> {
+        let event = ctx.accounts.handler(&ctx.bumps)?;
+        emit_event!(ctx, event);
+        Ok(())
+    }
+
+    pub fn join_challenge(
+        ctx: Context<JoinChallenge>,
+        stake: u64,
+        allowlist_proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        let event = ctx
+            .accounts
+            .handler(stake, allowlist_proof, ctx.bumps.candidate_account)?;
+        emit_event!(ctx, event);
+        Ok(())
     }
 
     pub fn exit_challenge(ctx: Context<ExitChallenge>) -> Result<()> {
-        ctx.accounts.handler()
+        let event = ctx.accounts.handler()?;
+        emit_event!(ctx, event);
+        Ok(())
     }
 
-    pub fn submit_proof(ctx: Context<SubmitProof>, proof: String) -> Result<()> {
-        ctx.accounts.handler(proof)
+    pub fn cancel_challenge(ctx: Context<CancelChallenge>) -> Result<()> {
+        let event = ctx.accounts.handler()?;
+        emit_event!(ctx, event);
+        Ok(())
     }
 
-    pub fn validate_proof(ctx: Context<ValidateProof>) -> Result<()> {
-        ctx.accounts.handler()
+    pub fn emergency_cancel_challenge(ctx: Context<EmergencyCancelChallenge>) -> Result<()> {
+        let event = ctx.accounts.handler()?;
+        emit_event!(ctx, event);
+        Ok(())
+    }
+
+    pub fn sponsor_challenge(ctx: Context<SponsorChallenge>, amount: u64) -> Result<()> {
+        let event = ctx.accounts.handler(amount, ctx.bumps.sponsorship)?;
+        emit_event!(ctx, event);
+        Ok(())
+    }
+
+    pub fn submit_proof(
+        ctx: Context<SubmitProof>,
+        proof_hash: [u8; 32],
+        proof: Option<String>,
+    ) -> Result<()> {
+        let event = ctx.accounts.handler(proof_hash, proof)?;
+        emit_event!(ctx, event);
+        Ok(())
+    }
+
+    pub fn validate_proof(
+        ctx: Context<ValidateProof>,
+        vote: Vote,
+        proof_hash: [u8; 32],
+    ) -> Result<()> {
+        let (validated, reached) = ctx
+            .accounts
+            .handler(vote, proof_hash, ctx.bumps.validation)?;
+        emit_event!(ctx, validated);
+        if let Some(reached) = reached {
+            emit_event!(ctx, reached);
+        }
+        Ok(())
+    }
+
+    pub fn submit_milestone(
+        ctx: Context<SubmitMilestone>,
+        index: u8,
+        proof_hash: [u8; 32],
+        proof: Option<String>,
+    ) -> Result<()> {
+        let event = ctx
+            .accounts
+            .handler(index, proof_hash, proof, ctx.bumps.milestone_proof)?;
+        emit_event!(ctx, event);
+        Ok(())
+    }
+
+    pub fn validate_milestone(
+        ctx: Context<ValidateMilestone>,
+        vote: Vote,
+        proof_hash: [u8; 32],
+    ) -> Result<()> {
+        let event = ctx
+            .accounts
+            .handler(vote, proof_hash, ctx.bumps.validation)?;
+        emit_event!(ctx, event);
+        Ok(())
+    }
+
+    pub fn finalize_milestone(ctx: Context<FinalizeMilestone>) -> Result<()> {
+        let event = ctx.accounts.handler()?;
+        emit_event!(ctx, event);
+        Ok(())
+    }
+
+    pub fn attest_result(ctx: Context<AttestResult>, passed: bool) -> Result<()> {
+        let (attested, reached) = ctx.accounts.handler(passed)?;
+        emit_event!(ctx, attested);
+        if let Some(reached) = reached {
+            emit_event!(ctx, reached);
+        }
+        Ok(())
+    }
+
+    pub fn commit_vote(
+        ctx: Context<CommitVote>,
+        commitment: [u8; 32],
+        proof_hash: [u8; 32],
+    ) -> Result<()> {
+        let event = ctx
+            .accounts
+            .handler(commitment, proof_hash, ctx.bumps.validation)?;
+        emit_event!(ctx, event);
+        Ok(())
+    }
+
+    pub fn reveal_vote(ctx: Context<RevealVote>, vote: Vote, salt: [u8; 32]) -> Result<()> {
+        let (validated, reached) = ctx.accounts.handler(vote, salt)?;
+        emit_event!(ctx, validated);
+        if let Some(reached) = reached {
+            emit_event!(ctx, reached);
+        }
+        Ok(())
+    }
+
+    pub fn finalize_challenge<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FinalizeChallenge<'info>>,
+    ) -> Result<()> {
+        let finalized = ctx.accounts.handler(ctx.remaining_accounts)?;
+        if let Some(finalized) = finalized {
+            emit_event!(ctx, finalized);
+        }
+        Ok(())
+    }
+
+    pub fn tally_vote(ctx: Context<TallyVote>) -> Result<()> {
+        let event = ctx.accounts.handler()?;
+        emit_event!(ctx, event);
+        Ok(())
+    }
+
+    pub fn claim_validator_reward(ctx: Context<ClaimValidatorReward>) -> Result<()> {
+        let event = ctx.accounts.handler()?;
+        emit_event!(ctx, event);
+        Ok(())
     }
 
     pub fn withdraw_reward(ctx: Context<WithdrawReward>) -> Result<()> {
-        ctx.accounts.handler()
+        let event = ctx.accounts.handler()?;
+        emit_event!(ctx, event);
+        Ok(())
+    }
+
+    pub fn claim_badge(ctx: Context<ClaimBadge>, service_id: Pubkey) -> Result<()> {
+        let event = ctx.accounts.handler(service_id, &ctx.bumps)?;
+        emit_event!(ctx, event);
+        Ok(())
+    }
+
+    pub fn refund_stake(ctx: Context<RefundStake>) -> Result<()> {
+        let event = ctx.accounts.handler()?;
+        emit_event!(ctx, event);
+        Ok(())
+    }
+
+    pub fn refund_sponsorship(ctx: Context<RefundSponsorship>) -> Result<()> {
+        let event = ctx.accounts.handler()?;
+        emit_event!(ctx, event);
+        Ok(())
+    }
+
+    pub fn close_validation(ctx: Context<CloseValidation>) -> Result<()> {
+        let event = ctx.accounts.handler()?;
+        emit_event!(ctx, event);
+        Ok(())
+    }
+
+    pub fn close_candidate(ctx: Context<CloseCandidate>) -> Result<()> {
+        let event = ctx.accounts.handler()?;
+        emit_event!(ctx, event);
+        Ok(())
+    }
+
+    pub fn close_sponsorship(ctx: Context<CloseSponsorship>) -> Result<()> {
+        let event = ctx.accounts.handler()?;
+        emit_event!(ctx, event);
+        Ok(())
+    }
+
+    pub fn close_milestone(ctx: Context<CloseMilestone>, index: u8) -> Result<()> {
+        let event = ctx.accounts.handler(index)?;
+        emit_event!(ctx, event);
+        Ok(())
+    }
+
+    pub fn close_challenge(ctx: Context<CloseChallenge>) -> Result<()> {
+        let event = ctx.accounts.handler()?;
+        emit_event!(ctx, event);
+        Ok(())
     }
 }
@@ -1,4 +1,9 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+use crate::constants::{BASIS_POINTS, MAX_MILESTONES, MAX_SERVICE_MINTS, MAX_SIGNERS};
+use crate::error::AaasError;
+use crate::events::WinnerThresholdReached;
 
 #[account]
 #[derive(InitSpace)]
@@ -10,6 +15,24 @@ pub struct AaasConfig {
     pub admin: Pubkey,
 }
 
+impl AaasConfig {
+    /// signer set invariants, checked whenever signers or threshold are written
+    pub fn validate_signers(signers: &[Pubkey], threshold: u8) -> Result<()> {
+        require!(
+            !signers.is_empty() && signers.len() <= MAX_SIGNERS,
+            AaasError::InvalidSigners
+        );
+        for (i, signer) in signers.iter().enumerate() {
+            require!(!signers[..i].contains(signer), AaasError::DuplicateSigner);
+        }
+        require!(
+            threshold > 0 && threshold as usize <= signers.len(),
+            AaasError::InvalidThreshold
+        );
+        Ok(())
+    }
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct Service {
@@ -17,7 +40,97 @@ pub struct Service {
     pub id: Pubkey,
     ///service fee in basis point
     pub fee: u16,
+    ///basis point of a stake kept as fee, when a challenge ends without winners
+    pub forfeit_fee: u16,
     pub bump: u8,
+    ///paused services don't allow creating or joining challenges
+    pub paused: bool,
+    pub limits: ServiceLimits,
+    ///mints that challenges of the service can stake in
+    #[max_len(10)]
+    pub mints: Vec<Pubkey>,
+    ///trusted key attesting candidates results, instead of peer votes, None for peer validation
+    pub attestor: Option<Pubkey>,
+    ///challenges created and not closed yet, the service can't be closed till it's 0
+    pub challenge_count: u32,
+}
+
+impl Service {
+    pub fn validate_fee(fee: u16) -> Result<()> {
+        require!(fee as u64 <= BASIS_POINTS, AaasError::InvalidFee);
+        Ok(())
+    }
+
+    pub fn validate_mints(mints: &[Pubkey]) -> Result<()> {
+        require!(
+            mints.len() <= MAX_SERVICE_MINTS,
+            AaasError::InvalidServiceMints
+        );
+        for (i, mint) in mints.iter().enumerate() {
+            require!(!mints[..i].contains(mint), AaasError::InvalidServiceMints);
+        }
+        Ok(())
+    }
+}
+
+///bounds that every challenge of a service has to respect
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct ServiceLimits {
+    ///minimum challenge duration (end_time - start_time) in seconds
+    pub min_duration: u64,
+    ///maximum challenge duration in seconds
+    pub max_duration: u64,
+    ///minimum validation period after the challenge ends, in seconds
+    pub min_validation_period: u64,
+    ///maximum validation period in seconds
+    pub max_validation_period: u64,
+    ///reputation a candidate needs to join the service challenges
+    pub min_reputation: u64,
+}
+
+impl ServiceLimits {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.min_duration > 0 && self.min_duration <= self.max_duration,
+            AaasError::InvalidServiceLimits
+        );
+        require!(
+            self.min_validation_period > 0
+                && self.min_validation_period <= self.max_validation_period,
+            AaasError::InvalidServiceLimits
+        );
+        Ok(())
+    }
+}
+
+///optional rewards for candidates reviewing their peers, and penalties for voting against the outcome
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct ValidatorIncentives {
+    ///basis point of the losers stake set aside for the validators
+    pub reward: u16,
+    ///votes a candidate must cast to share the validator pool
+    pub min_votes: u16,
+    ///basis point of a validator's votes that must agree with the final outcome, to avoid the penalty
+    pub min_agreement: u16,
+    ///basis point of the stake a penalized validator loses to the validator pool
+    pub penalty: u16,
+}
+
+impl ValidatorIncentives {
+    pub fn validate(&self, forfeit_fee: u16) -> Result<()> {
+        require!(
+            self.reward as u64 <= BASIS_POINTS
+                && self.min_agreement as u64 <= BASIS_POINTS
+                && (self.penalty as u64).checked_add(forfeit_fee as u64).unwrap() <= BASIS_POINTS,
+            AaasError::InvalidIncentives
+        );
+        Ok(())
+    }
+
+    ///votes only need to be tallied when they are rewarded or penalized
+    pub fn enabled(&self) -> bool {
+        self.reward > 0 || self.penalty > 0
+    }
 }
 
 #[account]
@@ -25,29 +138,588 @@ pub struct Service {
 pub struct Challenge {
     ///challenge id
     pub id: Pubkey,
+    ///paid the challenge and vault rent, and gets it back on close
+    pub creator: Pubkey,
+    ///mint of the stake, rewards and fees
+    pub mint: Pubkey,
     pub start_time: u64,
     pub end_time: u64,
+    ///minimum stake, candidates stake exactly this unless the weighting is Stake
     pub stake_amnt: u64,
+    ///how much a validator's vote counts
+    pub weighting: VoteWeighting,
+    ///votes are committed till commit_end, and revealed till reveal_end, both 0 for open voting
+    pub commit_end: u64,
+    pub reveal_end: u64,
+    ///sum of candidates weight, acceptance rate is measured against it
+    pub total_weight: u64,
+    ///sum of candidates stake
+    pub total_stake: u64,
+    ///sum of winners stake, losers stake is shared pro-rata to it
+    pub winner_stake: u64,
+    ///peer validation runs from start_time till end_time + validation_period, in seconds
+    pub validation_period: u64,
     pub service: Pubkey,
     pub bump: u8,
-    pub candidate_count: u8,
-    pub winner_count: u8,
-    ///winning acceptance threshold in basis point
+    pub candidate_count: u32,
+    ///most candidates that can join
+    pub max_candidates: u32,
+    pub winner_count: u32,
+    ///candidates whose result is recorded by finalize_challenge
+    pub finalized_count: u32,
+    ///every candidate's result is recorded, winner_count and winner_stake are final
+    pub finalized: bool,
+    ///winners who withdrew their reward
+    pub rewarded_count: u32,
+    ///losers stake paid out so far, including fee
+    pub distributed: u64,
+    ///every winner withdrew and the losers stake is fully paid out, or every candidate got refunded
+    pub settled: bool,
+    ///validation accounts not closed yet
+    pub validation_count: u32,
+    ///candidate accounts closed after settlement
+    pub closed_count: u32,
+    ///winning approval threshold in basis point, of the weight that voted on a proof
     pub winning_threshold: u16,
+    ///minimum weight that must vote on a proof, in basis point of the total weight
+    pub quorum: u16,
+    ///service fee in basis point at the time of creation, later fee changes don't apply
+    pub fee: u16,
+    ///service forfeit fee in basis point at the time of creation
+    pub forfeit_fee: u16,
+    ///candidates who got their stake refunded, when there are no winners
+    pub refunded_count: u32,
+    pub incentives: ValidatorIncentives,
+    ///votes compared to the final outcome by tally_vote
+    pub tallied_count: u32,
+    ///votes of validators with a profile, not tallied yet, their agreement goes to the profile
+    pub profiled_votes: u32,
+    ///stake taken from penalized validators
+    pub penalty_pool: u64,
+    ///validators sharing the validator pool
+    pub eligible_count: u32,
+    ///eligible validators who claimed their reward
+    pub validator_rewarded_count: u32,
+    ///validator pool paid out so far
+    pub validator_distributed: u64,
+    ///end time of each milestone, ascending and the last is end_time, empty for a single proof challenge
+    #[max_len(10)]
+    pub milestones: Vec<u64>,
+    ///milestones a candidate must pass to win
+    pub min_milestones: u8,
+    ///milestone proofs not closed yet
+    pub milestone_count: u32,
+    ///service attestor at the time of creation, its attestations replace peer votes
+    pub attestor: Option<Pubkey>,
+    ///merkle root of the candidates allowed to join, None for a public challenge
+    pub allowlist_root: Option<[u8; 32]>,
+    ///sponsored tokens, shared by the winners along with the losers stake
+    pub bonus_pool: u64,
+    ///sponsorship accounts not closed yet
+    pub sponsor_count: u32,
+    ///sponsors who got their bonus refunded, when there are no winners
+    pub sponsor_refunded_count: u32,
+    ///cancelled before the results were final, every candidate and sponsor gets refunded in full
+    pub cancelled: bool,
     #[max_len(200)]
     pub proof: String, //offchain link to proof description
 }
 
+impl Challenge {
+    pub fn validation_end(&self) -> u64 {
+        self.end_time.checked_add(self.validation_period).unwrap()
+    }
+
+    ///votes are committed as hashes then revealed, instead of cast openly
+    pub fn commit_reveal(&self) -> bool {
+        self.reveal_end > 0
+    }
+
+    pub fn validate_milestones(
+        milestones: &[u64],
+        min_milestones: u8,
+        start_time: u64,
+        end_time: u64,
+    ) -> Result<()> {
+        if milestones.is_empty() {
+            require!(min_milestones == 0, AaasError::InvalidMilestones);
+            return Ok(());
+        }
+        require!(
+            milestones.len() <= MAX_MILESTONES
+                && min_milestones > 0
+                && min_milestones as usize <= milestones.len()
+                && milestones.last() == Some(&end_time),
+            AaasError::InvalidMilestones
+        );
+        let mut prev = start_time;
+        for end in milestones {
+            require!(*end > prev, AaasError::InvalidMilestones);
+            prev = *end;
+        }
+        Ok(())
+    }
+
+    ///submission window of a milestone, from the previous milestone end (or start_time) till its end
+    pub fn milestone_window(&self, index: u8) -> Result<(u64, u64)> {
+        let i = index as usize;
+        require!(i < self.milestones.len(), AaasError::InvalidMilestone);
+        let start = if i == 0 {
+            self.start_time
+        } else {
+            self.milestones[i - 1]
+        };
+        Ok((start, self.milestones[i]))
+    }
+
+    ///a candidate wins by its attestation, its own proof, or by passing min_milestones of a milestone challenge
+    pub fn candidate_wins(&self, candidate: &CandidateAccount) -> Result<bool> {
+        if self.attestor.is_some() {
+            return Ok(candidate.attestation == Some(true));
+        }
+        if self.milestones.is_empty() {
+            return Ok(self.is_winning(
+                candidate.weight,
+                candidate.acceptance,
+                candidate.rejections,
+            ));
+        }
+        require!(
+            candidate.milestones_finalized == candidate.milestones_submitted,
+            AaasError::MilestonesNotFinalized
+        );
+        Ok(candidate.milestones_passed >= self.min_milestones)
+    }
+
+    /// approvals in basis point of the weight that voted, 0 if nobody voted
+    pub fn acceptance_rate(&self, acceptance: u64, rejections: u64) -> u64 {
+        let votes = acceptance.checked_add(rejections).unwrap();
+        if votes == 0 {
+            return 0;
+        }
+        acceptance
+            .checked_mul(BASIS_POINTS)
+            .unwrap()
+            .checked_div(votes)
+            .unwrap()
+    }
+
+    /// weight that voted in basis point of the weight that can vote, a candidate doesn't vote on its own proof
+    pub fn participation_rate(&self, weight: u64, acceptance: u64, rejections: u64) -> u64 {
+        let voters = self.total_weight.checked_sub(weight).unwrap();
+        if voters == 0 {
+            return 0;
+        }
+        acceptance
+            .checked_add(rejections)
+            .unwrap()
+            .checked_mul(BASIS_POINTS)
+            .unwrap()
+            .checked_div(voters)
+            .unwrap()
+    }
+
+    /// silent candidates don't count against a proof, as long as enough weight voted on it,
+    /// `weight` is the weight of the candidate owning the proof
+    pub fn is_winning(&self, weight: u64, acceptance: u64, rejections: u64) -> bool {
+        self.participation_rate(weight, acceptance, rejections) >= self.quorum as u64
+            && self.acceptance_rate(acceptance, rejections) >= self.winning_threshold as u64
+    }
+
+    ///every vote is compared to the final outcome, validation accounts are only closed once settled,
+    ///votes of profiled validators are always tallied, so their reputation doesn't depend on a crank
+    pub fn tallied(&self) -> bool {
+        self.profiled_votes == 0
+            && (!self.incentives.enabled() || self.tallied_count == self.validation_count)
+    }
+
+    ///losers stake set aside for the validators, only when there are winners and someone to pay
+    pub fn validator_cut(&self) -> u64 {
+        if self.winner_count == 0 || self.eligible_count == 0 {
+            return 0;
+        }
+        self.total_stake
+            .checked_sub(self.winner_stake)
+            .unwrap()
+            .checked_mul(self.incentives.reward as u64)
+            .unwrap()
+            .checked_div(BASIS_POINTS)
+            .unwrap()
+    }
+
+    ///penalties of winners go back to the winners pool, when no validator is eligible to share them
+    pub fn unclaimed_penalties(&self) -> u64 {
+        if self.eligible_count == 0 {
+            return self.penalty_pool;
+        }
+        0
+    }
+
+    pub fn penalty_amount(&self, stake: u64) -> u64 {
+        stake
+            .checked_mul(self.incentives.penalty as u64)
+            .unwrap()
+            .checked_div(BASIS_POINTS)
+            .unwrap()
+    }
+
+    ///stops the challenge, winners can't be finalized anymore and stakes are refunded without fee
+    pub fn cancel(&mut self) -> Result<()> {
+        require!(!self.cancelled, AaasError::ChallengeCancelled);
+        //finalized results can still be dropped, as long as nobody got paid by them
+        require!(!self.payouts_started(), AaasError::PayoutsStarted);
+        self.cancelled = true;
+        //a challenge nobody joined nor sponsored is already settled
+        self.settled = self.is_settled();
+        Ok(())
+    }
+
+    ///any reward, refund or validator reward paid out of the vault
+    pub fn payouts_started(&self) -> bool {
+        self.rewarded_count > 0
+            || self.refunded_count > 0
+            || self.sponsor_refunded_count > 0
+            || self.validator_rewarded_count > 0
+    }
+
+    ///winners or refunds are paid, and every eligible validator claimed
+    pub fn is_settled(&self) -> bool {
+        //a cancelled challenge may have winners counted by a partial finalization, but never pays them
+        let payouts_done = if self.winner_count > 0 && !self.cancelled {
+            self.rewarded_count == self.winner_count
+        } else {
+            self.refunded_count == self.candidate_count
+                && self.sponsor_refunded_count == self.sponsor_count
+        };
+        //validators of a cancelled challenge aren't paid, even when its votes were tallied before
+        payouts_done && (self.cancelled || self.validator_rewarded_count == self.eligible_count)
+    }
+
+    /// weight of a candidate's vote, reputation is read from the candidate's profile
+    pub fn candidate_weight(&self, stake: u64, reputation: u64) -> u64 {
+        match self.weighting {
+            VoteWeighting::Equal => 1,
+            VoteWeighting::Stake => stake,
+            VoteWeighting::Reputation => reputation.checked_add(1).unwrap(),
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum VoteWeighting {
+    ///one vote per candidate
+    Equal,
+    ///votes weigh the validator's stake, candidates can stake more than stake_amnt
+    Stake,
+    ///votes weigh 1 + the validator's profile reputation
+    Reputation,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum Vote {
+    Approve,
+    Reject,
+}
+
+impl Vote {
+    ///hash(vote || salt || validator || candidate_account) committed by commit_vote, the vote is its borsh byte,
+    ///the keys tie it to one validator and proof so it can't be copied
+    pub fn commitment(
+        &self,
+        salt: &[u8; 32],
+        validator: &Pubkey,
+        candidate_account: &Pubkey,
+    ) -> [u8; 32] {
+        hashv(&[
+            &[*self as u8],
+            salt,
+            validator.as_ref(),
+            candidate_account.as_ref(),
+        ])
+        .to_bytes()
+    }
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct CandidateAccount {
     /// challenge key
     pub challenge: Pubkey,
-    /// proof link
+    /// proof link, optional
     #[max_len(100)]
     pub proof: String,
+    /// sha256 of the proof artifact, votes are tied to it
+    pub proof_hash: [u8; 32],
     pub candidate: Pubkey,
-    pub acceptance: u16,
+    ///sum of the weights of approvals received
+    pub acceptance: u64,
+    ///sum of the weights of rejections received
+    pub rejections: u64,
+    ///staked amount
+    pub stake: u64,
+    ///weight of the candidate's votes
+    pub weight: u64,
+    ///result is recorded by finalize_challenge
+    pub finalized: bool,
+    ///reached the winning threshold and quorum, once finalized
+    pub winner: bool,
+    ///proofs of other candidates this candidate voted on
+    pub votes_cast: u32,
+    ///votes compared to the final outcome
+    pub votes_tallied: u32,
+    ///votes matching the final outcome
+    pub votes_agreed: u32,
+    ///voted against the outcome more than the min agreement allows
+    pub penalized: bool,
+    ///shares the validator pool
+    pub eligible: bool,
+    pub validator_rewarded: bool,
+    ///milestone proofs submitted, finalized and passed, in a milestone challenge
+    pub milestones_submitted: u8,
+    pub milestones_finalized: u8,
+    pub milestones_passed: u8,
     pub bump: u8,
     pub rewarded: bool,
+    ///stake refunded, in a challenge without winners
+    pub refunded: bool,
+    ///attested result, in an attestor validated challenge
+    pub attestation: Option<bool>,
+    ///joined with a profile, so the profile must be passed to record the candidate's history
+    pub profiled: bool,
+}
+
+impl CandidateAccount {
+    ///the proof hash is required, the link isn't
+    pub fn has_proof(&self) -> bool {
+        self.proof_hash != [0u8; 32]
+    }
+
+    ///adds a vote weight to the approvals or rejections, winners are counted by finalize_challenge
+    pub fn add_vote(&mut self, vote: Vote, weight: u64) {
+        match vote {
+            Vote::Approve => self.acceptance = self.acceptance.checked_add(weight).unwrap(),
+            Vote::Reject => self.rejections = self.rejections.checked_add(weight).unwrap(),
+        }
+    }
+
+    pub fn threshold_reached(&self) -> WinnerThresholdReached {
+        WinnerThresholdReached {
+            challenge: self.challenge,
+            candidate: self.candidate,
+            acceptance: self.acceptance,
+            rejections: self.rejections,
+        }
+    }
+}
+
+///admin actions that need the multisig threshold, encoded in a proposal
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub enum AdminAction {
+    CreateService {
+        id: Pubkey,
+        fee: u16,
+        forfeit_fee: u16,
+        limits: ServiceLimits,
+        #[max_len(10)]
+        mints: Vec<Pubkey>,
+    },
+    UpdateServiceFee {
+        id: Pubkey,
+        fee: u16,
+        forfeit_fee: u16,
+    },
+    RotateSigners {
+        #[max_len(5)]
+        signers: Vec<Pubkey>,
+        threshold: u8,
+    },
+    AddSigner {
+        signer: Pubkey,
+    },
+    RemoveSigner {
+        signer: Pubkey,
+    },
+    SetThreshold {
+        threshold: u8,
+    },
+    TransferAdmin {
+        admin: Pubkey,
+    },
+    PauseService {
+        id: Pubkey,
+    },
+    ResumeService {
+        id: Pubkey,
+    },
+    CloseService {
+        id: Pubkey,
+    },
+    UpdateServiceLimits {
+        id: Pubkey,
+        limits: ServiceLimits,
+    },
+    SetServiceMints {
+        id: Pubkey,
+        #[max_len(10)]
+        mints: Vec<Pubkey>,
+    },
+    SetServiceAttestor {
+        id: Pubkey,
+        attestor: Option<Pubkey>,
+    },
+    CancelChallenge {
+        challenge: Pubkey,
+    },
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Proposal {
+    ///proposal identifier
+    pub id: Pubkey,
+    ///config signer who created the proposal, and receives the rent back on execution
+    pub proposer: Pubkey,
+    pub action: AdminAction,
+    ///config signers who approved the proposal
+    #[max_len(5)]
+    pub approvals: Vec<Pubkey>,
+    ///approvals and execution are rejected after it, and anyone can cancel the proposal
+    pub expires_at: u64,
+    pub bump: u8,
+}
+
+impl Proposal {
+    pub fn is_expired(&self, now: u64) -> bool {
+        now > self.expires_at
+    }
+
+    /// approvals only count while the approver is still a config signer
+    pub fn approval_count(&self, config: &AaasConfig) -> usize {
+        self.approvals
+            .iter()
+            .filter(|approver| config.signers.contains(approver))
+            .count()
+    }
+
+    /// checks the proposal hasn't expired, and reached the config threshold
+    pub fn require_approved(&self, config: &AaasConfig, now: u64) -> Result<()> {
+        require!(!self.is_expired(now), AaasError::ProposalExpired);
+        require!(
+            self.approval_count(config) >= config.threshold as usize,
+            AaasError::MutliSignerThreshold
+        );
+        Ok(())
+    }
+}
+
+///a candidate's proof of one milestone, voted on like a candidate's proof
+#[account]
+#[derive(InitSpace)]
+pub struct MilestoneProof {
+    pub candidate_account: Pubkey,
+    pub index: u8,
+    /// proof link, optional
+    #[max_len(100)]
+    pub proof: String,
+    /// sha256 of the proof artifact, votes are tied to it
+    pub proof_hash: [u8; 32],
+    pub acceptance: u64,
+    pub rejections: u64,
+    ///result is recorded by finalize_milestone
+    pub finalized: bool,
+    pub passed: bool,
+    pub bump: u8,
+}
+
+impl MilestoneProof {
+    ///adds a vote weight to the approvals or rejections, passing is recorded by finalize_milestone
+    pub fn add_vote(&mut self, vote: Vote, weight: u64) {
+        match vote {
+            Vote::Approve => self.acceptance = self.acceptance.checked_add(weight).unwrap(),
+            Vote::Reject => self.rejections = self.rejections.checked_add(weight).unwrap(),
+        }
+    }
+}
+
+///a vote of a validator on a candidate's proof, or milestone proof
+#[account]
+#[derive(InitSpace)]
+pub struct Validation {
+    pub validator: Pubkey,
+    ///candidate account voted on, or milestone proof in a milestone challenge
+    pub candidate_account: Pubkey,
+    ///none till a committed vote is revealed, unrevealed votes are abstentions
+    pub vote: Option<Vote>,
+    ///hash(vote || salt || validator || candidate_account) of a committed vote, zeroed for open votes
+    pub commitment: [u8; 32],
+    ///proof hash the validator reviewed
+    pub proof_hash: [u8; 32],
+    ///compared to the final outcome by tally_vote
+    pub tallied: bool,
+    pub bump: u8,
+}
+
+///tokens a sponsor added to a challenge bonus pool
+#[account]
+#[derive(InitSpace)]
+pub struct Sponsorship {
+    pub challenge: Pubkey,
+    pub sponsor: Pubkey,
+    ///sum of the sponsor's deposits
+    pub amount: u64,
+    ///bonus got back, when nobody won
+    pub refunded: bool,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Profile {
+    pub owner: Pubkey,
+    ///challenges won plus votes that agreed with the final outcome
+    pub reputation: u64,
+    pub challenges_joined: u32,
+    pub challenges_won: u32,
+    pub challenges_lost: u32,
+    ///votes cast on proofs and milestone proofs, revealed ones for commit-reveal voting
+    pub proofs_validated: u32,
+    ///tallied votes that agreed with the final outcome
+    pub votes_agreed: u32,
+    pub bump: u8,
+}
+
+impl Profile {
+    ///losing doesn't take reputation away, it just doesn't add any
+    fn update_reputation(&mut self) {
+        self.reputation = (self.challenges_won as u64)
+            .checked_add(self.votes_agreed as u64)
+            .unwrap();
+    }
+
+    pub fn record_join(&mut self) {
+        self.challenges_joined = self.challenges_joined.checked_add(1).unwrap();
+    }
+
+    ///a challenge left before its start isn't counted as joined
+    pub fn record_exit(&mut self) {
+        self.challenges_joined = self.challenges_joined.checked_sub(1).unwrap();
+    }
+
+    pub fn record_win(&mut self) {
+        self.challenges_won = self.challenges_won.checked_add(1).unwrap();
+        self.update_reputation();
+    }
+
+    pub fn record_loss(&mut self) {
+        self.challenges_lost = self.challenges_lost.checked_add(1).unwrap();
+    }
+
+    pub fn record_validation(&mut self) {
+        self.proofs_validated = self.proofs_validated.checked_add(1).unwrap();
+    }
+
+    pub fn record_agreed_vote(&mut self) {
+        self.votes_agreed = self.votes_agreed.checked_add(1).unwrap();
+        self.update_reputation();
+    }
 }
@@ -1,21 +1,161 @@
 use anchor_lang::prelude::*;
-use std::str::FromStr;
+use anchor_lang::solana_program::hash::hashv;
+use anchor_lang::system_program;
+use anchor_spl::token_2022::spl_token_2022;
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::TransferFeeConfig;
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    BaseStateWithExtensions, StateWithExtensions,
+};
+use anchor_spl::token_interface;
 
-const USDC_MINT_MAINNET: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v"; //mainnet usdc
+use crate::error::AaasError;
 
-const USDC_MINT_DEVNET: &str = "4zMMC9srt5Ri5X14GAgXhaHii3GnPAEERYPJgZJDncDU"; //devnet usdc
+/// closes a program owned account that isn't deserialized as anchor account, and sends its rent to the destination
+pub fn close_account<'info>(
+    account: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+) -> Result<()> {
+    let lamports = account.lamports();
+    **destination.lamports.borrow_mut() = destination.lamports().checked_add(lamports).unwrap();
+    **account.lamports.borrow_mut() = 0;
 
-pub fn usdc_mint_value() -> Option<Pubkey> {
-    #[cfg(feature = "mainnet")]
-    {
-        return Some(Pubkey::from_str(USDC_MINT_MAINNET).unwrap());
+    account.assign(&System::id());
+    account.realloc(0, false).map_err(Into::into)
+}
+
+/// creates a pda the way anchor's `init` does, so lamports sent to its address beforehand don't block it
+pub fn create_pda_account<'info>(
+    payer: &AccountInfo<'info>,
+    account: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    lamports: u64,
+    space: u64,
+    owner: &Pubkey,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let current_lamports = account.lamports();
+    if current_lamports == 0 {
+        return system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                system_program::CreateAccount {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+                signer_seeds,
+            ),
+            lamports,
+            space,
+            owner,
+        );
     }
-    #[cfg(feature = "devnet")]
-    {
-        return Some(Pubkey::from_str(USDC_MINT_DEVNET).unwrap());
+
+    //create_account fails on a funded address, so it is topped up, allocated and assigned instead
+    let top_up = lamports.saturating_sub(current_lamports);
+    if top_up > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                system_program::Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            top_up,
+        )?;
     }
-    #[cfg(feature = "localnet")]
-    {
-        return None; //no restriction
+    system_program::allocate(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            system_program::Allocate {
+                account_to_allocate: account.clone(),
+            },
+            signer_seeds,
+        ),
+        space,
+    )?;
+    system_program::assign(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            system_program::Assign {
+                account_to_assign: account.clone(),
+            },
+            signer_seeds,
+        ),
+        owner,
+    )
+}
+
+/// transfer fee config of a token-2022 mint, None for spl-token mints or mints without the extension
+pub fn transfer_fee_config(
+    mint: &InterfaceAccount<token_interface::Mint>,
+) -> Result<Option<TransferFeeConfig>> {
+    let mint_info = mint.to_account_info();
+    if *mint_info.owner != spl_token_2022::ID {
+        return Ok(None);
     }
+    let data = mint_info.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+    Ok(mint_state.get_extension::<TransferFeeConfig>().ok().copied())
+}
+
+/// amount to send, so that the receiver gets exactly `amount` after the transfer fee
+pub fn gross_up_transfer_fee(
+    mint: &InterfaceAccount<token_interface::Mint>,
+    amount: u64,
+) -> Result<u64> {
+    let fee = match transfer_fee_config(mint)? {
+        Some(config) => config
+            .calculate_inverse_epoch_fee(Clock::get()?.epoch, amount)
+            .ok_or(AaasError::MathOverflow)?,
+        None => 0,
+    };
+    Ok(amount.checked_add(fee).ok_or(AaasError::MathOverflow)?)
+}
+
+/// public key and message of the only signature of an ed25519 program instruction,
+/// the ed25519 program already verified it, as the transaction would fail otherwise
+pub fn ed25519_signed_message(data: &[u8]) -> Result<(Pubkey, Vec<u8>)> {
+    let read_u16 = |at: usize| -> Result<u16> {
+        let bytes = data.get(at..at + 2).ok_or(AaasError::InvalidAttestation)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    };
+    let read = |offset: u16, len: usize| -> Result<&[u8]> {
+        data.get(offset as usize..offset as usize + len)
+            .ok_or(AaasError::InvalidAttestation.into())
+    };
+
+    //num signatures and a padding byte, followed by the offsets of each signature
+    require!(data.first() == Some(&1), AaasError::UnexpectedSignatures);
+    let signature_ix = read_u16(4)?;
+    let public_key_offset = read_u16(6)?;
+    let public_key_ix = read_u16(8)?;
+    let message_offset = read_u16(10)?;
+    let message_size = read_u16(12)?;
+    let message_ix = read_u16(14)?;
+
+    //offsets must point in the ed25519 instruction itself, or the signed data could be anywhere
+    require!(
+        signature_ix == u16::MAX && public_key_ix == u16::MAX && message_ix == u16::MAX,
+        AaasError::UnverifiableSignature
+    );
+
+    let public_key = Pubkey::try_from(read(public_key_offset, 32)?).unwrap();
+    let message = read(message_offset, message_size as usize)?.to_vec();
+    Ok((public_key, message))
+}
+
+/// checks a candidate is a leaf of the allowlist merkle tree, leaves are sha256(0x00 || candidate)
+/// and nodes sha256(0x01 || lower child || higher child), so the proof needs no left/right flags
+pub fn verify_allowlist_proof(proof: &[[u8; 32]], root: [u8; 32], candidate: &Pubkey) -> bool {
+    let leaf = hashv(&[&[0u8], candidate.as_ref()]).to_bytes();
+    let computed = proof.iter().fold(leaf, |node, sibling| {
+        let (lower, higher) = if node <= *sibling {
+            (node, *sibling)
+        } else {
+            (*sibling, node)
+        };
+        hashv(&[&[1u8], &lower, &higher]).to_bytes()
+    });
+    computed == root
 }
@@ -0,0 +1,442 @@
+mod utils;
+
+use aaas::error::AaasError;
+use aaas::{AaasConfig, CandidateAccount, Challenge, Service, Vote};
+use aaas_client::pda;
+use anchor_lang::prelude::Pubkey;
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use utils::*;
+
+#[test]
+fn initialize() {
+    let mut tv = create_values();
+    utils::initialize(&mut tv).unwrap();
+
+    let config: AaasConfig = get_account(&tv.svm, &pda::config().0);
+    assert_eq!(config.admin, tv.admin.pubkey());
+    let signers: Vec<Pubkey> = tv.signers.iter().map(|signer| signer.pubkey()).collect();
+    assert_eq!(config.signers, signers);
+    assert_eq!(config.threshold, tv.threshold);
+    assert!(tv.svm.get_account(&tv.treasury()).is_some());
+}
+
+#[test]
+fn service_needs_threshold_approvals() {
+    let mut tv = create_values();
+    utils::initialize(&mut tv).unwrap();
+
+    //only the proposer approved
+    let action = create_service_action(&tv);
+    let proposal_id = propose(&mut tv, action, 1);
+    let ix = initialize_service_ix(&tv, &proposal_id);
+    let admin = tv.admin.insecure_clone();
+    expect_error(
+        send(&mut tv.svm, std::slice::from_ref(&ix), &admin, &[]),
+        AaasError::MutliSignerThreshold,
+    );
+
+    //a signer can't approve twice
+    let approve = aaas_client::approve_proposal(&admin.pubkey(), &proposal_id);
+    expect_error(
+        send(&mut tv.svm, &[approve], &admin, &[]),
+        AaasError::AlreadyApproved,
+    );
+
+    //outsiders can't approve
+    let outsider = tv.challenge.creator.insecure_clone();
+    let approve = aaas_client::approve_proposal(&outsider.pubkey(), &proposal_id);
+    expect_error(
+        send(&mut tv.svm, &[approve], &outsider, &[]),
+        AaasError::NotASigner,
+    );
+
+    //the second signer reaches the threshold
+    let signer = tv.signers[1].insecure_clone();
+    let approve = aaas_client::approve_proposal(&signer.pubkey(), &proposal_id);
+    send(&mut tv.svm, &[approve], &signer, &[]).unwrap();
+    send(&mut tv.svm, &[ix], &admin, &[]).unwrap();
+
+    let service: Service = get_account(&tv.svm, &tv.service);
+    assert_eq!(service.id, tv.service_id);
+    assert_eq!(service.fee, tv.fee);
+    assert_eq!(service.forfeit_fee, tv.forfeit_fee);
+    assert_eq!(service.mints, vec![tv.mint]);
+}
+
+#[test]
+fn service_rejects_other_action() {
+    let mut tv = create_values();
+    utils::initialize(&mut tv).unwrap();
+
+    //approved, but for another service id
+    let mut action = create_service_action(&tv);
+    if let aaas::AdminAction::CreateService { id, .. } = &mut action {
+        *id = Pubkey::new_unique();
+    }
+    let approvals = tv.threshold as usize;
+    let proposal_id = propose(&mut tv, action, approvals);
+    let ix = initialize_service_ix(&tv, &proposal_id);
+    let admin = tv.admin.insecure_clone();
+    expect_error(
+        send(&mut tv.svm, &[ix], &admin, &[]),
+        AaasError::ProposalActionMismatch,
+    );
+
+    //approved, but for another action
+    let action = aaas::AdminAction::UpdateServiceFee {
+        id: tv.service_id,
+        fee: tv.fee,
+        forfeit_fee: tv.forfeit_fee,
+    };
+    let proposal_id = propose(&mut tv, action, approvals);
+    let ix = initialize_service_ix(&tv, &proposal_id);
+    expect_error(
+        send(&mut tv.svm, &[ix], &admin, &[]),
+        AaasError::ProposalActionMismatch,
+    );
+}
+
+#[test]
+fn create_challenge() {
+    let tv = setup();
+
+    let challenge: Challenge = get_account(&tv.svm, &tv.challenge.key);
+    assert_eq!(challenge.creator, tv.challenge.creator.pubkey());
+    assert_eq!(challenge.mint, tv.mint);
+    assert_eq!(challenge.start_time, tv.challenge.start_time);
+    assert_eq!(challenge.end_time, tv.challenge.end_time);
+    assert_eq!(challenge.stake_amnt, tv.challenge.stake_amnt);
+    assert_eq!(challenge.fee, tv.fee);
+    assert_eq!(challenge.candidate_count, 0);
+    assert_eq!(challenge.winner_count, 0);
+    assert_eq!(token_amount(&tv.svm, &tv.vault()), 0);
+}
+
+#[test]
+fn create_challenge_rejects_bad_times() {
+    let mut tv = create_values();
+    utils::initialize(&mut tv).unwrap();
+    initialize_service(&mut tv).unwrap();
+    let creator = tv.challenge.creator.insecure_clone();
+    let start_time = tv.challenge.start_time;
+
+    //ends before it starts
+    let ix = create_challenge_ix(&tv, start_time, start_time - 1);
+    expect_error(
+        send(&mut tv.svm, &[ix], &creator, &[]),
+        AaasError::InvalidEndTime,
+    );
+
+    //shorter than the service min_duration
+    let ix = create_challenge_ix(&tv, start_time, start_time + HOUR - 1);
+    expect_error(
+        send(&mut tv.svm, &[ix], &creator, &[]),
+        AaasError::ChallengeTooShort,
+    );
+
+    //longer than the service max_duration
+    let ix = create_challenge_ix(&tv, start_time, start_time + 30 * DAY + 1);
+    expect_error(
+        send(&mut tv.svm, &[ix], &creator, &[]),
+        AaasError::ChallengeTooLong,
+    );
+
+    //starts now
+    set_clock(&mut tv.svm, start_time);
+    let ix = create_challenge_ix(&tv, start_time, tv.challenge.end_time);
+    expect_error(
+        send(&mut tv.svm, &[ix], &creator, &[]),
+        AaasError::InvalidStartTime,
+    );
+    assert!(tv.svm.get_account(&tv.challenge.key).is_none());
+}
+
+#[test]
+fn join_challenge() {
+    let mut tv = setup();
+    let (candidate, ata) = new_candidate(&mut tv);
+    let balance = token_amount(&tv.svm, &ata);
+    utils::join_challenge(&mut tv, &candidate, &ata).unwrap();
+
+    let candidate_account: CandidateAccount =
+        get_account(&tv.svm, &tv.candidate_account(&candidate.pubkey()));
+    assert_eq!(candidate_account.candidate, candidate.pubkey());
+    assert_eq!(candidate_account.stake, tv.challenge.stake_amnt);
+    assert_eq!(candidate_account.acceptance, 0);
+    assert!(candidate_account.proof.is_empty());
+    assert!(!candidate_account.rewarded);
+
+    let challenge: Challenge = get_account(&tv.svm, &tv.challenge.key);
+    assert_eq!(challenge.candidate_count, 1);
+    assert_eq!(token_amount(&tv.svm, &tv.vault()), tv.challenge.stake_amnt);
+    assert_eq!(
+        token_amount(&tv.svm, &ata),
+        balance - tv.challenge.stake_amnt
+    );
+
+    //a candidate joins once, its candidate account already exists
+    assert!(utils::join_challenge(&mut tv, &candidate, &ata).is_err());
+}
+
+#[test]
+fn join_challenge_closes_at_start() {
+    let mut tv = setup();
+    let (candidate, ata) = new_candidate(&mut tv);
+
+    set_clock(&mut tv.svm, tv.challenge.start_time - 1);
+    join_with_new_candidate(&mut tv);
+
+    set_clock(&mut tv.svm, tv.challenge.start_time);
+    expect_error(
+        utils::join_challenge(&mut tv, &candidate, &ata),
+        AaasError::ChallengeStarted,
+    );
+    assert!(tv
+        .svm
+        .get_account(&tv.candidate_account(&candidate.pubkey()))
+        .is_none());
+}
+
+#[test]
+fn exit_challenge() {
+    let mut tv = setup();
+    join_with_new_candidate(&mut tv);
+    let (candidate, ata) = join_with_new_candidate(&mut tv);
+    let balance = token_amount(&tv.svm, &ata);
+
+    let ix = aaas_client::exit_challenge(
+        &candidate.pubkey(),
+        &tv.service,
+        &tv.challenge.key,
+        &tv.mint,
+        &ata,
+        &anchor_spl::token::ID,
+        false,
+    );
+    send(&mut tv.svm, &[ix], &candidate, &[]).unwrap();
+
+    let candidate_account = tv
+        .svm
+        .get_account(&tv.candidate_account(&candidate.pubkey()));
+    assert!(candidate_account.is_none_or(|account| account.data.is_empty()));
+    let challenge: Challenge = get_account(&tv.svm, &tv.challenge.key);
+    assert_eq!(challenge.candidate_count, 1);
+    assert_eq!(
+        token_amount(&tv.svm, &ata),
+        balance + tv.challenge.stake_amnt
+    );
+}
+
+#[test]
+fn exit_challenge_closes_at_start() {
+    let mut tv = setup();
+    let (candidate, ata) = join_with_new_candidate(&mut tv);
+
+    set_clock(&mut tv.svm, tv.challenge.start_time);
+    let ix = aaas_client::exit_challenge(
+        &candidate.pubkey(),
+        &tv.service,
+        &tv.challenge.key,
+        &tv.mint,
+        &ata,
+        &anchor_spl::token::ID,
+        false,
+    );
+    expect_error(
+        send(&mut tv.svm, &[ix], &candidate, &[]),
+        AaasError::ChallengeStarted,
+    );
+
+    let challenge: Challenge = get_account(&tv.svm, &tv.challenge.key);
+    assert_eq!(challenge.candidate_count, 1);
+}
+
+#[test]
+fn submit_proof_within_challenge() {
+    let mut tv = setup();
+    let (candidate, _) = join_with_new_candidate(&mut tv);
+    let proof = "http://linkt/to/proof";
+
+    //proofs are accepted after start_time, till end_time
+    set_clock(&mut tv.svm, tv.challenge.start_time);
+    expect_error(
+        submit_proof(&mut tv, &candidate, proof),
+        AaasError::ChallengeNotStarted,
+    );
+
+    set_clock(&mut tv.svm, tv.challenge.start_time + 1);
+    submit_proof(&mut tv, &candidate, proof).unwrap();
+
+    let candidate_account: CandidateAccount =
+        get_account(&tv.svm, &tv.candidate_account(&candidate.pubkey()));
+    assert_eq!(candidate_account.proof, proof);
+    assert_eq!(candidate_account.proof_hash, proof_hash(proof));
+
+    //a proof can't be replaced
+    set_clock(&mut tv.svm, tv.challenge.end_time - 1);
+    expect_error(
+        submit_proof(&mut tv, &candidate, "https://new/proof/link"),
+        AaasError::DuplicateProof,
+    );
+    let candidate_account: CandidateAccount =
+        get_account(&tv.svm, &tv.candidate_account(&candidate.pubkey()));
+    assert_eq!(candidate_account.proof, proof);
+}
+
+#[test]
+fn submit_proof_closes_at_end() {
+    let mut tv = setup();
+    let (candidate, _) = join_with_new_candidate(&mut tv);
+
+    set_clock(&mut tv.svm, tv.challenge.end_time);
+    expect_error(
+        submit_proof(&mut tv, &candidate, "https://some/link/to/proof"),
+        AaasError::ChallengeEnded,
+    );
+
+    let candidate_account: CandidateAccount =
+        get_account(&tv.svm, &tv.candidate_account(&candidate.pubkey()));
+    assert!(candidate_account.proof.is_empty());
+}
+
+#[test]
+fn validate_proof() {
+    let mut tv = setup();
+    let (validator, _) = join_with_new_candidate(&mut tv);
+    let (approved, _) = join_with_new_candidate(&mut tv);
+    let (rejected, _) = join_with_new_candidate(&mut tv);
+
+    set_clock(&mut tv.svm, tv.challenge.end_time - 1000);
+    submit_proof(&mut tv, &approved, "http://linkt/to/proof").unwrap();
+    submit_proof(&mut tv, &rejected, "http://linkt/to/proof").unwrap();
+
+    //after the end, within the validation period
+    set_clock(&mut tv.svm, tv.challenge.end_time + 500);
+    utils::validate_proof(&mut tv, &validator, &approved.pubkey(), Vote::Approve).unwrap();
+    utils::validate_proof(&mut tv, &validator, &rejected.pubkey(), Vote::Reject).unwrap();
+
+    let candidate_account: CandidateAccount =
+        get_account(&tv.svm, &tv.candidate_account(&approved.pubkey()));
+    assert_eq!(candidate_account.acceptance, 1);
+    assert_eq!(candidate_account.rejections, 0);
+    let candidate_account: CandidateAccount =
+        get_account(&tv.svm, &tv.candidate_account(&rejected.pubkey()));
+    assert_eq!(candidate_account.acceptance, 0);
+    assert_eq!(candidate_account.rejections, 1);
+
+    //one vote per validator and proof, the validation account already exists
+    assert!(utils::validate_proof(&mut tv, &validator, &approved.pubkey(), Vote::Approve).is_err());
+}
+
+#[test]
+fn validate_proof_closes_at_validation_end() {
+    let mut tv = setup();
+    let (validator, _) = join_with_new_candidate(&mut tv);
+    let (candidate, _) = join_with_new_candidate(&mut tv);
+
+    set_clock(&mut tv.svm, tv.challenge.end_time - 500);
+    submit_proof(&mut tv, &candidate, "http://linkt/to/proof").unwrap();
+
+    set_clock(&mut tv.svm, tv.challenge.validation_end());
+    expect_error(
+        utils::validate_proof(&mut tv, &validator, &candidate.pubkey(), Vote::Approve),
+        AaasError::ValidationPeriodEnded,
+    );
+
+    let candidate_account: CandidateAccount =
+        get_account(&tv.svm, &tv.candidate_account(&candidate.pubkey()));
+    assert_eq!(candidate_account.acceptance, 0);
+}
+
+#[test]
+fn validate_proof_needs_a_proof() {
+    let mut tv = setup();
+    let (validator, _) = join_with_new_candidate(&mut tv);
+    let (candidate, _) = join_with_new_candidate(&mut tv);
+
+    set_clock(&mut tv.svm, tv.challenge.end_time + 500);
+    expect_error(
+        utils::validate_proof(&mut tv, &validator, &candidate.pubkey(), Vote::Approve),
+        AaasError::RequiredProof,
+    );
+}
+
+#[test]
+fn lifecycle() {
+    let mut tv = setup();
+
+    //with equal weighting, the 6 other candidates voting is the whole quorum
+    let candidates: Vec<(Keypair, Pubkey)> =
+        (0..7).map(|_| join_with_new_candidate(&mut tv)).collect();
+    let (winner, winner_ata) = &candidates[0];
+    let (loser, loser_ata) = &candidates[1];
+
+    set_clock(&mut tv.svm, tv.challenge.start_time + 500);
+    submit_proof(&mut tv, winner, "http://linkt/to/proof").unwrap();
+
+    //every other candidate approves the winner's proof, within the challenge
+    set_clock(&mut tv.svm, tv.challenge.start_time + 1000);
+    for (validator, _) in &candidates[1..] {
+        utils::validate_proof(&mut tv, validator, &winner.pubkey(), Vote::Approve).unwrap();
+    }
+    let winner_account: CandidateAccount =
+        get_account(&tv.svm, &tv.candidate_account(&winner.pubkey()));
+    assert_eq!(winner_account.acceptance, 6);
+
+    //no withdrawal during the validation period
+    set_clock(&mut tv.svm, tv.challenge.validation_end());
+    expect_error(
+        withdraw_reward(&mut tv, winner, winner_ata),
+        AaasError::ValidationPeriod,
+    );
+
+    //nor before the winners are finalized
+    set_clock(&mut tv.svm, tv.challenge.validation_end() + 1);
+    expect_error(
+        withdraw_reward(&mut tv, winner, winner_ata),
+        AaasError::ChallengeNotFinalized,
+    );
+
+    let candidate_accounts: Vec<Pubkey> = candidates
+        .iter()
+        .map(|(candidate, _)| tv.candidate_account(&candidate.pubkey()))
+        .collect();
+    finalize_challenge(&mut tv, &candidate_accounts);
+    let challenge: Challenge = get_account(&tv.svm, &tv.challenge.key);
+    assert!(challenge.finalized);
+    assert_eq!(challenge.finalized_count, 7);
+    assert_eq!(challenge.winner_count, 1);
+    assert_eq!(challenge.winner_stake, tv.challenge.stake_amnt);
+
+    let vault = token_amount(&tv.svm, &tv.vault());
+    let treasury = token_amount(&tv.svm, &tv.treasury());
+    let balance = token_amount(&tv.svm, winner_ata);
+    withdraw_reward(&mut tv, winner, winner_ata).unwrap();
+
+    //the winner gets back the stake and the losers stake, less the fees
+    let losers_stake = 6 * tv.challenge.stake_amnt;
+    let received = token_amount(&tv.svm, winner_ata) - balance;
+    let fees = token_amount(&tv.svm, &tv.treasury()) - treasury;
+    assert!(fees > 0);
+    assert_eq!(received + fees, tv.challenge.stake_amnt + losers_stake);
+    assert_eq!(token_amount(&tv.svm, &tv.vault()), vault - received - fees);
+
+    let winner_account: CandidateAccount =
+        get_account(&tv.svm, &tv.candidate_account(&winner.pubkey()));
+    assert!(winner_account.winner);
+    assert!(winner_account.rewarded);
+
+    expect_error(
+        withdraw_reward(&mut tv, winner, winner_ata),
+        AaasError::AlreadyRewarded,
+    );
+    expect_error(
+        withdraw_reward(&mut tv, loser, loser_ata),
+        AaasError::WinningThreshold,
+    );
+
+    let challenge: Challenge = get_account(&tv.svm, &tv.challenge.key);
+    assert_eq!(challenge.rewarded_count, 1);
+    assert!(challenge.settled);
+}
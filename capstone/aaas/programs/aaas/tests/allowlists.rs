@@ -0,0 +1,98 @@
+mod utils;
+
+use aaas::error::AaasError;
+use aaas::Challenge;
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::hash::hashv;
+use litesvm::types::TransactionResult;
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use utils::*;
+
+fn leaf(candidate: &Pubkey) -> [u8; 32] {
+    hashv(&[&[0u8], candidate.as_ref()]).to_bytes()
+}
+
+/// nodes hash their children sorted, so proofs need no left/right flags
+fn node(a: [u8; 32], b: [u8; 32]) -> [u8; 32] {
+    let (lower, higher) = if a <= b { (a, b) } else { (b, a) };
+    hashv(&[&[1u8], &lower, &higher]).to_bytes()
+}
+
+fn join_with(
+    tv: &mut TestValues,
+    candidate: &Keypair,
+    ata: &Pubkey,
+    proof: Vec<[u8; 32]>,
+) -> TransactionResult {
+    let ix = join_ix(tv, &candidate.pubkey(), ata, tv.challenge.stake_amnt, proof);
+    send(&mut tv.svm, &[ix], candidate, &[])
+}
+
+#[test]
+fn allowlisted_candidates_join() {
+    let mut tv = setup();
+    let candidates: Vec<(Keypair, Pubkey)> = (0..3).map(|_| new_candidate(&mut tv)).collect();
+    let leaves: Vec<[u8; 32]> = candidates
+        .iter()
+        .map(|(candidate, _)| leaf(&candidate.pubkey()))
+        .collect();
+    let pair = node(leaves[0], leaves[1]);
+    let root = node(pair, leaves[2]);
+    new_challenge(&mut tv, |args| args.allowlist_root = Some(root)).unwrap();
+
+    let (outsider, outsider_ata) = new_candidate(&mut tv);
+    expect_error(
+        join_with(&mut tv, &outsider, &outsider_ata, vec![]),
+        AaasError::NotAllowlisted,
+    );
+    //another candidate's proof doesn't work for the outsider
+    expect_error(
+        join_with(
+            &mut tv,
+            &outsider,
+            &outsider_ata,
+            vec![leaves[1], leaves[2]],
+        ),
+        AaasError::NotAllowlisted,
+    );
+    expect_error(
+        join_with(&mut tv, &candidates[0].0, &candidates[0].1, vec![leaves[2]]),
+        AaasError::NotAllowlisted,
+    );
+
+    let proofs = [
+        vec![leaves[1], leaves[2]],
+        vec![leaves[0], leaves[2]],
+        vec![pair],
+    ];
+    for ((candidate, ata), proof) in candidates.iter().zip(proofs) {
+        join_with(&mut tv, candidate, ata, proof).unwrap();
+    }
+    let challenge: Challenge = get_account(&tv.svm, &tv.challenge.key);
+    assert_eq!(challenge.candidate_count, 3);
+}
+
+#[test]
+fn full_challenge_rejects_candidates() {
+    let mut tv = setup();
+    expect_error(
+        new_challenge(&mut tv, |args| args.max_candidates = 0),
+        AaasError::InvalidMaxCandidates,
+    );
+    new_challenge(&mut tv, |args| args.max_candidates = 2).unwrap();
+    let (candidate, ata) = join_with_new_candidate(&mut tv);
+    join_with_new_candidate(&mut tv);
+
+    let (late, late_ata) = new_candidate(&mut tv);
+    expect_error(
+        join_challenge(&mut tv, &late, &late_ata),
+        AaasError::ChallengeFull,
+    );
+
+    //an exit makes room for another candidate
+    exit_challenge(&mut tv, &candidate, &ata).unwrap();
+    join_challenge(&mut tv, &late, &late_ata).unwrap();
+    let challenge: Challenge = get_account(&tv.svm, &tv.challenge.key);
+    assert_eq!(challenge.candidate_count, 2);
+}
@@ -0,0 +1,144 @@
+mod utils;
+
+use aaas::error::AaasError;
+use aaas::{CandidateAccount, Vote};
+use anchor_lang::prelude::Pubkey;
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use utils::*;
+
+/// two candidates join a challenge of the service attestor
+fn attested_challenge(tv: &mut TestValues) -> (Keypair, Vec<(Keypair, Pubkey)>) {
+    let attestor = Keypair::new();
+    set_attestor(tv, Some(attestor.pubkey())).unwrap();
+    new_challenge(tv, |_| {}).unwrap();
+    let candidates = (0..2).map(|_| join_with_new_candidate(tv)).collect();
+    (attestor, candidates)
+}
+
+#[test]
+fn attested_candidates_win() {
+    let mut tv = setup();
+    let (attestor, candidates) = attested_challenge(&mut tv);
+    let passed = candidates[0].0.pubkey();
+    let failed = candidates[1].0.pubkey();
+
+    expect_error(
+        attest_result(&mut tv, &attestor, &passed, true),
+        AaasError::ChallengeNotStarted,
+    );
+    set_clock(&mut tv.svm, tv.challenge.start_time + 1);
+    attest_result(&mut tv, &attestor, &passed, true).unwrap();
+    attest_result(&mut tv, &attestor, &failed, false).unwrap();
+
+    //an attestation is final, and peer votes are denied
+    expect_error(
+        attest_result(&mut tv, &attestor, &failed, true),
+        AaasError::AlreadyAttested,
+    );
+    submit_proof(&mut tv, &candidates[0].0, "http://linkt/to/proof").unwrap();
+    expect_error(
+        validate_proof(&mut tv, &candidates[1].0, &passed, Vote::Approve),
+        AaasError::AttestorChallenge,
+    );
+
+    set_clock(&mut tv.svm, tv.challenge.validation_end() + 1);
+    let accounts = candidate_accounts(&tv, &candidates);
+    finalize_challenge(&mut tv, &accounts);
+    let winner: CandidateAccount = get_account(&tv.svm, &accounts[0]);
+    assert!(winner.attestation == Some(true) && winner.winner);
+    let loser: CandidateAccount = get_account(&tv.svm, &accounts[1]);
+    assert!(loser.attestation == Some(false) && !loser.winner);
+}
+
+#[test]
+fn attestation_must_be_signed_by_the_attestor() {
+    let mut tv = setup();
+    let (attestor, candidates) = attested_challenge(&mut tv);
+    let candidate = candidates[0].0.pubkey();
+    let admin = tv.admin.insecure_clone();
+    set_clock(&mut tv.svm, tv.challenge.start_time + 1);
+
+    //signed by another key
+    expect_error(
+        attest_result(&mut tv, &Keypair::new(), &candidate, true),
+        AaasError::UnexpectedSigner,
+    );
+
+    //a failed result submitted as passed
+    let message = attestation_message(&tv, &candidate, false);
+    let ixs = [
+        ed25519_ix(&attestor, &message),
+        attest_ix(&tv, &candidate, true),
+    ];
+    expect_error(
+        send(&mut tv.svm, &ixs, &admin, &[]),
+        AaasError::UnexpectedMessage,
+    );
+
+    //the attestation of another candidate
+    let message = attestation_message(&tv, &candidates[1].0.pubkey(), true);
+    let ixs = [
+        ed25519_ix(&attestor, &message),
+        attest_ix(&tv, &candidate, true),
+    ];
+    expect_error(
+        send(&mut tv.svm, &ixs, &admin, &[]),
+        AaasError::UnexpectedMessage,
+    );
+
+    //the signature must come first in the transaction
+    let ix = attest_ix(&tv, &candidate, true);
+    expect_error(
+        send(&mut tv.svm, &[ix], &admin, &[]),
+        AaasError::UnexpectedProgram,
+    );
+
+    //the offsets must point in the ed25519 instruction
+    let message = attestation_message(&tv, &candidate, true);
+    let mut signature = ed25519_ix(&attestor, &message);
+    signature.data[14..16].copy_from_slice(&1u16.to_le_bytes());
+    let ixs = [signature, attest_ix(&tv, &candidate, true)];
+    assert!(send(&mut tv.svm, &ixs, &admin, &[]).is_err());
+
+    let candidate_account: CandidateAccount =
+        get_account(&tv.svm, &tv.candidate_account(&candidate));
+    assert!(candidate_account.attestation.is_none());
+}
+
+#[test]
+fn attestations_need_an_attestor_challenge() {
+    let mut tv = setup();
+    let attestor = Keypair::new();
+    let (candidate, _) = join_with_new_candidate(&mut tv);
+    //running challenges keep the attestor they were created with
+    set_attestor(&mut tv, Some(attestor.pubkey())).unwrap();
+    set_clock(&mut tv.svm, tv.challenge.start_time + 1);
+    expect_error(
+        attest_result(&mut tv, &attestor, &candidate.pubkey(), true),
+        AaasError::NotAttestorChallenge,
+    );
+}
+
+#[test]
+fn attestations_close_with_the_validation_period() {
+    let mut tv = setup();
+    let (attestor, candidates) = attested_challenge(&mut tv);
+    set_clock(&mut tv.svm, tv.challenge.validation_end());
+    expect_error(
+        attest_result(&mut tv, &attestor, &candidates[0].0.pubkey(), true),
+        AaasError::ValidationPeriodEnded,
+    );
+}
+
+#[test]
+fn cancelled_challenge_takes_no_attestations() {
+    let mut tv = setup();
+    let (attestor, candidates) = attested_challenge(&mut tv);
+    set_clock(&mut tv.svm, tv.challenge.start_time + 1);
+    emergency_cancel(&mut tv).unwrap();
+    expect_error(
+        attest_result(&mut tv, &attestor, &candidates[0].0.pubkey(), true),
+        AaasError::ChallengeCancelled,
+    );
+}
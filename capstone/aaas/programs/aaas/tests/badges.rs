@@ -0,0 +1,137 @@
+mod utils;
+
+use aaas::error::AaasError;
+use aaas::Vote;
+use aaas_client::pda;
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    BaseStateWithExtensions, ExtensionType, StateWithExtensions,
+};
+use anchor_spl::token_2022::spl_token_2022::state::Mint;
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use spl_token_group_interface::state::{TokenGroup, TokenGroupMember};
+use utils::*;
+
+///candidates[0] and candidates[1] win, the others lose
+fn challenge_with_two_winners(tv: &mut TestValues) -> Vec<(Keypair, Pubkey)> {
+    let candidates: Vec<(Keypair, Pubkey)> = (0..7).map(|_| join_with_new_candidate(tv)).collect();
+
+    set_clock(&mut tv.svm, tv.challenge.start_time + 500);
+    for (candidate, _) in &candidates[..2] {
+        submit_proof(tv, candidate, "http://linkt/to/proof").unwrap();
+    }
+    set_clock(&mut tv.svm, tv.challenge.start_time + 1000);
+    for (winner, _) in &candidates[..2] {
+        for (validator, _) in &candidates {
+            if validator.pubkey() != winner.pubkey() {
+                validate_proof(tv, validator, &winner.pubkey(), Vote::Approve).unwrap();
+            }
+        }
+    }
+
+    set_clock(&mut tv.svm, tv.challenge.validation_end() + 1);
+    let accounts = candidate_accounts(tv, &candidates);
+    finalize_challenge(tv, &accounts);
+    candidates
+}
+
+fn token_group(tv: &TestValues) -> TokenGroup {
+    let data = tv
+        .svm
+        .get_account(&pda::collection(&tv.service).0)
+        .unwrap()
+        .data;
+    let mint = StateWithExtensions::<Mint>::unpack(&data).unwrap();
+    *mint.get_extension::<TokenGroup>().unwrap()
+}
+
+#[test]
+fn claim_badge_joins_the_service_collection() {
+    let mut tv = setup();
+    let candidates = challenge_with_two_winners(&mut tv);
+    let collection = pda::collection(&tv.service).0;
+    assert!(tv.svm.get_account(&collection).is_none());
+
+    let (winner, _) = &candidates[0];
+    claim_badge(&mut tv, winner).unwrap();
+
+    //the first claim creates the collection, with the service as its authority
+    let group = token_group(&tv);
+    assert_eq!(
+        Option::<Pubkey>::from(group.update_authority),
+        Some(tv.service)
+    );
+    assert_eq!(u64::from(group.size), 1);
+
+    //one soulbound badge, no more can be minted
+    let badge = pda::badge(&tv.challenge.key, &winner.pubkey()).0;
+    let data = tv.svm.get_account(&badge).unwrap().data;
+    let mint = StateWithExtensions::<Mint>::unpack(&data).unwrap();
+    assert_eq!(mint.base.supply, 1);
+    assert!(mint.base.mint_authority.is_none());
+    assert!(mint
+        .get_extension_types()
+        .unwrap()
+        .contains(&ExtensionType::NonTransferable));
+    let member = mint.get_extension::<TokenGroupMember>().unwrap();
+    assert_eq!(member.group, collection);
+    assert_eq!(u64::from(member.member_number), 1);
+    assert_eq!(token_amount(&tv.svm, &badge_ata(&tv, &winner.pubkey())), 1);
+
+    //the next winner joins the same collection
+    let (winner, _) = &candidates[1];
+    claim_badge(&mut tv, winner).unwrap();
+    assert_eq!(u64::from(token_group(&tv).size), 2);
+}
+
+#[test]
+fn claim_badge_once_per_winner() {
+    let mut tv = setup();
+    let candidates = challenge_with_two_winners(&mut tv);
+    let (winner, _) = &candidates[0];
+
+    claim_badge(&mut tv, winner).unwrap();
+    expect_error(claim_badge(&mut tv, winner), AaasError::BadgeClaimed);
+    assert_eq!(u64::from(token_group(&tv).size), 1);
+}
+
+#[test]
+fn claim_badge_only_for_winners() {
+    let mut tv = setup();
+    let candidates = challenge_with_winner(&mut tv, 7);
+    let (winner, _) = &candidates[0];
+
+    //not before the results are final
+    set_clock(&mut tv.svm, tv.challenge.validation_end() + 1);
+    expect_error(
+        claim_badge(&mut tv, winner),
+        AaasError::ChallengeNotFinalized,
+    );
+
+    let accounts = candidate_accounts(&tv, &candidates);
+    finalize_challenge(&mut tv, &accounts);
+    let (loser, _) = &candidates[1];
+    expect_error(claim_badge(&mut tv, loser), AaasError::WinningThreshold);
+    claim_badge(&mut tv, winner).unwrap();
+}
+
+#[test]
+fn claim_badge_with_prefunded_accounts() {
+    let mut tv = setup();
+    let candidates = challenge_with_two_winners(&mut tv);
+    let (winner, _) = &candidates[0];
+
+    //anyone can send lamports to the collection and badge addresses, it doesn't block the claim
+    let collection = pda::collection(&tv.service).0;
+    let badge = pda::badge(&tv.challenge.key, &winner.pubkey()).0;
+    tv.svm.airdrop(&collection, 1_000).unwrap();
+    tv.svm.airdrop(&badge, LAMPORTS_PER_SOL).unwrap();
+    claim_badge(&mut tv, winner).unwrap();
+
+    assert_eq!(u64::from(token_group(&tv).size), 1);
+    let account = tv.svm.get_account(&badge).unwrap();
+    assert_eq!(account.owner, anchor_spl::token_2022::ID);
+    assert!(account.lamports >= LAMPORTS_PER_SOL);
+    assert_eq!(token_amount(&tv.svm, &badge_ata(&tv, &winner.pubkey())), 1);
+}
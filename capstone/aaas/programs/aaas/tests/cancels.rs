@@ -0,0 +1,176 @@
+mod utils;
+
+use aaas::error::AaasError;
+use aaas::{Challenge, Vote};
+use anchor_lang::prelude::Pubkey;
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use utils::*;
+
+/// three candidates joined, the first one submitted a proof
+fn started_challenge(tv: &mut TestValues) -> Vec<(Keypair, Pubkey)> {
+    let candidates: Vec<(Keypair, Pubkey)> = (0..3).map(|_| join_with_new_candidate(tv)).collect();
+    set_clock(&mut tv.svm, tv.challenge.start_time + 1);
+    submit_proof(tv, &candidates[0].0, "http://linkt/to/proof").unwrap();
+    candidates
+}
+
+#[test]
+fn cancelled_challenge_takes_no_proofs_nor_votes() {
+    let mut tv = setup();
+    let candidates = started_challenge(&mut tv);
+    emergency_cancel(&mut tv).unwrap();
+
+    expect_error(
+        submit_proof(&mut tv, &candidates[1].0, "http://linkt/to/proof"),
+        AaasError::ChallengeCancelled,
+    );
+    expect_error(
+        validate_proof(
+            &mut tv,
+            &candidates[1].0,
+            &candidates[0].0.pubkey(),
+            Vote::Approve,
+        ),
+        AaasError::ChallengeCancelled,
+    );
+}
+
+#[test]
+fn cancelled_challenge_takes_no_commits_nor_reveals() {
+    let mut tv = setup();
+    new_challenge(&mut tv, |args| args.reveal_period = HOUR).unwrap();
+    let candidates = started_challenge(&mut tv);
+    let reviewed = candidates[0].0.pubkey();
+    let (validator, _) = &candidates[1];
+    let salt = [7u8; 32];
+    let commitment =
+        Vote::Approve.commitment(&salt, &validator.pubkey(), &tv.candidate_account(&reviewed));
+    commit_vote(&mut tv, validator, &reviewed, commitment).unwrap();
+    emergency_cancel(&mut tv).unwrap();
+
+    expect_error(
+        commit_vote(&mut tv, &candidates[2].0, &reviewed, commitment),
+        AaasError::ChallengeCancelled,
+    );
+    set_clock(&mut tv.svm, tv.challenge.validation_end() - HOUR);
+    expect_error(
+        reveal_vote(&mut tv, validator, &reviewed, Vote::Approve, salt),
+        AaasError::ChallengeCancelled,
+    );
+}
+
+/// every candidate gets the full stake back, and the challenge settles
+fn refund_everyone(tv: &mut TestValues, candidates: &[(Keypair, Pubkey)]) {
+    for (candidate, ata) in candidates {
+        let balance = token_amount(&tv.svm, ata);
+        refund_stake(tv, candidate, ata).unwrap();
+        assert_eq!(
+            token_amount(&tv.svm, ata),
+            balance + tv.challenge.stake_amnt
+        );
+    }
+    let challenge: Challenge = get_account(&tv.svm, &tv.challenge.key);
+    assert!(challenge.settled);
+    assert_eq!(token_amount(&tv.svm, &tv.vault()), 0);
+}
+
+#[test]
+fn creator_cancels_before_the_start() {
+    let mut tv = setup();
+    let candidates: Vec<(Keypair, Pubkey)> =
+        (0..2).map(|_| join_with_new_candidate(&mut tv)).collect();
+    cancel_challenge(&mut tv).unwrap();
+    expect_error(cancel_challenge(&mut tv), AaasError::ChallengeCancelled);
+
+    let (late, late_ata) = new_candidate(&mut tv);
+    expect_error(
+        join_challenge(&mut tv, &late, &late_ata),
+        AaasError::ChallengeCancelled,
+    );
+    refund_everyone(&mut tv, &candidates);
+    expect_error(
+        refund_stake(&mut tv, &candidates[0].0, &candidates[0].1),
+        AaasError::AlreadyRefunded,
+    );
+}
+
+#[test]
+fn cancelled_challenge_cant_be_exited() {
+    let mut tv = setup();
+    let candidates: Vec<(Keypair, Pubkey)> =
+        (0..2).map(|_| join_with_new_candidate(&mut tv)).collect();
+    cancel_challenge(&mut tv).unwrap();
+
+    //a refunded stake isn't paid a second time by exiting
+    let (refunded, refunded_ata) = &candidates[0];
+    refund_stake(&mut tv, refunded, refunded_ata).unwrap();
+    expect_error(
+        exit_challenge(&mut tv, refunded, refunded_ata),
+        AaasError::ChallengeCancelled,
+    );
+
+    //nor can the others exit, refund is the only way out
+    let (candidate, ata) = &candidates[1];
+    expect_error(
+        exit_challenge(&mut tv, candidate, ata),
+        AaasError::ChallengeCancelled,
+    );
+    refund_stake(&mut tv, candidate, ata).unwrap();
+    let challenge: Challenge = get_account(&tv.svm, &tv.challenge.key);
+    assert!(challenge.settled);
+    assert_eq!(challenge.candidate_count, 2);
+    assert_eq!(token_amount(&tv.svm, &tv.vault()), 0);
+}
+
+#[test]
+fn only_the_creator_cancels_before_the_start() {
+    let mut tv = setup();
+    join_with_new_candidate(&mut tv);
+    let other = Keypair::new();
+    tv.svm.airdrop(&other.pubkey(), LAMPORTS_PER_SOL).unwrap();
+    let ix = aaas_client::cancel_challenge(&other.pubkey(), &tv.challenge.key);
+    assert!(send(&mut tv.svm, &[ix], &other, &[]).is_err());
+
+    set_clock(&mut tv.svm, tv.challenge.start_time);
+    expect_error(cancel_challenge(&mut tv), AaasError::ChallengeStarted);
+}
+
+#[test]
+fn multisig_cancels_a_started_challenge() {
+    let mut tv = setup();
+    let candidates = started_challenge(&mut tv);
+    emergency_cancel(&mut tv).unwrap();
+    expect_error(emergency_cancel(&mut tv), AaasError::ChallengeCancelled);
+    refund_everyone(&mut tv, &candidates);
+}
+
+#[test]
+fn multisig_cancels_a_finalized_challenge_before_payouts() {
+    let mut tv = setup();
+    let candidates = challenge_with_winner(&mut tv, 3);
+    set_clock(&mut tv.svm, tv.challenge.validation_end() + 1);
+    let accounts = candidate_accounts(&tv, &candidates);
+    finalize_challenge(&mut tv, &accounts);
+
+    //nothing was paid yet, the winners can't claim the reward anymore
+    emergency_cancel(&mut tv).unwrap();
+    let (winner, winner_ata) = &candidates[0];
+    expect_error(
+        withdraw_reward(&mut tv, winner, winner_ata),
+        AaasError::ChallengeCancelled,
+    );
+    refund_everyone(&mut tv, &candidates);
+}
+
+#[test]
+fn multisig_cant_cancel_after_a_payout() {
+    let mut tv = setup();
+    let candidates = challenge_with_winner(&mut tv, 2);
+    set_clock(&mut tv.svm, tv.challenge.validation_end() + 1);
+    let accounts = candidate_accounts(&tv, &candidates);
+    finalize_challenge(&mut tv, &accounts);
+    let (winner, winner_ata) = &candidates[0];
+    withdraw_reward(&mut tv, winner, winner_ata).unwrap();
+    expect_error(emergency_cancel(&mut tv), AaasError::PayoutsStarted);
+}
@@ -0,0 +1,211 @@
+mod utils;
+
+use aaas::error::AaasError;
+use aaas::Challenge;
+use aaas_client::pda;
+use anchor_lang::prelude::Pubkey;
+use litesvm::types::TransactionResult;
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use utils::*;
+
+fn close_candidate(tv: &mut TestValues, candidate: &Pubkey) -> TransactionResult {
+    let closer = tv.admin.insecure_clone();
+    let ix = aaas_client::close_candidate(
+        &closer.pubkey(),
+        &tv.service,
+        &tv.challenge.key,
+        candidate,
+        false,
+    );
+    send(&mut tv.svm, &[ix], &closer, &[])
+}
+
+fn close_validation(
+    tv: &mut TestValues,
+    validator: &Pubkey,
+    candidate: &Pubkey,
+) -> TransactionResult {
+    let closer = tv.admin.insecure_clone();
+    let ix = aaas_client::close_validation(
+        &closer.pubkey(),
+        &tv.service,
+        &tv.challenge.key,
+        &tv.candidate_account(candidate),
+        validator,
+    );
+    send(&mut tv.svm, &[ix], &closer, &[])
+}
+
+fn close_milestone(tv: &mut TestValues, candidate: &Pubkey, index: u8) -> TransactionResult {
+    let closer = tv.admin.insecure_clone();
+    let ix = aaas_client::close_milestone(
+        &closer.pubkey(),
+        &tv.service,
+        &tv.challenge.key,
+        candidate,
+        index,
+    );
+    send(&mut tv.svm, &[ix], &closer, &[])
+}
+
+#[test]
+fn close_accounts_once_settled() {
+    let mut tv = setup();
+    let candidates = challenge_with_winner(&mut tv, 7);
+    let winner = candidates[0].0.pubkey();
+
+    set_clock(&mut tv.svm, tv.challenge.validation_end() + 1);
+    let accounts = candidate_accounts(&tv, &candidates);
+    finalize_challenge(&mut tv, &accounts);
+
+    //the winner hasn't withdrawn yet
+    expect_error(
+        close_candidate(&mut tv, &winner),
+        AaasError::ChallengeNotSettled,
+    );
+    expect_error(
+        close_validation(&mut tv, &candidates[1].0.pubkey(), &winner),
+        AaasError::ChallengeNotSettled,
+    );
+    expect_error(close_challenge(&mut tv), AaasError::ChallengeNotSettled);
+
+    let (winner_keypair, winner_ata) = &candidates[0];
+    withdraw_reward(&mut tv, winner_keypair, winner_ata).unwrap();
+    let challenge: Challenge = get_account(&tv.svm, &tv.challenge.key);
+    assert!(challenge.settled);
+
+    //the challenge is closed last
+    expect_error(
+        close_challenge(&mut tv),
+        AaasError::ChallengeHasOpenAccounts,
+    );
+
+    for (validator, _) in &candidates[1..] {
+        let validator = validator.pubkey();
+        let validation = pda::validation(
+            &tv.service,
+            &tv.challenge.key,
+            &tv.candidate_account(&winner),
+            &validator,
+        )
+        .0;
+        let rent = tv.svm.get_account(&validation).unwrap().lamports;
+        let before = tv.svm.get_account(&validator).unwrap().lamports;
+        close_validation(&mut tv, &validator, &winner).unwrap();
+        assert!(tv.svm.get_account(&validation).is_none());
+        assert_eq!(
+            tv.svm.get_account(&validator).unwrap().lamports,
+            before + rent
+        );
+    }
+    for (candidate, _) in &candidates {
+        close_candidate(&mut tv, &candidate.pubkey()).unwrap();
+        assert!(tv
+            .svm
+            .get_account(&tv.candidate_account(&candidate.pubkey()))
+            .is_none());
+    }
+
+    let creator = tv.challenge.creator.pubkey();
+    let rent = tv.svm.get_account(&tv.challenge.key).unwrap().lamports
+        + tv.svm.get_account(&tv.vault()).unwrap().lamports;
+    let before = tv.svm.get_account(&creator).unwrap().lamports;
+    close_challenge(&mut tv).unwrap();
+    assert!(tv.svm.get_account(&tv.challenge.key).is_none());
+    assert!(tv.svm.get_account(&tv.vault()).is_none());
+    assert_eq!(
+        tv.svm.get_account(&creator).unwrap().lamports,
+        before + rent
+    );
+}
+
+#[test]
+fn close_challenge_nobody_joined() {
+    let mut tv = setup();
+
+    set_clock(&mut tv.svm, tv.challenge.validation_end());
+    expect_error(close_challenge(&mut tv), AaasError::ChallengeNotSettled);
+
+    set_clock(&mut tv.svm, tv.challenge.validation_end() + 1);
+    close_challenge(&mut tv).unwrap();
+    assert!(tv.svm.get_account(&tv.challenge.key).is_none());
+}
+
+#[test]
+fn close_milestones_once_settled() {
+    let mut tv = setup();
+    new_challenge(&mut tv, |args| {
+        args.milestones = vec![args.start_time + 2 * DAY, args.end_time];
+        args.min_milestones = 1;
+    })
+    .unwrap();
+    let candidates: Vec<(Keypair, Pubkey)> =
+        (0..2).map(|_| join_with_new_candidate(&mut tv)).collect();
+    let (candidate, _) = &candidates[0];
+    let candidate = candidate.pubkey();
+
+    //nobody votes on the milestone, so nobody wins
+    set_clock(&mut tv.svm, tv.challenge.start_time + 1);
+    submit_milestone(&mut tv, &candidates[0].0, 0, "http://linkt/to/run/0").unwrap();
+    set_clock(&mut tv.svm, tv.challenge.validation_end() + 1);
+    finalize_milestone(&mut tv, &candidate, 0).unwrap();
+    let accounts = candidate_accounts(&tv, &candidates);
+    finalize_challenge(&mut tv, &accounts);
+    expect_error(
+        close_milestone(&mut tv, &candidate, 0),
+        AaasError::ChallengeNotSettled,
+    );
+
+    for (candidate, ata) in &candidates {
+        refund_stake(&mut tv, candidate, ata).unwrap();
+    }
+    for (candidate, _) in &candidates {
+        close_candidate(&mut tv, &candidate.pubkey()).unwrap();
+    }
+    expect_error(
+        close_challenge(&mut tv),
+        AaasError::ChallengeHasOpenAccounts,
+    );
+
+    //the milestone rent goes back to the candidate, even with its candidate account closed
+    let milestone = milestone_proof(&tv, &candidate, 0);
+    let rent = tv.svm.get_account(&milestone).unwrap().lamports;
+    let before = tv.svm.get_account(&candidate).unwrap().lamports;
+    close_milestone(&mut tv, &candidate, 0).unwrap();
+    assert!(tv.svm.get_account(&milestone).is_none());
+    assert_eq!(
+        tv.svm.get_account(&candidate).unwrap().lamports,
+        before + rent
+    );
+    assert!(close_milestone(&mut tv, &candidate, 0).is_err());
+
+    close_challenge(&mut tv).unwrap();
+    assert!(tv.svm.get_account(&tv.challenge.key).is_none());
+}
+
+#[test]
+fn close_cancelled_challenge_once_refunded() {
+    let mut tv = setup();
+    let candidates: Vec<(Keypair, Pubkey)> =
+        (0..2).map(|_| join_with_new_candidate(&mut tv)).collect();
+    cancel_challenge(&mut tv).unwrap();
+
+    //no need to wait for the validation period, refunds settle it
+    let (candidate, ata) = &candidates[0];
+    refund_stake(&mut tv, candidate, ata).unwrap();
+    expect_error(close_challenge(&mut tv), AaasError::ChallengeNotSettled);
+    expect_error(
+        close_candidate(&mut tv, &candidate.pubkey()),
+        AaasError::ChallengeNotSettled,
+    );
+    let (candidate, ata) = &candidates[1];
+    refund_stake(&mut tv, candidate, ata).unwrap();
+
+    for (candidate, _) in &candidates {
+        close_candidate(&mut tv, &candidate.pubkey()).unwrap();
+    }
+    close_challenge(&mut tv).unwrap();
+    assert!(tv.svm.get_account(&tv.challenge.key).is_none());
+    assert!(tv.svm.get_account(&tv.vault()).is_none());
+}
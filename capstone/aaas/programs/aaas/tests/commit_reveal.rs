@@ -0,0 +1,165 @@
+mod utils;
+
+use aaas::error::AaasError;
+use aaas::{CandidateAccount, Validation, Vote};
+use aaas_client::pda;
+use anchor_lang::prelude::Pubkey;
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use utils::*;
+
+/// three candidates join a commit-reveal challenge, the first one submits a proof
+fn commit_reveal_challenge(tv: &mut TestValues) -> Vec<(Keypair, Pubkey)> {
+    new_challenge(tv, |args| args.reveal_period = HOUR).unwrap();
+    let candidates: Vec<(Keypair, Pubkey)> = (0..3).map(|_| join_with_new_candidate(tv)).collect();
+    set_clock(&mut tv.svm, tv.challenge.start_time + 1);
+    submit_proof(tv, &candidates[0].0, "http://linkt/to/proof").unwrap();
+    candidates
+}
+
+fn commit_end(challenge: &ChallengeValues) -> u64 {
+    challenge.validation_end() - HOUR
+}
+
+fn commitment(
+    validator: &Keypair,
+    candidate: &Pubkey,
+    tv: &TestValues,
+    vote: Vote,
+    salt: [u8; 32],
+) -> [u8; 32] {
+    vote.commitment(&salt, &validator.pubkey(), &tv.candidate_account(candidate))
+}
+
+#[test]
+fn commit_then_reveal() {
+    let mut tv = setup();
+    let candidates = commit_reveal_challenge(&mut tv);
+    let reviewed = candidates[0].0.pubkey();
+    let (validator, _) = &candidates[1];
+    let salt = [7u8; 32];
+
+    //open votes are denied
+    expect_error(
+        validate_proof(&mut tv, validator, &reviewed, Vote::Approve),
+        AaasError::CommitRevealVoting,
+    );
+    for (validator, _) in &candidates[1..] {
+        let commitment = commitment(validator, &reviewed, &tv, Vote::Approve, salt);
+        commit_vote(&mut tv, validator, &reviewed, commitment).unwrap();
+    }
+
+    //the vote stays hidden till the commit phase ends
+    let candidate_account = tv.candidate_account(&reviewed);
+    let validation = pda::validation(
+        &tv.service,
+        &tv.challenge.key,
+        &candidate_account,
+        &validator.pubkey(),
+    )
+    .0;
+    let committed: Validation = get_account(&tv.svm, &validation);
+    assert!(committed.vote.is_none());
+    expect_error(
+        reveal_vote(&mut tv, validator, &reviewed, Vote::Approve, salt),
+        AaasError::NotRevealPhase,
+    );
+
+    set_clock(&mut tv.svm, commit_end(&tv.challenge));
+
+    //the revealed vote must be the committed one
+    expect_error(
+        reveal_vote(&mut tv, validator, &reviewed, Vote::Reject, salt),
+        AaasError::InvalidReveal,
+    );
+    expect_error(
+        reveal_vote(&mut tv, validator, &reviewed, Vote::Approve, [8u8; 32]),
+        AaasError::InvalidReveal,
+    );
+    for (validator, _) in &candidates[1..] {
+        reveal_vote(&mut tv, validator, &reviewed, Vote::Approve, salt).unwrap();
+    }
+    expect_error(
+        reveal_vote(&mut tv, validator, &reviewed, Vote::Approve, salt),
+        AaasError::AlreadyRevealed,
+    );
+    let revealed: Validation = get_account(&tv.svm, &validation);
+    assert!(revealed.vote == Some(Vote::Approve));
+    let candidate: CandidateAccount = get_account(&tv.svm, &candidate_account);
+    assert_eq!(candidate.acceptance, 2);
+
+    //reveals end with the validation period
+    set_clock(&mut tv.svm, tv.challenge.validation_end());
+    let (validator, _) = &candidates[2];
+    expect_error(
+        reveal_vote(&mut tv, validator, &reviewed, Vote::Approve, salt),
+        AaasError::NotRevealPhase,
+    );
+
+    set_clock(&mut tv.svm, tv.challenge.validation_end() + 1);
+    let accounts = candidate_accounts(&tv, &candidates);
+    finalize_challenge(&mut tv, &accounts);
+    let candidate: CandidateAccount = get_account(&tv.svm, &candidate_account);
+    assert!(candidate.winner);
+}
+
+#[test]
+fn commits_end_before_the_reveals() {
+    let mut tv = setup();
+    let candidates = commit_reveal_challenge(&mut tv);
+    let reviewed = candidates[0].0.pubkey();
+    let (validator, _) = &candidates[1];
+    let commitment = commitment(validator, &reviewed, &tv, Vote::Approve, [7u8; 32]);
+
+    set_clock(&mut tv.svm, commit_end(&tv.challenge));
+    expect_error(
+        commit_vote(&mut tv, validator, &reviewed, commitment),
+        AaasError::CommitPhaseEnded,
+    );
+}
+
+#[test]
+fn copied_commitment_doesnt_reveal() {
+    let mut tv = setup();
+    let candidates = commit_reveal_challenge(&mut tv);
+    let reviewed = candidates[0].0.pubkey();
+    let (validator, _) = &candidates[1];
+    let (copier, _) = &candidates[2];
+    let salt = [7u8; 32];
+
+    //the copier commits the other validator's commitment, and reveals its vote once it's public
+    let commitment = commitment(validator, &reviewed, &tv, Vote::Approve, salt);
+    commit_vote(&mut tv, validator, &reviewed, commitment).unwrap();
+    commit_vote(&mut tv, copier, &reviewed, commitment).unwrap();
+    set_clock(&mut tv.svm, commit_end(&tv.challenge));
+    reveal_vote(&mut tv, validator, &reviewed, Vote::Approve, salt).unwrap();
+    expect_error(
+        reveal_vote(&mut tv, copier, &reviewed, Vote::Approve, salt),
+        AaasError::InvalidReveal,
+    );
+}
+
+#[test]
+fn unrevealed_votes_abstain() {
+    let mut tv = setup();
+    let candidates = commit_reveal_challenge(&mut tv);
+    let reviewed = candidates[0].0.pubkey();
+    let salt = [7u8; 32];
+    for (validator, _) in &candidates[1..] {
+        let commitment = commitment(validator, &reviewed, &tv, Vote::Approve, salt);
+        commit_vote(&mut tv, validator, &reviewed, commitment).unwrap();
+    }
+    set_clock(&mut tv.svm, commit_end(&tv.challenge));
+    reveal_vote(&mut tv, &candidates[1].0, &reviewed, Vote::Approve, salt).unwrap();
+
+    //half of the other candidates voted, below the quorum
+    set_clock(&mut tv.svm, tv.challenge.validation_end() + 1);
+    let accounts = candidate_accounts(&tv, &candidates);
+    finalize_challenge(&mut tv, &accounts);
+    let candidate: CandidateAccount = get_account(&tv.svm, &tv.candidate_account(&reviewed));
+    assert_eq!(candidate.acceptance, 1);
+    assert!(!candidate.winner);
+    let silent: CandidateAccount =
+        get_account(&tv.svm, &tv.candidate_account(&candidates[2].0.pubkey()));
+    assert_eq!(silent.votes_cast, 0);
+}
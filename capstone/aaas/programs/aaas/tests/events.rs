@@ -0,0 +1,305 @@
+mod utils;
+
+use aaas::{
+    AdminAction, BadgeMinted, CandidateAccount, ChallengeCancelled, ChallengeClosed,
+    ChallengeFinalized, ChallengeSponsored, ConfigInitialized, MilestoneFinalized,
+    MilestoneSubmitted, MilestoneValidated, ProposalApproved, ProposalCancelled, ProposalCreated,
+    ResultAttested, ServiceAttestorSet, SponsorshipClosed, SponsorshipRefunded, StakeRefunded,
+    ValidatorIncentives, ValidatorRewardClaimed, Vote, VoteCommitted, VoteTallied,
+    WinnerThresholdReached,
+};
+use aaas_client::pda;
+use anchor_lang::prelude::Pubkey;
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use utils::*;
+
+#[test]
+fn cancel_and_refund_events() {
+    let mut tv = setup();
+    let (candidate, ata) = join_with_new_candidate(&mut tv);
+
+    let cancelled: ChallengeCancelled = event(cancel_challenge(&mut tv));
+    assert_eq!(cancelled.challenge, tv.challenge.key);
+    assert!(!cancelled.emergency);
+
+    //no fee nor penalty on a cancelled challenge
+    let refunded: StakeRefunded = event(refund_stake(&mut tv, &candidate, &ata));
+    assert_eq!(refunded.challenge, tv.challenge.key);
+    assert_eq!(refunded.candidate, candidate.pubkey());
+    assert_eq!(refunded.amount, tv.challenge.stake_amnt);
+    assert_eq!(refunded.forfeit, 0);
+    assert_eq!(refunded.penalty, 0);
+}
+
+#[test]
+fn emergency_cancel_event() {
+    let mut tv = setup();
+    join_with_new_candidate(&mut tv);
+    set_clock(&mut tv.svm, tv.challenge.start_time + 1);
+
+    let cancelled: ChallengeCancelled = event(emergency_cancel(&mut tv));
+    assert_eq!(cancelled.challenge, tv.challenge.key);
+    assert!(cancelled.emergency);
+}
+
+#[test]
+fn sponsor_event() {
+    let mut tv = setup();
+    let (sponsor, sponsor_ata) = new_candidate(&mut tv);
+
+    sponsor_challenge(&mut tv, &sponsor, &sponsor_ata, 300).unwrap();
+    let sponsored: ChallengeSponsored =
+        event(sponsor_challenge(&mut tv, &sponsor, &sponsor_ata, 200));
+    assert_eq!(sponsored.challenge, tv.challenge.key);
+    assert_eq!(sponsored.sponsor, sponsor.pubkey());
+    assert_eq!(sponsored.amount, 200);
+    assert_eq!(sponsored.bonus_pool, 500);
+}
+
+#[test]
+fn finalize_event_once_every_candidate_is_finalized() {
+    let mut tv = setup();
+    let candidates = challenge_with_winner(&mut tv, 3);
+    set_clock(&mut tv.svm, tv.challenge.validation_end() + 1);
+    let accounts = candidate_accounts(&tv, &candidates);
+    let cranker = tv.admin.insecure_clone();
+
+    //the first batch leaves a candidate to finalize
+    let ix = aaas_client::finalize_challenge(&cranker.pubkey(), &tv.challenge.key, &accounts[..2]);
+    let meta = send(&mut tv.svm, &[ix], &cranker, &[]).unwrap();
+    assert!(events::<ChallengeFinalized>(&meta).is_empty());
+
+    let ix = aaas_client::finalize_challenge(&cranker.pubkey(), &tv.challenge.key, &accounts[2..]);
+    let finalized: ChallengeFinalized = event(send(&mut tv.svm, &[ix], &cranker, &[]));
+    assert_eq!(finalized.challenge, tv.challenge.key);
+    assert_eq!(finalized.winner_count, 1);
+    assert_eq!(finalized.winner_stake, tv.challenge.stake_amnt);
+}
+
+#[test]
+fn commit_vote_event() {
+    let mut tv = setup();
+    new_challenge(&mut tv, |args| args.reveal_period = HOUR).unwrap();
+    let candidates: Vec<(Keypair, Pubkey)> =
+        (0..2).map(|_| join_with_new_candidate(&mut tv)).collect();
+    let reviewed = candidates[0].0.pubkey();
+    let (validator, _) = &candidates[1];
+    set_clock(&mut tv.svm, tv.challenge.start_time + 1);
+    submit_proof(&mut tv, &candidates[0].0, "http://linkt/to/proof").unwrap();
+
+    let commitment = Vote::Approve.commitment(
+        &[7u8; 32],
+        &validator.pubkey(),
+        &tv.candidate_account(&reviewed),
+    );
+    let committed: VoteCommitted = event(commit_vote(&mut tv, validator, &reviewed, commitment));
+    assert_eq!(committed.challenge, tv.challenge.key);
+    assert_eq!(committed.candidate, reviewed);
+    assert_eq!(committed.validator, validator.pubkey());
+    assert_eq!(committed.commitment, commitment);
+}
+
+#[test]
+fn tally_and_validator_reward_events() {
+    let mut tv = setup();
+    new_challenge(&mut tv, |args| {
+        args.winning_threshold = 6000;
+        args.quorum = 5000;
+        args.incentives = ValidatorIncentives {
+            reward: 1000,
+            min_votes: 1,
+            min_agreement: 10000,
+            penalty: 1000,
+        };
+    })
+    .unwrap();
+    let candidates: Vec<(Keypair, Pubkey)> =
+        (0..3).map(|_| join_with_new_candidate(&mut tv)).collect();
+    let reviewed = candidates[0].0.pubkey();
+    set_clock(&mut tv.svm, tv.challenge.start_time + 1);
+    submit_proof(&mut tv, &candidates[0].0, "http://linkt/to/proof").unwrap();
+    for (validator, _) in &candidates[1..] {
+        validate_proof(&mut tv, validator, &reviewed, Vote::Approve).unwrap();
+    }
+    set_clock(&mut tv.svm, tv.challenge.validation_end() + 1);
+    let accounts = candidate_accounts(&tv, &candidates);
+    finalize_challenge(&mut tv, &accounts);
+
+    //the approval of the winner agreed, the validator's standing is decided with its only vote
+    for (validator, _) in &candidates[1..] {
+        let tallied: VoteTallied = event(tally_vote(&mut tv, &reviewed, &validator.pubkey()));
+        assert_eq!(tallied.challenge, tv.challenge.key);
+        assert_eq!(tallied.candidate, reviewed);
+        assert_eq!(tallied.validator, validator.pubkey());
+        assert!(tallied.vote == Some(Vote::Approve));
+        assert!(tallied.agreed);
+        assert!(tallied.eligible);
+        assert!(!tallied.penalized);
+    }
+
+    let (validator, ata) = &candidates[1];
+    let balance = token_amount(&tv.svm, ata);
+    let claimed: ValidatorRewardClaimed = event(claim_validator_reward(&mut tv, validator, ata));
+    assert_eq!(claimed.challenge, tv.challenge.key);
+    assert_eq!(claimed.validator, validator.pubkey());
+    assert!(claimed.amount > 0);
+    assert_eq!(token_amount(&tv.svm, ata), balance + claimed.amount);
+}
+
+#[test]
+fn milestone_events() {
+    let mut tv = setup();
+    new_challenge(&mut tv, |args| {
+        args.milestones = vec![args.start_time + 2 * DAY, args.end_time];
+        args.min_milestones = 1;
+    })
+    .unwrap();
+    let candidates: Vec<(Keypair, Pubkey)> =
+        (0..2).map(|_| join_with_new_candidate(&mut tv)).collect();
+    let (candidate, _) = &candidates[0];
+    let (validator, _) = &candidates[1];
+    let start = tv.challenge.start_time;
+
+    set_clock(&mut tv.svm, start + 1);
+    let submitted: MilestoneSubmitted = event(submit_milestone(
+        &mut tv,
+        candidate,
+        0,
+        "http://linkt/to/run/0",
+    ));
+    assert_eq!(submitted.challenge, tv.challenge.key);
+    assert_eq!(submitted.candidate, candidate.pubkey());
+    assert_eq!(submitted.index, 0);
+    assert_eq!(submitted.proof, "http://linkt/to/run/0");
+
+    let validated: MilestoneValidated = event(validate_milestone(
+        &mut tv,
+        validator,
+        &candidate.pubkey(),
+        0,
+        Vote::Approve,
+    ));
+    let validator_account: CandidateAccount =
+        get_account(&tv.svm, &tv.candidate_account(&validator.pubkey()));
+    assert_eq!(validated.candidate, candidate.pubkey());
+    assert_eq!(validated.validator, validator.pubkey());
+    assert_eq!(validated.index, 0);
+    assert!(validated.vote == Vote::Approve);
+    assert_eq!(validated.weight, validator_account.weight);
+    assert_eq!(validated.acceptance, validator_account.weight);
+    assert_eq!(validated.rejections, 0);
+
+    set_clock(
+        &mut tv.svm,
+        start + 2 * DAY + tv.challenge.validation_period + 1,
+    );
+    let finalized: MilestoneFinalized = event(finalize_milestone(&mut tv, &candidate.pubkey(), 0));
+    assert_eq!(finalized.candidate, candidate.pubkey());
+    assert_eq!(finalized.index, 0);
+    assert!(finalized.passed);
+    assert_eq!(finalized.milestones_passed, 1);
+}
+
+#[test]
+fn claim_badge_event() {
+    let mut tv = setup();
+    let candidates = challenge_with_winner(&mut tv, 3);
+    set_clock(&mut tv.svm, tv.challenge.validation_end() + 1);
+    let accounts = candidate_accounts(&tv, &candidates);
+    finalize_challenge(&mut tv, &accounts);
+
+    let (winner, _) = &candidates[0];
+    let minted: BadgeMinted = event(claim_badge(&mut tv, winner));
+    assert_eq!(minted.challenge, tv.challenge.key);
+    assert_eq!(minted.winner, winner.pubkey());
+    assert_eq!(
+        minted.badge,
+        pda::badge(&tv.challenge.key, &winner.pubkey()).0
+    );
+    assert_eq!(minted.collection, pda::collection(&tv.service).0);
+}
+
+#[test]
+fn config_and_proposal_events() {
+    let mut tv = create_values();
+    let initialized: ConfigInitialized = event(initialize(&mut tv));
+    assert_eq!(initialized.admin, tv.admin.pubkey());
+    assert_eq!(initialized.signers.len(), tv.signers.len());
+    assert_eq!(initialized.threshold, tv.threshold);
+
+    let id = Pubkey::new_unique();
+    let proposal = pda::proposal(&id).0;
+    let proposer = tv.signers[0].insecure_clone();
+    let ix = aaas_client::create_proposal(&proposer.pubkey(), id, create_service_action(&tv));
+    let created: ProposalCreated = event(send(&mut tv.svm, &[ix], &proposer, &[]));
+    assert_eq!(created.proposal, proposal);
+    assert_eq!(created.id, id);
+    assert_eq!(created.proposer, proposer.pubkey());
+    assert!(matches!(created.action, AdminAction::CreateService { .. }));
+
+    //the proposer's approval counts
+    let approver = tv.signers[1].insecure_clone();
+    let ix = aaas_client::approve_proposal(&approver.pubkey(), &id);
+    let approved: ProposalApproved = event(send(&mut tv.svm, &[ix], &approver, &[]));
+    assert_eq!(approved.proposal, proposal);
+    assert_eq!(approved.signer, approver.pubkey());
+    assert_eq!(approved.approvals, 2);
+
+    let ix = aaas_client::cancel_proposal(&proposer.pubkey(), &id, &proposer.pubkey());
+    let cancelled: ProposalCancelled = event(send(&mut tv.svm, &[ix], &proposer, &[]));
+    assert_eq!(cancelled.proposal, proposal);
+    assert_eq!(cancelled.canceller, proposer.pubkey());
+}
+
+#[test]
+fn set_attestor_event() {
+    let mut tv = setup();
+    let attestor = Pubkey::new_unique();
+
+    let set: ServiceAttestorSet = event(set_attestor(&mut tv, Some(attestor)));
+    assert_eq!(set.service, tv.service);
+    assert_eq!(set.attestor, Some(attestor));
+}
+
+#[test]
+fn failed_attestation_event() {
+    let mut tv = setup();
+    let attestor = Keypair::new();
+    set_attestor(&mut tv, Some(attestor.pubkey())).unwrap();
+    new_challenge(&mut tv, |_| {}).unwrap();
+    let (candidate, _) = join_with_new_candidate(&mut tv);
+    set_clock(&mut tv.svm, tv.challenge.start_time + 1);
+
+    //a failed result is logged too, without reaching the threshold
+    let meta = attest_result(&mut tv, &attestor, &candidate.pubkey(), false).unwrap();
+    let attested = events::<ResultAttested>(&meta);
+    assert_eq!(attested.len(), 1);
+    assert_eq!(attested[0].challenge, tv.challenge.key);
+    assert_eq!(attested[0].candidate, candidate.pubkey());
+    assert_eq!(attested[0].attestor, attestor.pubkey());
+    assert!(!attested[0].passed);
+    assert!(events::<WinnerThresholdReached>(&meta).is_empty());
+}
+
+#[test]
+fn refund_and_close_sponsorship_events() {
+    let mut tv = setup();
+    let (sponsor, sponsor_ata) = new_candidate(&mut tv);
+    sponsor_challenge(&mut tv, &sponsor, &sponsor_ata, 400).unwrap();
+    cancel_challenge(&mut tv).unwrap();
+
+    let refunded: SponsorshipRefunded = event(refund_sponsorship(&mut tv, &sponsor, &sponsor_ata));
+    assert_eq!(refunded.challenge, tv.challenge.key);
+    assert_eq!(refunded.sponsor, sponsor.pubkey());
+    assert_eq!(refunded.amount, 400);
+
+    let closed: SponsorshipClosed = event(close_sponsorship(&mut tv, &sponsor.pubkey()));
+    assert_eq!(closed.challenge, tv.challenge.key);
+    assert_eq!(closed.sponsor, sponsor.pubkey());
+
+    let closed: ChallengeClosed = event(close_challenge(&mut tv));
+    assert_eq!(closed.challenge, tv.challenge.key);
+    assert_eq!(closed.service, tv.service);
+    assert_eq!(closed.swept, 0);
+}
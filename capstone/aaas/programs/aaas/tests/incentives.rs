@@ -0,0 +1,289 @@
+mod utils;
+
+use aaas::error::AaasError;
+use aaas::{CandidateAccount, Challenge, ValidatorIncentives, Vote};
+use anchor_lang::prelude::Pubkey;
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use utils::*;
+
+/// four candidates, the first one submits a proof and the others vote on it as given
+fn challenge_with_votes(tv: &mut TestValues, votes: [Vote; 3]) -> Vec<(Keypair, Pubkey)> {
+    new_challenge(tv, |args| {
+        args.winning_threshold = 6000;
+        args.quorum = 5000;
+        args.incentives = ValidatorIncentives {
+            reward: 1000,
+            min_votes: 1,
+            min_agreement: 10000,
+            penalty: 1000,
+        };
+    })
+    .unwrap();
+    let candidates: Vec<(Keypair, Pubkey)> = (0..4).map(|_| join_with_new_candidate(tv)).collect();
+    let reviewed = candidates[0].0.pubkey();
+    set_clock(&mut tv.svm, tv.challenge.start_time + 1);
+    submit_proof(tv, &candidates[0].0, "http://linkt/to/proof").unwrap();
+    for ((validator, _), vote) in candidates[1..].iter().zip(votes) {
+        validate_proof(tv, validator, &reviewed, vote).unwrap();
+    }
+
+    set_clock(&mut tv.svm, tv.challenge.validation_end() + 1);
+    let accounts = candidate_accounts(tv, &candidates);
+    finalize_challenge(tv, &accounts);
+    candidates
+}
+
+fn tally_all(tv: &mut TestValues, candidates: &[(Keypair, Pubkey)]) {
+    let reviewed = candidates[0].0.pubkey();
+    for (validator, _) in &candidates[1..] {
+        tally_vote(tv, &reviewed, &validator.pubkey()).unwrap();
+    }
+}
+
+#[test]
+fn tally_rewards_the_agreeing_validators() {
+    let mut tv = setup();
+    let candidates = challenge_with_votes(&mut tv, [Vote::Approve, Vote::Approve, Vote::Reject]);
+    let (winner, winner_ata) = &candidates[0];
+    let (validator, validator_ata) = &candidates[1];
+    let reviewed = winner.pubkey();
+
+    //nothing is paid till every vote is compared to the outcome
+    expect_error(
+        withdraw_reward(&mut tv, winner, winner_ata),
+        AaasError::VotesNotTallied,
+    );
+    tally_vote(&mut tv, &reviewed, &validator.pubkey()).unwrap();
+    expect_error(
+        claim_validator_reward(&mut tv, validator, validator_ata),
+        AaasError::VotesNotTallied,
+    );
+    //a vote is only tallied once
+    assert!(tally_vote(&mut tv, &reviewed, &validator.pubkey()).is_err());
+    for (validator, _) in &candidates[2..] {
+        tally_vote(&mut tv, &reviewed, &validator.pubkey()).unwrap();
+    }
+
+    let agreed: CandidateAccount = get_account(&tv.svm, &tv.candidate_account(&validator.pubkey()));
+    assert_eq!(agreed.votes_agreed, 1);
+    assert!(agreed.eligible);
+    let (disagreed, disagreed_ata) = &candidates[3];
+    let penalized: CandidateAccount =
+        get_account(&tv.svm, &tv.candidate_account(&disagreed.pubkey()));
+    assert_eq!(penalized.votes_agreed, 0);
+    assert!(penalized.penalized && !penalized.eligible);
+
+    //the loser's stake already goes to the winner, there is nothing to penalize
+    let challenge: Challenge = get_account(&tv.svm, &tv.challenge.key);
+    assert_eq!(challenge.eligible_count, 2);
+    assert_eq!(challenge.penalty_pool, 0);
+    expect_error(
+        claim_validator_reward(&mut tv, disagreed, disagreed_ata),
+        AaasError::NotEligible,
+    );
+
+    //the validators share the cut of the losers stake
+    let stake = tv.challenge.stake_amnt;
+    let cut = 3 * stake * 1000 / 10000;
+    for (validator, ata) in &candidates[1..3] {
+        let balance = token_amount(&tv.svm, ata);
+        claim_validator_reward(&mut tv, validator, ata).unwrap();
+        assert_eq!(token_amount(&tv.svm, ata), balance + cut / 2);
+    }
+    expect_error(
+        claim_validator_reward(&mut tv, validator, validator_ata),
+        AaasError::ValidatorAlreadyRewarded,
+    );
+
+    //the winner gets the rest of it
+    let balance = token_amount(&tv.svm, winner_ata);
+    withdraw_reward(&mut tv, winner, winner_ata).unwrap();
+    let reward = 3 * stake - cut;
+    let tax = reward * tv.fee as u64 / 10000;
+    assert_eq!(
+        token_amount(&tv.svm, winner_ata),
+        balance + reward - tax + stake
+    );
+    let challenge: Challenge = get_account(&tv.svm, &tv.challenge.key);
+    assert!(challenge.settled);
+}
+
+#[test]
+fn penalty_goes_to_the_validators() {
+    let mut tv = setup();
+    //the proof is rejected, nobody wins
+    let candidates = challenge_with_votes(&mut tv, [Vote::Approve, Vote::Reject, Vote::Reject]);
+    tally_all(&mut tv, &candidates);
+
+    let (penalized, penalized_ata) = &candidates[1];
+    let stake = tv.challenge.stake_amnt;
+    let forfeit = stake * tv.forfeit_fee as u64 / 10000;
+    let penalty = stake * 1000 / 10000;
+    let challenge: Challenge = get_account(&tv.svm, &tv.challenge.key);
+    assert_eq!(challenge.winner_count, 0);
+    assert_eq!(challenge.penalty_pool, penalty);
+
+    //the penalty is kept from the refund
+    let balance = token_amount(&tv.svm, penalized_ata);
+    refund_stake(&mut tv, penalized, penalized_ata).unwrap();
+    assert_eq!(
+        token_amount(&tv.svm, penalized_ata),
+        balance + stake - forfeit - penalty
+    );
+    expect_error(
+        claim_validator_reward(&mut tv, penalized, penalized_ata),
+        AaasError::NotEligible,
+    );
+
+    //and shared by the validators who agreed with the outcome
+    for (validator, ata) in &candidates[2..] {
+        let balance = token_amount(&tv.svm, ata);
+        claim_validator_reward(&mut tv, validator, ata).unwrap();
+        assert_eq!(token_amount(&tv.svm, ata), balance + penalty / 2);
+    }
+}
+
+#[test]
+fn tally_waits_for_finalize() {
+    let mut tv = setup();
+    new_challenge(&mut tv, |args| {
+        args.incentives = ValidatorIncentives {
+            reward: 1000,
+            min_votes: 1,
+            min_agreement: 5000,
+            penalty: 0,
+        };
+    })
+    .unwrap();
+    let candidates = challenge_with_winner(&mut tv, 2);
+    expect_error(
+        tally_vote(
+            &mut tv,
+            &candidates[0].0.pubkey(),
+            &candidates[1].0.pubkey(),
+        ),
+        AaasError::ChallengeNotFinalized,
+    );
+}
+
+/// four candidates, the first two submit a proof, nobody can cast the min_votes to be eligible
+fn challenge_without_eligible_validators(
+    tv: &mut TestValues,
+    votes: [(usize, usize, Vote); 6],
+) -> Vec<(Keypair, Pubkey)> {
+    new_challenge(tv, |args| {
+        args.winning_threshold = 6000;
+        args.quorum = 5000;
+        args.incentives = ValidatorIncentives {
+            reward: 1000,
+            min_votes: 3,
+            min_agreement: 10000,
+            penalty: 1000,
+        };
+    })
+    .unwrap();
+    let candidates: Vec<(Keypair, Pubkey)> = (0..4).map(|_| join_with_new_candidate(tv)).collect();
+    set_clock(&mut tv.svm, tv.challenge.start_time + 1);
+    for (candidate, _) in &candidates[..2] {
+        submit_proof(tv, candidate, "http://linkt/to/proof").unwrap();
+    }
+    for (validator, reviewed, vote) in votes {
+        let reviewed = candidates[reviewed].0.pubkey();
+        validate_proof(tv, &candidates[validator].0, &reviewed, vote).unwrap();
+    }
+
+    set_clock(&mut tv.svm, tv.challenge.validation_end() + 1);
+    let accounts = candidate_accounts(tv, &candidates);
+    finalize_challenge(tv, &accounts);
+    for (validator, reviewed, _) in votes {
+        let reviewed = candidates[reviewed].0.pubkey();
+        tally_vote(tv, &reviewed, &candidates[validator].0.pubkey()).unwrap();
+    }
+    candidates
+}
+
+#[test]
+fn penalty_goes_to_the_winners_without_eligible_validators() {
+    let mut tv = setup();
+    //both proofs win, the second winner rejected the first one and is penalized
+    let candidates = challenge_without_eligible_validators(
+        &mut tv,
+        [
+            (1, 0, Vote::Reject),
+            (2, 0, Vote::Approve),
+            (3, 0, Vote::Approve),
+            (0, 1, Vote::Approve),
+            (2, 1, Vote::Approve),
+            (3, 1, Vote::Approve),
+        ],
+    );
+    let stake = tv.challenge.stake_amnt;
+    let penalty = stake * 1000 / 10000;
+    let challenge: Challenge = get_account(&tv.svm, &tv.challenge.key);
+    assert_eq!(challenge.winner_count, 2);
+    assert_eq!(challenge.eligible_count, 0);
+    assert_eq!(challenge.penalty_pool, penalty);
+
+    //the winners share the losers stake in full, and the penalty
+    let reward = (2 * stake + penalty) / 2;
+    let tax = reward * tv.fee as u64 / 10000;
+    let (winner, winner_ata) = &candidates[0];
+    let balance = token_amount(&tv.svm, winner_ata);
+    withdraw_reward(&mut tv, winner, winner_ata).unwrap();
+    assert_eq!(
+        token_amount(&tv.svm, winner_ata),
+        balance + reward - tax + stake
+    );
+    //the last winner also gets the integer division remainder
+    let reward = 2 * stake + penalty - reward;
+    let tax = reward * tv.fee as u64 / 10000;
+    let (penalized, penalized_ata) = &candidates[1];
+    let balance = token_amount(&tv.svm, penalized_ata);
+    withdraw_reward(&mut tv, penalized, penalized_ata).unwrap();
+    assert_eq!(
+        token_amount(&tv.svm, penalized_ata),
+        balance + reward - tax + stake - penalty
+    );
+
+    //nothing is left in the vault for the treasury
+    let challenge: Challenge = get_account(&tv.svm, &tv.challenge.key);
+    assert!(challenge.settled);
+    assert_eq!(token_amount(&tv.svm, &tv.vault()), 0);
+}
+
+#[test]
+fn no_penalty_without_eligible_validators() {
+    let mut tv = setup();
+    //both proofs lose, the approvals disagree with the outcome
+    let candidates = challenge_without_eligible_validators(
+        &mut tv,
+        [
+            (1, 0, Vote::Approve),
+            (2, 0, Vote::Reject),
+            (3, 0, Vote::Reject),
+            (0, 1, Vote::Reject),
+            (2, 1, Vote::Reject),
+            (3, 1, Vote::Reject),
+        ],
+    );
+    let stake = tv.challenge.stake_amnt;
+    let forfeit = stake * tv.forfeit_fee as u64 / 10000;
+    let challenge: Challenge = get_account(&tv.svm, &tv.challenge.key);
+    assert_eq!(challenge.winner_count, 0);
+    assert_eq!(challenge.eligible_count, 0);
+
+    //nobody can claim the penalty, so the penalized candidate only pays the forfeit fee
+    let (penalized, _) = &candidates[1];
+    let account: CandidateAccount =
+        get_account(&tv.svm, &tv.candidate_account(&penalized.pubkey()));
+    assert!(account.penalized);
+    for (candidate, ata) in &candidates {
+        let balance = token_amount(&tv.svm, ata);
+        refund_stake(&mut tv, candidate, ata).unwrap();
+        assert_eq!(token_amount(&tv.svm, ata), balance + stake - forfeit);
+    }
+    let challenge: Challenge = get_account(&tv.svm, &tv.challenge.key);
+    assert!(challenge.settled);
+    assert_eq!(token_amount(&tv.svm, &tv.vault()), 0);
+}
@@ -0,0 +1,162 @@
+mod utils;
+
+use aaas::error::AaasError;
+use aaas::{CandidateAccount, MilestoneProof, Vote};
+use anchor_lang::prelude::Pubkey;
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use utils::*;
+
+/// three candidates join a challenge with three milestones, two of them must pass
+fn milestone_challenge(tv: &mut TestValues) -> Vec<(Keypair, Pubkey)> {
+    new_challenge(tv, |args| {
+        args.milestones = vec![
+            args.start_time + 2 * DAY,
+            args.start_time + 4 * DAY,
+            args.end_time,
+        ];
+        args.min_milestones = 2;
+    })
+    .unwrap();
+    (0..3).map(|_| join_with_new_candidate(tv)).collect()
+}
+
+/// the other candidates approve the candidate's milestone
+fn approve_milestone(
+    tv: &mut TestValues,
+    candidates: &[(Keypair, Pubkey)],
+    candidate: usize,
+    index: u8,
+) {
+    let reviewed = candidates[candidate].0.pubkey();
+    for (validator, _) in candidates
+        .iter()
+        .filter(|(validator, _)| validator.pubkey() != reviewed)
+    {
+        validate_milestone(tv, validator, &reviewed, index, Vote::Approve).unwrap();
+    }
+}
+
+#[test]
+fn pass_min_milestones_to_win() {
+    let mut tv = setup();
+    let candidates = milestone_challenge(&mut tv);
+    let start = tv.challenge.start_time;
+    let (first, _) = &candidates[0];
+    let (second, _) = &candidates[1];
+
+    //each milestone is submitted once, within its window
+    set_clock(&mut tv.svm, start + 1);
+    expect_error(
+        submit_proof(&mut tv, first, "http://linkt/to/proof"),
+        AaasError::MilestoneChallenge,
+    );
+    expect_error(
+        submit_milestone(&mut tv, first, 1, "http://linkt/to/run/1"),
+        AaasError::MilestoneNotOpen,
+    );
+    submit_milestone(&mut tv, first, 0, "http://linkt/to/run/0").unwrap();
+    assert!(submit_milestone(&mut tv, first, 0, "http://linkt/to/run/0").is_err());
+    submit_milestone(&mut tv, second, 0, "http://linkt/to/run/0").unwrap();
+    approve_milestone(&mut tv, &candidates, 0, 0);
+    approve_milestone(&mut tv, &candidates, 1, 0);
+
+    set_clock(&mut tv.svm, start + 2 * DAY);
+    submit_milestone(&mut tv, first, 1, "http://linkt/to/run/1").unwrap();
+    approve_milestone(&mut tv, &candidates, 0, 1);
+
+    //a milestone is final once its validation period is over
+    expect_error(
+        finalize_milestone(&mut tv, &first.pubkey(), 0),
+        AaasError::ValidationPeriod,
+    );
+    set_clock(
+        &mut tv.svm,
+        start + 2 * DAY + tv.challenge.validation_period + 1,
+    );
+    finalize_milestone(&mut tv, &first.pubkey(), 0).unwrap();
+    finalize_milestone(&mut tv, &second.pubkey(), 0).unwrap();
+    expect_error(
+        finalize_milestone(&mut tv, &first.pubkey(), 0),
+        AaasError::MilestoneFinalized,
+    );
+    let milestone: MilestoneProof = get_account(&tv.svm, &milestone_proof(&tv, &first.pubkey(), 0));
+    assert!(milestone.finalized && milestone.passed);
+    assert_eq!(milestone.acceptance, 2);
+
+    //every submitted milestone is finalized before the challenge
+    set_clock(&mut tv.svm, tv.challenge.validation_end() + 1);
+    let accounts = candidate_accounts(&tv, &candidates);
+    let cranker = tv.admin.insecure_clone();
+    let ix = aaas_client::finalize_challenge(&cranker.pubkey(), &tv.challenge.key, &accounts);
+    expect_error(
+        send(&mut tv.svm, &[ix], &cranker, &[]),
+        AaasError::MilestonesNotFinalized,
+    );
+    finalize_milestone(&mut tv, &first.pubkey(), 1).unwrap();
+    finalize_challenge(&mut tv, &accounts);
+
+    let winner: CandidateAccount = get_account(&tv.svm, &accounts[0]);
+    assert_eq!(winner.milestones_passed, 2);
+    assert!(winner.winner);
+    let loser: CandidateAccount = get_account(&tv.svm, &accounts[1]);
+    assert_eq!(loser.milestones_passed, 1);
+    assert!(!loser.winner);
+}
+
+#[test]
+fn rejected_milestone_doesnt_pass() {
+    let mut tv = setup();
+    let candidates = milestone_challenge(&mut tv);
+    let start = tv.challenge.start_time;
+    let (first, _) = &candidates[0];
+    let reviewed = first.pubkey();
+
+    set_clock(&mut tv.svm, start + 1);
+    submit_milestone(&mut tv, first, 0, "http://linkt/to/run/0").unwrap();
+    //the candidate can't vote on its own milestone
+    assert!(validate_milestone(&mut tv, first, &reviewed, 0, Vote::Approve).is_err());
+    validate_milestone(&mut tv, &candidates[1].0, &reviewed, 0, Vote::Reject).unwrap();
+
+    //votes close with the validation period after the milestone end
+    set_clock(
+        &mut tv.svm,
+        start + 2 * DAY + tv.challenge.validation_period,
+    );
+    expect_error(
+        validate_milestone(&mut tv, &candidates[2].0, &reviewed, 0, Vote::Approve),
+        AaasError::ValidationPeriodEnded,
+    );
+    set_clock(
+        &mut tv.svm,
+        start + 2 * DAY + tv.challenge.validation_period + 1,
+    );
+    finalize_milestone(&mut tv, &reviewed, 0).unwrap();
+    let milestone: MilestoneProof = get_account(&tv.svm, &milestone_proof(&tv, &reviewed, 0));
+    assert!(milestone.finalized && !milestone.passed);
+    assert_eq!(milestone.rejections, 1);
+}
+
+#[test]
+fn cancelled_challenge_takes_no_milestones() {
+    let mut tv = setup();
+    let candidates = milestone_challenge(&mut tv);
+    let (first, _) = &candidates[0];
+    set_clock(&mut tv.svm, tv.challenge.start_time + 1);
+    submit_milestone(&mut tv, first, 0, "http://linkt/to/run/0").unwrap();
+    emergency_cancel(&mut tv).unwrap();
+
+    expect_error(
+        submit_milestone(&mut tv, &candidates[1].0, 0, "http://linkt/to/run/0"),
+        AaasError::ChallengeCancelled,
+    );
+    expect_error(
+        validate_milestone(&mut tv, &candidates[1].0, &first.pubkey(), 0, Vote::Approve),
+        AaasError::ChallengeCancelled,
+    );
+    set_clock(&mut tv.svm, tv.challenge.start_time + 3 * DAY + 1);
+    expect_error(
+        finalize_milestone(&mut tv, &first.pubkey(), 0),
+        AaasError::ChallengeCancelled,
+    );
+}
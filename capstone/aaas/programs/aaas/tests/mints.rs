@@ -0,0 +1,98 @@
+mod utils;
+
+use aaas::error::AaasError;
+use aaas::{CandidateAccount, Challenge};
+use anchor_lang::prelude::Pubkey;
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use utils::*;
+
+#[test]
+fn create_challenge_rejects_unlisted_mint() {
+    let mut tv = create_values();
+    utils::initialize(&mut tv).unwrap();
+    initialize_service(&mut tv).unwrap();
+
+    let admin = tv.admin.insecure_clone();
+    tv.mint = create_mint(&mut tv.svm, &admin);
+    let ix = create_challenge_ix(&tv, tv.challenge.start_time, tv.challenge.end_time);
+    let creator = tv.challenge.creator.insecure_clone();
+    expect_error(
+        send(&mut tv.svm, &[ix], &creator, &[]),
+        AaasError::MintNotAllowed,
+    );
+}
+
+#[test]
+fn join_rejects_other_mint() {
+    let mut tv = setup();
+
+    let admin = tv.admin.insecure_clone();
+    tv.mint = create_mint(&mut tv.svm, &admin);
+    let (candidate, ata) = new_candidate(&mut tv);
+    expect_error(
+        utils::join_challenge(&mut tv, &candidate, &ata),
+        AaasError::InvalidMint,
+    );
+}
+
+#[test]
+fn join_with_transfer_fee() {
+    //1% fee
+    let mut tv = setup_values(create_values_with_fee(Some((100, 1_000_000))));
+    let (candidate, ata) = new_candidate(&mut tv);
+    let balance = token_amount(&tv.svm, &ata);
+    utils::join_challenge(&mut tv, &candidate, &ata).unwrap();
+
+    //the candidate pays the fee, the vault gets at least the stake and it is what's accounted
+    let candidate_account: CandidateAccount =
+        get_account(&tv.svm, &tv.candidate_account(&candidate.pubkey()));
+    assert!(candidate_account.stake >= tv.challenge.stake_amnt);
+    assert_eq!(token_amount(&tv.svm, &tv.vault()), candidate_account.stake);
+    assert!(balance - token_amount(&tv.svm, &ata) > candidate_account.stake);
+    let challenge: Challenge = get_account(&tv.svm, &tv.challenge.key);
+    assert_eq!(challenge.total_stake, candidate_account.stake);
+}
+
+#[test]
+fn join_with_capped_transfer_fee() {
+    //50% fee capped to 1 token unit, the gross amount isn't a fixed ratio of the stake
+    let mut tv = setup_values(create_values_with_fee(Some((5000, 1))));
+    let (candidate, ata) = new_candidate(&mut tv);
+    let balance = token_amount(&tv.svm, &ata);
+    utils::join_challenge(&mut tv, &candidate, &ata).unwrap();
+
+    let stake = tv.challenge.stake_amnt;
+    assert_eq!(token_amount(&tv.svm, &tv.vault()), stake);
+    assert_eq!(token_amount(&tv.svm, &ata), balance - stake - 1);
+}
+
+#[test]
+fn refund_with_transfer_fee() {
+    let mut tv = setup_values(create_values_with_fee(Some((100, 1_000_000))));
+    let candidates: Vec<(Keypair, Pubkey)> =
+        (0..2).map(|_| join_with_new_candidate(&mut tv)).collect();
+
+    //nobody won, every stake is paid out from the vault
+    set_clock(&mut tv.svm, tv.challenge.validation_end() + 1);
+    let accounts = candidate_accounts(&tv, &candidates);
+    finalize_challenge(&mut tv, &accounts);
+    for (candidate, ata) in &candidates {
+        refund_stake(&mut tv, candidate, ata).unwrap();
+    }
+    assert_eq!(token_amount(&tv.svm, &tv.vault()), 0);
+    let challenge: Challenge = get_account(&tv.svm, &tv.challenge.key);
+    assert!(challenge.settled);
+}
+
+#[test]
+fn sponsor_overflowing_transfer_fee() {
+    let mut tv = setup_values(create_values_with_fee(Some((100, 1_000_000))));
+    let (sponsor, sponsor_ata) = new_candidate(&mut tv);
+
+    //the amount plus its fee doesn't fit a u64, it fails with an error instead of a panic
+    expect_error(
+        sponsor_challenge(&mut tv, &sponsor, &sponsor_ata, u64::MAX - 1),
+        AaasError::MathOverflow,
+    );
+}
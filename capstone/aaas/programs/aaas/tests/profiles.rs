@@ -0,0 +1,162 @@
+mod utils;
+
+use aaas::error::AaasError;
+use aaas::{AdminAction, Profile, Vote};
+use aaas_client::pda;
+use anchor_lang::prelude::Pubkey;
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use utils::*;
+
+fn profile(tv: &TestValues, owner: &Pubkey) -> Profile {
+    get_account(&tv.svm, &pda::profile(owner).0)
+}
+
+/// joins `count` new candidates with a profile
+fn join_with_profiles(tv: &mut TestValues, count: usize) -> Vec<(Keypair, Pubkey)> {
+    (0..count)
+        .map(|_| {
+            let (candidate, ata) = new_candidate(tv);
+            init_profile(tv, &candidate);
+            join_challenge(tv, &candidate, &ata).unwrap();
+            (candidate, ata)
+        })
+        .collect()
+}
+
+#[test]
+fn profiled_votes_are_tallied_before_payouts() {
+    let mut tv = setup();
+    new_challenge(&mut tv, |args| args.winning_threshold = 5000).unwrap();
+    let candidates = join_with_profiles(&mut tv, 3);
+    let (winner, winner_ata) = &candidates[0];
+    set_clock(&mut tv.svm, tv.challenge.start_time + 1);
+    submit_proof(&mut tv, winner, "http://linkt/to/proof").unwrap();
+    validate_proof(&mut tv, &candidates[1].0, &winner.pubkey(), Vote::Approve).unwrap();
+    validate_proof(&mut tv, &candidates[2].0, &winner.pubkey(), Vote::Reject).unwrap();
+    set_clock(&mut tv.svm, tv.challenge.validation_end() + 1);
+    let accounts = candidate_accounts(&tv, &candidates);
+    finalize_challenge(&mut tv, &accounts);
+
+    //no incentives, but the agreements of profiled validators must be recorded first
+    expect_error(
+        withdraw_reward(&mut tv, winner, winner_ata),
+        AaasError::VotesNotTallied,
+    );
+    for (validator, _) in &candidates[1..] {
+        tally_vote(&mut tv, &winner.pubkey(), &validator.pubkey()).unwrap();
+    }
+    withdraw_reward(&mut tv, winner, winner_ata).unwrap();
+
+    let won = profile(&tv, &winner.pubkey());
+    assert_eq!(won.challenges_joined, 1);
+    assert_eq!(won.challenges_won, 1);
+    assert_eq!(won.reputation, 1);
+    let agreed = profile(&tv, &candidates[1].0.pubkey());
+    assert_eq!(agreed.proofs_validated, 1);
+    assert_eq!(agreed.votes_agreed, 1);
+    assert_eq!(agreed.reputation, 1);
+    let disagreed = profile(&tv, &candidates[2].0.pubkey());
+    assert_eq!(disagreed.proofs_validated, 1);
+    assert_eq!(disagreed.votes_agreed, 0);
+    assert_eq!(disagreed.reputation, 0);
+}
+
+#[test]
+fn exit_takes_the_join_back() {
+    let mut tv = setup();
+    let candidates = join_with_profiles(&mut tv, 1);
+    let (candidate, ata) = &candidates[0];
+    assert_eq!(profile(&tv, &candidate.pubkey()).challenges_joined, 1);
+
+    //the history can't be skipped
+    let ix = aaas_client::exit_challenge(
+        &candidate.pubkey(),
+        &tv.service,
+        &tv.challenge.key,
+        &tv.mint,
+        ata,
+        &tv.token_program,
+        false,
+    );
+    expect_error(
+        send(&mut tv.svm, &[ix], candidate, &[]),
+        AaasError::ProfileRequired,
+    );
+    exit_challenge(&mut tv, candidate, ata).unwrap();
+    assert_eq!(profile(&tv, &candidate.pubkey()).challenges_joined, 0);
+}
+
+#[test]
+fn min_reputation_gates_the_service() {
+    let mut tv = setup();
+    let mut limits = tv.limits.clone();
+    limits.min_reputation = 1;
+    let action = AdminAction::UpdateServiceLimits {
+        id: tv.service_id,
+        limits,
+    };
+    let service_id = tv.service_id;
+    execute(&mut tv, action, |executor, proposal_id, proposer| {
+        aaas_client::update_service_limits(executor, proposal_id, proposer, &service_id)
+    })
+    .unwrap();
+
+    //a missing profile counts as no reputation
+    let (candidate, ata) = new_candidate(&mut tv);
+    expect_error(
+        join_challenge(&mut tv, &candidate, &ata),
+        AaasError::InsufficientReputation,
+    );
+    init_profile(&mut tv, &candidate);
+    expect_error(
+        join_challenge(&mut tv, &candidate, &ata),
+        AaasError::InsufficientReputation,
+    );
+}
+
+#[test]
+fn profile_created_after_joining_isnt_updated() {
+    let mut tv = setup();
+    let candidates = challenge_with_winner(&mut tv, 3);
+    let (winner, winner_ata) = &candidates[0];
+    let (loser, _) = &candidates[1];
+    for (candidate, _) in &candidates {
+        init_profile(&mut tv, candidate);
+    }
+
+    //the helpers now pass the profiles, the challenge is still left out of them
+    set_clock(&mut tv.svm, tv.challenge.validation_end() + 1);
+    let accounts = candidate_accounts(&tv, &candidates);
+    finalize_challenge(&mut tv, &accounts);
+    tally_vote(&mut tv, &winner.pubkey(), &loser.pubkey()).unwrap();
+    withdraw_reward(&mut tv, winner, winner_ata).unwrap();
+    let closer = tv.admin.insecure_clone();
+    let ix = aaas_client::close_candidate(
+        &closer.pubkey(),
+        &tv.service,
+        &tv.challenge.key,
+        &loser.pubkey(),
+        true,
+    );
+    send(&mut tv.svm, &[ix], &closer, &[]).unwrap();
+
+    for (candidate, _) in &candidates[..2] {
+        let history = profile(&tv, &candidate.pubkey());
+        assert_eq!(history.challenges_won, 0);
+        assert_eq!(history.challenges_lost, 0);
+        assert_eq!(history.votes_agreed, 0);
+        assert_eq!(history.reputation, 0);
+    }
+}
+
+#[test]
+fn exit_with_a_profile_created_after_joining() {
+    let mut tv = setup();
+    let (candidate, ata) = join_with_new_candidate(&mut tv);
+    init_profile(&mut tv, &candidate);
+
+    //the join wasn't counted, so there is nothing to take back
+    exit_challenge(&mut tv, &candidate, &ata).unwrap();
+    assert_eq!(profile(&tv, &candidate.pubkey()).challenges_joined, 0);
+}
@@ -0,0 +1,119 @@
+mod utils;
+
+use aaas::error::AaasError;
+use aaas::{AdminAction, Service, PROPOSAL_LIFETIME};
+use aaas_client::pda;
+use solana_signer::Signer;
+use utils::*;
+
+#[test]
+fn update_service_fee_reads_the_proposal() {
+    let mut tv = setup();
+    let action = AdminAction::UpdateServiceFee {
+        id: tv.service_id,
+        fee: 50,
+        forfeit_fee: 200,
+    };
+    let approvals = tv.threshold as usize;
+    let proposal_id = propose(&mut tv, action, approvals);
+
+    let executor = tv.signers[2].insecure_clone();
+    let ix = aaas_client::update_service_fee(
+        &executor.pubkey(),
+        &proposal_id,
+        &tv.signers[0].pubkey(),
+        &tv.service_id,
+    );
+    send(&mut tv.svm, &[ix], &executor, &[]).unwrap();
+
+    let service: Service = get_account(&tv.svm, &tv.service);
+    assert_eq!(service.fee, 50);
+    assert_eq!(service.forfeit_fee, 200);
+    //executed proposals are closed
+    assert!(tv.svm.get_account(&pda::proposal(&proposal_id).0).is_none());
+}
+
+#[test]
+fn expired_proposal_is_rejected() {
+    let mut tv = create_values();
+    utils::initialize(&mut tv).unwrap();
+    let action = create_service_action(&tv);
+    let proposal_id = propose(&mut tv, action, 1);
+
+    set_clock(&mut tv.svm, PROPOSAL_LIFETIME + 1);
+
+    let signer = tv.signers[1].insecure_clone();
+    let approve = aaas_client::approve_proposal(&signer.pubkey(), &proposal_id);
+    expect_error(
+        send(&mut tv.svm, &[approve], &signer, &[]),
+        AaasError::ProposalExpired,
+    );
+
+    let ix = initialize_service_ix(&tv, &proposal_id);
+    let admin = tv.admin.insecure_clone();
+    expect_error(
+        send(&mut tv.svm, &[ix], &admin, &[]),
+        AaasError::ProposalExpired,
+    );
+}
+
+#[test]
+fn approved_proposal_expires() {
+    let mut tv = create_values();
+    utils::initialize(&mut tv).unwrap();
+    let action = create_service_action(&tv);
+    let approvals = tv.threshold as usize;
+    let proposal_id = propose(&mut tv, action, approvals);
+
+    set_clock(&mut tv.svm, PROPOSAL_LIFETIME + 1);
+
+    let ix = initialize_service_ix(&tv, &proposal_id);
+    let admin = tv.admin.insecure_clone();
+    expect_error(
+        send(&mut tv.svm, &[ix], &admin, &[]),
+        AaasError::ProposalExpired,
+    );
+}
+
+#[test]
+fn cancel_proposal() {
+    let mut tv = create_values();
+    utils::initialize(&mut tv).unwrap();
+    let action = create_service_action(&tv);
+    let proposal_id = propose(&mut tv, action, 1);
+    let proposer = tv.signers[0].insecure_clone();
+    let proposal = pda::proposal(&proposal_id).0;
+
+    //other signers have to wait for the expiry
+    let signer = tv.signers[1].insecure_clone();
+    let ix = aaas_client::cancel_proposal(&signer.pubkey(), &proposal_id, &proposer.pubkey());
+    expect_error(
+        send(&mut tv.svm, &[ix], &signer, &[]),
+        AaasError::ProposalNotExpired,
+    );
+
+    //the proposer gets the rent back
+    let rent = tv.svm.get_account(&proposal).unwrap().lamports;
+    let before = tv.svm.get_account(&proposer.pubkey()).unwrap().lamports;
+    let ix = aaas_client::cancel_proposal(&proposer.pubkey(), &proposal_id, &proposer.pubkey());
+    send(&mut tv.svm, &[ix], &signer, &[&proposer]).unwrap();
+    assert!(tv.svm.get_account(&proposal).is_none());
+    let after = tv.svm.get_account(&proposer.pubkey()).unwrap().lamports;
+    assert_eq!(after, before + rent);
+}
+
+#[test]
+fn anyone_cancels_expired_proposal() {
+    let mut tv = create_values();
+    utils::initialize(&mut tv).unwrap();
+    let action = create_service_action(&tv);
+    let proposal_id = propose(&mut tv, action, 1);
+    let proposer = tv.signers[0].pubkey();
+
+    set_clock(&mut tv.svm, PROPOSAL_LIFETIME + 1);
+
+    let outsider = tv.challenge.creator.insecure_clone();
+    let ix = aaas_client::cancel_proposal(&outsider.pubkey(), &proposal_id, &proposer);
+    send(&mut tv.svm, &[ix], &outsider, &[]).unwrap();
+    assert!(tv.svm.get_account(&pda::proposal(&proposal_id).0).is_none());
+}
@@ -0,0 +1,77 @@
+mod utils;
+
+use aaas::error::AaasError;
+use aaas::{CandidateAccount, Challenge};
+use anchor_lang::prelude::Pubkey;
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use utils::*;
+
+#[test]
+fn refund_when_nobody_wins() {
+    let mut tv = setup();
+    let candidates: Vec<(Keypair, Pubkey)> =
+        (0..2).map(|_| join_with_new_candidate(&mut tv)).collect();
+    let (candidate, ata) = &candidates[0];
+    let balance = token_amount(&tv.svm, ata);
+    let treasury = token_amount(&tv.svm, &tv.treasury());
+
+    //nobody submitted a proof, the stakes are refunded once the challenge is finalized
+    set_clock(&mut tv.svm, tv.challenge.validation_end());
+    expect_error(
+        refund_stake(&mut tv, candidate, ata),
+        AaasError::ValidationPeriod,
+    );
+    set_clock(&mut tv.svm, tv.challenge.validation_end() + 1);
+    expect_error(
+        refund_stake(&mut tv, candidate, ata),
+        AaasError::ChallengeNotFinalized,
+    );
+
+    let accounts = candidate_accounts(&tv, &candidates);
+    finalize_challenge(&mut tv, &accounts);
+    refund_stake(&mut tv, candidate, ata).unwrap();
+
+    //the forfeit fee goes to the treasury
+    let forfeit = tv.challenge.stake_amnt * tv.forfeit_fee as u64 / 10000;
+    assert_eq!(
+        token_amount(&tv.svm, ata),
+        balance + tv.challenge.stake_amnt - forfeit
+    );
+    assert_eq!(token_amount(&tv.svm, &tv.treasury()), treasury + forfeit);
+    let candidate_account: CandidateAccount =
+        get_account(&tv.svm, &tv.candidate_account(&candidate.pubkey()));
+    assert!(candidate_account.refunded);
+
+    expect_error(
+        refund_stake(&mut tv, candidate, ata),
+        AaasError::AlreadyRefunded,
+    );
+
+    //settled once everyone is refunded
+    let challenge: Challenge = get_account(&tv.svm, &tv.challenge.key);
+    assert!(!challenge.settled);
+    let (candidate, ata) = &candidates[1];
+    refund_stake(&mut tv, candidate, ata).unwrap();
+    let challenge: Challenge = get_account(&tv.svm, &tv.challenge.key);
+    assert!(challenge.settled);
+    assert_eq!(token_amount(&tv.svm, &tv.vault()), 0);
+}
+
+#[test]
+fn no_refund_when_someone_wins() {
+    let mut tv = setup();
+    let candidates = challenge_with_winner(&mut tv, 7);
+
+    set_clock(&mut tv.svm, tv.challenge.validation_end() + 1);
+    let accounts = candidate_accounts(&tv, &candidates);
+    finalize_challenge(&mut tv, &accounts);
+
+    //losers stake goes to the winner
+    for (candidate, ata) in &candidates {
+        expect_error(
+            refund_stake(&mut tv, candidate, ata),
+            AaasError::ChallengeHasWinners,
+        );
+    }
+}
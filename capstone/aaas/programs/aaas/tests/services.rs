@@ -0,0 +1,93 @@
+mod utils;
+
+use aaas::error::AaasError;
+use aaas::{AdminAction, Service};
+use litesvm::types::TransactionResult;
+use solana_signer::Signer;
+use utils::*;
+
+fn pause_service(tv: &mut TestValues, paused: bool) -> TransactionResult {
+    let id = tv.service_id;
+    if paused {
+        execute(
+            tv,
+            AdminAction::PauseService { id },
+            |executor, proposal_id, proposer| {
+                aaas_client::pause_service(executor, proposal_id, proposer, &id)
+            },
+        )
+    } else {
+        execute(
+            tv,
+            AdminAction::ResumeService { id },
+            |executor, proposal_id, proposer| {
+                aaas_client::resume_service(executor, proposal_id, proposer, &id)
+            },
+        )
+    }
+}
+
+fn close_service(tv: &mut TestValues) -> TransactionResult {
+    let id = tv.service_id;
+    execute(
+        tv,
+        AdminAction::CloseService { id },
+        |executor, proposal_id, proposer| {
+            aaas_client::close_service(executor, proposal_id, proposer, &id)
+        },
+    )
+}
+
+#[test]
+fn pause_blocks_creating_and_joining() {
+    let mut tv = setup();
+    let (candidate, ata) = new_candidate(&mut tv);
+
+    pause_service(&mut tv, true).unwrap();
+    expect_error(
+        pause_service(&mut tv, true),
+        AaasError::ServiceStateUnchanged,
+    );
+    expect_error(
+        join_challenge(&mut tv, &candidate, &ata),
+        AaasError::ServicePaused,
+    );
+
+    tv.challenge.id = anchor_lang::prelude::Pubkey::new_unique();
+    tv.challenge.key = aaas_client::pda::challenge(&tv.service, &tv.challenge.id).0;
+    let ix = create_challenge_ix(&tv, tv.challenge.start_time, tv.challenge.end_time);
+    let creator = tv.challenge.creator.insecure_clone();
+    expect_error(
+        send(&mut tv.svm, std::slice::from_ref(&ix), &creator, &[]),
+        AaasError::ServicePaused,
+    );
+
+    pause_service(&mut tv, false).unwrap();
+    send(&mut tv.svm, &[ix], &creator, &[]).unwrap();
+    join_challenge(&mut tv, &candidate, &ata).unwrap();
+}
+
+#[test]
+fn close_service_waits_for_its_challenges() {
+    let mut tv = setup();
+    let service: Service = get_account(&tv.svm, &tv.service);
+    assert_eq!(service.challenge_count, 1);
+
+    expect_error(close_service(&mut tv), AaasError::ServiceHasChallenges);
+
+    //nobody joined, so the challenge closes once its validation period is over
+    let validation_end = tv.challenge.validation_end();
+    set_clock(&mut tv.svm, validation_end + 1);
+    close_challenge(&mut tv).unwrap();
+    let service: Service = get_account(&tv.svm, &tv.service);
+    assert_eq!(service.challenge_count, 0);
+
+    let proposer = tv.signers[0].pubkey();
+    let rent = tv.svm.get_account(&tv.service).unwrap().lamports;
+    let before = tv.svm.get_account(&proposer).unwrap().lamports;
+    close_service(&mut tv).unwrap();
+    assert!(tv.svm.get_account(&tv.service).is_none());
+    //the proposer got the service rent, and paid the fees of proposing and executing
+    let after = tv.svm.get_account(&proposer).unwrap().lamports;
+    assert_eq!(after + 2 * 5_000, before + rent);
+}
@@ -0,0 +1,205 @@
+mod utils;
+
+use aaas::error::AaasError;
+use aaas::{AaasConfig, AdminAction};
+use aaas_client::pda;
+use anchor_lang::prelude::Pubkey;
+use litesvm::types::TransactionResult;
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use utils::*;
+
+fn rotate_signers(tv: &mut TestValues, signers: Vec<Pubkey>, threshold: u8) -> TransactionResult {
+    execute(
+        tv,
+        AdminAction::RotateSigners { signers, threshold },
+        aaas_client::rotate_signers,
+    )
+}
+
+fn add_signer(tv: &mut TestValues, signer: Pubkey) -> TransactionResult {
+    execute(
+        tv,
+        AdminAction::AddSigner { signer },
+        aaas_client::add_signer,
+    )
+}
+
+fn remove_signer(tv: &mut TestValues, signer: Pubkey) -> TransactionResult {
+    execute(
+        tv,
+        AdminAction::RemoveSigner { signer },
+        aaas_client::remove_signer,
+    )
+}
+
+fn set_threshold(tv: &mut TestValues, threshold: u8) -> TransactionResult {
+    execute(
+        tv,
+        AdminAction::SetThreshold { threshold },
+        aaas_client::set_threshold,
+    )
+}
+
+fn config(tv: &TestValues) -> AaasConfig {
+    get_account(&tv.svm, &pda::config().0)
+}
+
+#[test]
+fn rotate_signers_replaces_the_set() {
+    let mut tv = create_values();
+    utils::initialize(&mut tv).unwrap();
+    let old_signer = tv.signers[1].insecure_clone();
+
+    let new_signers: Vec<Keypair> = (0..3).map(|_| Keypair::new()).collect();
+    for signer in &new_signers {
+        tv.svm.airdrop(&signer.pubkey(), LAMPORTS_PER_SOL).unwrap();
+    }
+    let keys: Vec<Pubkey> = new_signers.iter().map(|signer| signer.pubkey()).collect();
+    rotate_signers(&mut tv, keys.clone(), 3).unwrap();
+
+    let config = config(&tv);
+    assert_eq!(config.signers, keys);
+    assert_eq!(config.threshold, 3);
+
+    //rotated out signers can't propose anymore
+    let ix = aaas_client::create_proposal(
+        &old_signer.pubkey(),
+        Pubkey::new_unique(),
+        AdminAction::SetThreshold { threshold: 1 },
+    );
+    expect_error(
+        send(&mut tv.svm, &[ix], &old_signer, &[]),
+        AaasError::NotASigner,
+    );
+
+    //the new set governs
+    tv.signers = new_signers;
+    tv.threshold = 3;
+    set_threshold(&mut tv, 2).unwrap();
+    assert_eq!(self::config(&tv).threshold, 2);
+}
+
+#[test]
+fn rotate_signers_rejects_invalid_sets() {
+    let mut tv = create_values();
+    utils::initialize(&mut tv).unwrap();
+    let signer = Pubkey::new_unique();
+
+    expect_error(
+        rotate_signers(&mut tv, vec![signer, signer], 1),
+        AaasError::DuplicateSigner,
+    );
+    expect_error(
+        rotate_signers(&mut tv, vec![signer], 2),
+        AaasError::InvalidThreshold,
+    );
+    expect_error(
+        rotate_signers(&mut tv, vec![], 0),
+        AaasError::InvalidSigners,
+    );
+}
+
+#[test]
+fn add_and_remove_signer() {
+    let mut tv = create_values();
+    utils::initialize(&mut tv).unwrap();
+    let last = tv.signers[4].pubkey();
+
+    //the config is full
+    expect_error(
+        add_signer(&mut tv, Pubkey::new_unique()),
+        AaasError::InvalidSigners,
+    );
+    expect_error(
+        remove_signer(&mut tv, Pubkey::new_unique()),
+        AaasError::SignerNotFound,
+    );
+
+    remove_signer(&mut tv, last).unwrap();
+    assert_eq!(config(&tv).signers.len(), 4);
+    assert!(!config(&tv).signers.contains(&last));
+
+    let signer = tv.signers[1].pubkey();
+    expect_error(add_signer(&mut tv, signer), AaasError::DuplicateSigner);
+    add_signer(&mut tv, last).unwrap();
+    assert_eq!(config(&tv).signers.last(), Some(&last));
+}
+
+#[test]
+fn set_threshold_within_signers() {
+    let mut tv = create_values();
+    utils::initialize(&mut tv).unwrap();
+
+    expect_error(set_threshold(&mut tv, 0), AaasError::InvalidThreshold);
+    expect_error(set_threshold(&mut tv, 6), AaasError::InvalidThreshold);
+
+    set_threshold(&mut tv, 5).unwrap();
+    assert_eq!(config(&tv).threshold, 5);
+
+    //removing a signer can't leave less signers than the threshold
+    tv.threshold = 5;
+    let last = tv.signers[4].pubkey();
+    expect_error(remove_signer(&mut tv, last), AaasError::InvalidThreshold);
+}
+
+#[test]
+fn removed_signer_approval_doesnt_count() {
+    let mut tv = create_values();
+    utils::initialize(&mut tv).unwrap();
+
+    //approved by signers 0 and 1
+    let action = create_service_action(&tv);
+    let approvals = tv.threshold as usize;
+    let proposal_id = propose(&mut tv, action, approvals);
+
+    let removed = tv.signers[1].pubkey();
+    remove_signer(&mut tv, removed).unwrap();
+
+    let ix = initialize_service_ix(&tv, &proposal_id);
+    let admin = tv.admin.insecure_clone();
+    expect_error(
+        send(&mut tv.svm, &[ix], &admin, &[]),
+        AaasError::MutliSignerThreshold,
+    );
+}
+
+#[test]
+fn transfer_admin_moves_the_treasury() {
+    let mut tv = create_values();
+    utils::initialize(&mut tv).unwrap();
+    let new_admin = Pubkey::new_unique();
+    let mint = tv.mint;
+    let transfer = |admin: Pubkey| {
+        move |executor: &Pubkey, proposal_id: &Pubkey, proposer: &Pubkey| {
+            aaas_client::transfer_admin(
+                executor,
+                proposal_id,
+                proposer,
+                &admin,
+                &mint,
+                &anchor_spl::token::ID,
+            )
+        }
+    };
+
+    //the new admin account has to be the proposed one
+    expect_error(
+        execute(
+            &mut tv,
+            AdminAction::TransferAdmin { admin: new_admin },
+            transfer(Pubkey::new_unique()),
+        ),
+        AaasError::ProposalActionMismatch,
+    );
+
+    execute(
+        &mut tv,
+        AdminAction::TransferAdmin { admin: new_admin },
+        transfer(new_admin),
+    )
+    .unwrap();
+    assert_eq!(config(&tv).admin, new_admin);
+    let treasury = pda::treasury(&new_admin, &mint, &anchor_spl::token::ID);
+    assert_eq!(token_amount(&tv.svm, &treasury), 0);
+}
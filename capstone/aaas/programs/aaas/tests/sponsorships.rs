@@ -0,0 +1,126 @@
+mod utils;
+
+use aaas::error::AaasError;
+use aaas::{Challenge, Sponsorship};
+use aaas_client::pda;
+use anchor_lang::prelude::Pubkey;
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use utils::*;
+
+#[test]
+fn bonus_goes_to_the_winners() {
+    let mut tv = setup();
+    let (sponsor, sponsor_ata) = new_candidate(&mut tv);
+    let balance = token_amount(&tv.svm, &sponsor_ata);
+    expect_error(
+        sponsor_challenge(&mut tv, &sponsor, &sponsor_ata, 0),
+        AaasError::InvalidSponsorAmount,
+    );
+    //later deposits add to the sponsorship
+    sponsor_challenge(&mut tv, &sponsor, &sponsor_ata, 300).unwrap();
+    sponsor_challenge(&mut tv, &sponsor, &sponsor_ata, 300).unwrap();
+    let sponsorship_key = pda::sponsorship(&tv.challenge.key, &sponsor.pubkey()).0;
+    let sponsorship: Sponsorship = get_account(&tv.svm, &sponsorship_key);
+    assert_eq!(sponsorship.amount, 600);
+    assert_eq!(token_amount(&tv.svm, &sponsor_ata), balance - 600);
+    let challenge: Challenge = get_account(&tv.svm, &tv.challenge.key);
+    assert_eq!(challenge.sponsor_count, 1);
+    assert_eq!(challenge.bonus_pool, 600);
+
+    let candidates = challenge_with_winner(&mut tv, 2);
+    set_clock(&mut tv.svm, tv.challenge.validation_end() + 1);
+    let accounts = candidate_accounts(&tv, &candidates);
+    finalize_challenge(&mut tv, &accounts);
+    expect_error(
+        refund_sponsorship(&mut tv, &sponsor, &sponsor_ata),
+        AaasError::ChallengeHasWinners,
+    );
+
+    //the bonus is shared in full by the winners, along with the losers stake
+    let (winner, winner_ata) = &candidates[0];
+    let balance = token_amount(&tv.svm, winner_ata);
+    withdraw_reward(&mut tv, winner, winner_ata).unwrap();
+    let reward = tv.challenge.stake_amnt + 600;
+    let tax = reward * tv.fee as u64 / 10000;
+    assert_eq!(
+        token_amount(&tv.svm, winner_ata),
+        balance + reward - tax + tv.challenge.stake_amnt
+    );
+
+    //the sponsor gets the rent back once settled
+    let rent = tv.svm.get_account(&sponsorship_key).unwrap().lamports;
+    let lamports = tv.svm.get_account(&sponsor.pubkey()).unwrap().lamports;
+    close_sponsorship(&mut tv, &sponsor.pubkey()).unwrap();
+    assert_eq!(
+        tv.svm.get_account(&sponsor.pubkey()).unwrap().lamports,
+        lamports + rent
+    );
+    let challenge: Challenge = get_account(&tv.svm, &tv.challenge.key);
+    assert_eq!(challenge.sponsor_count, 0);
+}
+
+#[test]
+fn bonus_refunded_when_nobody_wins() {
+    let mut tv = setup();
+    let (sponsor, sponsor_ata) = new_candidate(&mut tv);
+    sponsor_challenge(&mut tv, &sponsor, &sponsor_ata, 400).unwrap();
+    let balance = token_amount(&tv.svm, &sponsor_ata);
+    let candidates: Vec<(Keypair, Pubkey)> =
+        (0..2).map(|_| join_with_new_candidate(&mut tv)).collect();
+
+    set_clock(&mut tv.svm, tv.challenge.validation_end() + 1);
+    expect_error(
+        refund_sponsorship(&mut tv, &sponsor, &sponsor_ata),
+        AaasError::ChallengeNotFinalized,
+    );
+    let accounts = candidate_accounts(&tv, &candidates);
+    finalize_challenge(&mut tv, &accounts);
+
+    //no fee is taken from the bonus
+    refund_sponsorship(&mut tv, &sponsor, &sponsor_ata).unwrap();
+    assert_eq!(token_amount(&tv.svm, &sponsor_ata), balance + 400);
+    expect_error(
+        refund_sponsorship(&mut tv, &sponsor, &sponsor_ata),
+        AaasError::AlreadyRefunded,
+    );
+
+    //settled once the stakes are refunded too
+    expect_error(
+        close_sponsorship(&mut tv, &sponsor.pubkey()),
+        AaasError::ChallengeNotSettled,
+    );
+    for (candidate, ata) in &candidates {
+        refund_stake(&mut tv, candidate, ata).unwrap();
+    }
+    close_sponsorship(&mut tv, &sponsor.pubkey()).unwrap();
+}
+
+#[test]
+fn sponsoring_closes_with_the_challenge() {
+    let mut tv = setup();
+    let (sponsor, sponsor_ata) = new_candidate(&mut tv);
+    set_clock(&mut tv.svm, tv.challenge.end_time);
+    expect_error(
+        sponsor_challenge(&mut tv, &sponsor, &sponsor_ata, 400),
+        AaasError::ChallengeEnded,
+    );
+}
+
+#[test]
+fn cancelled_challenge_refunds_the_bonus() {
+    let mut tv = setup();
+    let (sponsor, sponsor_ata) = new_candidate(&mut tv);
+    sponsor_challenge(&mut tv, &sponsor, &sponsor_ata, 400).unwrap();
+    let balance = token_amount(&tv.svm, &sponsor_ata);
+    emergency_cancel(&mut tv).unwrap();
+
+    expect_error(
+        sponsor_challenge(&mut tv, &sponsor, &sponsor_ata, 400),
+        AaasError::ChallengeCancelled,
+    );
+    refund_sponsorship(&mut tv, &sponsor, &sponsor_ata).unwrap();
+    assert_eq!(token_amount(&tv.svm, &sponsor_ata), balance + 400);
+    let challenge: Challenge = get_account(&tv.svm, &tv.challenge.key);
+    assert!(challenge.settled);
+}
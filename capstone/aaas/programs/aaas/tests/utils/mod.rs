@@ -0,0 +1,972 @@
+//shared by every test file, each one uses a part of it
+#![allow(dead_code)]
+
+use aaas::error::AaasError;
+use aaas::{
+    instruction, AdminAction, CandidateAccount, ServiceLimits, ValidatorIncentives, Vote,
+    VoteWeighting,
+};
+use aaas_client::pda;
+#[cfg(feature = "event-cpi")]
+use anchor_lang::event::EVENT_IX_TAG_LE;
+use anchor_lang::prelude::{Clock, Pubkey};
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::instruction::{Instruction, InstructionError};
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::solana_program::system_instruction;
+use anchor_lang::{AccountDeserialize, AnchorDeserialize, Event};
+use anchor_spl::associated_token::{
+    get_associated_token_address_with_program_id, spl_associated_token_account,
+};
+use anchor_spl::token::spl_token;
+use anchor_spl::token_2022::spl_token_2022;
+use anchor_spl::token_2022::spl_token_2022::extension::ExtensionType;
+use anchor_spl::token_interface::TokenAccount;
+#[cfg(not(feature = "event-cpi"))]
+use base64::{engine::general_purpose::STANDARD, Engine};
+use litesvm::types::{TransactionMetadata, TransactionResult};
+use litesvm::LiteSVM;
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use solana_transaction::Transaction;
+use solana_transaction_error::TransactionError;
+use std::fs;
+use std::path::Path;
+use std::time::SystemTime;
+
+///built by `anchor build`, the tests run against the deployable program
+const PROGRAM_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../target/deploy/aaas.so");
+///the program has to be built again after any change to its sources
+const PROGRAM_SRC: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src");
+
+pub const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+pub const HOUR: u64 = 60 * 60;
+pub const DAY: u64 = 24 * HOUR;
+
+pub struct ChallengeValues {
+    pub id: Pubkey,
+    pub key: Pubkey,
+    pub creator: Keypair,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub stake_amnt: u64,
+    pub validation_period: u64,
+    pub winning_threshold: u16,
+    pub quorum: u16,
+}
+
+impl ChallengeValues {
+    pub fn validation_end(&self) -> u64 {
+        self.end_time + self.validation_period
+    }
+}
+
+pub struct TestValues {
+    pub svm: LiteSVM,
+    ///first config signer, mint authority and fee payer of the setup
+    pub admin: Keypair,
+    pub signers: Vec<Keypair>,
+    pub threshold: u8,
+    pub mint: Pubkey,
+    ///spl-token, or token-2022 for the transfer fee mint
+    pub token_program: Pubkey,
+    pub service_id: Pubkey,
+    pub service: Pubkey,
+    pub fee: u16,
+    pub forfeit_fee: u16,
+    pub limits: ServiceLimits,
+    pub challenge: ChallengeValues,
+    ///wallets with a profile, the helpers pass it for them
+    pub profiles: Vec<Pubkey>,
+}
+
+impl TestValues {
+    pub fn treasury(&self) -> Pubkey {
+        pda::treasury(&self.admin.pubkey(), &self.mint, &self.token_program)
+    }
+
+    pub fn vault(&self) -> Pubkey {
+        pda::vault(&self.challenge.key, &self.mint, &self.token_program)
+    }
+
+    pub fn candidate_account(&self, candidate: &Pubkey) -> Pubkey {
+        pda::candidate(&self.service, &self.challenge.key, candidate).0
+    }
+}
+
+///latest modification of the files under `dir`
+fn last_modified(dir: &Path) -> SystemTime {
+    fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                last_modified(&path)
+            } else {
+                fs::metadata(&path).unwrap().modified().unwrap()
+            }
+        })
+        .max()
+        .unwrap_or(SystemTime::UNIX_EPOCH)
+}
+
+pub fn new_svm() -> LiteSVM {
+    //a stale program would run the tests against old code
+    let built = fs::metadata(PROGRAM_PATH)
+        .and_then(|program| program.modified())
+        .unwrap_or_else(|_| {
+            panic!("{PROGRAM_PATH} not found, run `anchor build` before the rust tests")
+        });
+    assert!(
+        built >= last_modified(Path::new(PROGRAM_SRC)),
+        "{PROGRAM_PATH} is older than the program sources, run `anchor build` before the rust tests"
+    );
+
+    let mut svm = LiteSVM::new();
+    svm.add_program_from_file(aaas::ID, PROGRAM_PATH)
+        .expect("run `anchor build` before the rust tests");
+    svm
+}
+
+/// funds the signers and creates the usdc mint, the program accounts aren't created yet
+pub fn create_values() -> TestValues {
+    create_values_with_fee(None)
+}
+
+/// same as create_values, with a token-2022 mint charging `(basis points, maximum fee)` when given
+pub fn create_values_with_fee(transfer_fee: Option<(u16, u64)>) -> TestValues {
+    let mut svm = new_svm();
+    set_clock(&mut svm, 0);
+
+    let signers: Vec<Keypair> = (0..5).map(|_| Keypair::new()).collect();
+    for signer in &signers {
+        svm.airdrop(&signer.pubkey(), 100 * LAMPORTS_PER_SOL)
+            .unwrap();
+    }
+    let admin = signers[0].insecure_clone();
+    let (mint, token_program) = match transfer_fee {
+        Some((basis_points, maximum_fee)) => (
+            create_fee_mint(&mut svm, &admin, basis_points, maximum_fee),
+            spl_token_2022::ID,
+        ),
+        None => (create_mint(&mut svm, &admin), spl_token::ID),
+    };
+
+    let service_id = Pubkey::new_unique();
+    let creator = Keypair::new();
+    svm.airdrop(&creator.pubkey(), 100 * LAMPORTS_PER_SOL)
+        .unwrap();
+    let service = pda::service(&service_id).0;
+    let challenge_id = Pubkey::new_unique();
+
+    TestValues {
+        svm,
+        admin,
+        signers,
+        threshold: 2,
+        mint,
+        token_program,
+        service_id,
+        service,
+        fee: 30,          //in basis point
+        forfeit_fee: 100, //in basis point
+        limits: ServiceLimits {
+            min_duration: HOUR,
+            max_duration: 30 * DAY,
+            min_validation_period: HOUR,
+            max_validation_period: 3 * DAY,
+            min_reputation: 0, //open to everyone
+        },
+        challenge: ChallengeValues {
+            id: challenge_id,
+            key: pda::challenge(&service, &challenge_id).0,
+            creator,
+            start_time: DAY,
+            end_time: 7 * DAY,
+            stake_amnt: 500,
+            validation_period: DAY,
+            winning_threshold: 8500,
+            quorum: 8500,
+        },
+        profiles: vec![],
+    }
+}
+
+/// config and service are initialized, and the challenge is created
+pub fn setup() -> TestValues {
+    setup_values(create_values())
+}
+
+pub fn setup_values(mut tv: TestValues) -> TestValues {
+    initialize(&mut tv).unwrap();
+    initialize_service(&mut tv).unwrap();
+    let ix = create_challenge_ix(&tv, tv.challenge.start_time, tv.challenge.end_time);
+    let creator = tv.challenge.creator.insecure_clone();
+    send(&mut tv.svm, &[ix], &creator, &[]).unwrap();
+    tv
+}
+
+///sends the instructions in a fresh transaction, so identical transactions aren't deduplicated
+pub fn send(
+    svm: &mut LiteSVM,
+    ixs: &[Instruction],
+    payer: &Keypair,
+    signers: &[&Keypair],
+) -> TransactionResult {
+    svm.expire_blockhash();
+    let mut all_signers = vec![payer];
+    all_signers.extend_from_slice(signers);
+    let tx = Transaction::new_signed_with_payer(
+        ixs,
+        Some(&payer.pubkey()),
+        &all_signers,
+        svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx)
+}
+
+/// asserts the transaction failed with the given program error
+pub fn expect_error(res: TransactionResult, expected: AaasError) {
+    let failed = match res {
+        Ok(_) => panic!("expected {} but the transaction succeeded", expected.name()),
+        Err(failed) => failed,
+    };
+    match failed.err {
+        TransactionError::InstructionError(_, InstructionError::Custom(code)) => assert_eq!(
+            code,
+            u32::from(expected),
+            "expected {} but failed with {:?}",
+            expected.name(),
+            aaas_client::from_code(code).map(|err| err.name())
+        ),
+        err => panic!("expected {} but failed with {:?}", expected.name(), err),
+    }
+}
+
+///events emitted by the transaction, logged as program data or with `event-cpi` in self cpis
+pub fn events<T: Event + AnchorDeserialize>(meta: &TransactionMetadata) -> Vec<T> {
+    #[cfg(feature = "event-cpi")]
+    let emitted: Vec<Vec<u8>> = meta
+        .inner_instructions
+        .iter()
+        .flatten()
+        .filter_map(|inner| {
+            let data = inner.instruction.data.strip_prefix(EVENT_IX_TAG_LE)?;
+            Some(data.to_vec())
+        })
+        .collect();
+    #[cfg(not(feature = "event-cpi"))]
+    let emitted: Vec<Vec<u8>> = meta
+        .logs
+        .iter()
+        .filter_map(|log| STANDARD.decode(log.strip_prefix("Program data: ")?).ok())
+        .collect();
+    emitted
+        .iter()
+        .filter_map(|data| aaas_client::decode_event(data))
+        .collect()
+}
+
+///the single event of the given type emitted by the transaction
+pub fn event<T: Event + AnchorDeserialize>(res: TransactionResult) -> T {
+    let mut events = events(&res.unwrap());
+    assert_eq!(events.len(), 1, "expected a single event");
+    events.remove(0)
+}
+
+pub fn set_clock(svm: &mut LiteSVM, unix_timestamp: u64) {
+    let mut clock = svm.get_sysvar::<Clock>();
+    clock.unix_timestamp = unix_timestamp as i64;
+    svm.set_sysvar::<Clock>(&clock);
+}
+
+pub fn get_account<T: AccountDeserialize>(svm: &LiteSVM, key: &Pubkey) -> T {
+    let account = svm.get_account(key).expect("account doesn't exist");
+    aaas_client::decode(&account.data).unwrap()
+}
+
+pub fn token_amount(svm: &LiteSVM, key: &Pubkey) -> u64 {
+    get_account::<TokenAccount>(svm, key).amount
+}
+
+/// creates a 6 decimals spl-token mint
+pub fn create_mint(svm: &mut LiteSVM, authority: &Keypair) -> Pubkey {
+    let mint = Keypair::new();
+    let ixs = [
+        system_instruction::create_account(
+            &authority.pubkey(),
+            &mint.pubkey(),
+            svm.minimum_balance_for_rent_exemption(spl_token::state::Mint::LEN),
+            spl_token::state::Mint::LEN as u64,
+            &spl_token::ID,
+        ),
+        spl_token::instruction::initialize_mint2(
+            &spl_token::ID,
+            &mint.pubkey(),
+            &authority.pubkey(),
+            None,
+            6,
+        )
+        .unwrap(),
+    ];
+    send(svm, &ixs, authority, &[&mint]).unwrap();
+    mint.pubkey()
+}
+
+/// creates a 6 decimals token-2022 mint with a transfer fee
+pub fn create_fee_mint(
+    svm: &mut LiteSVM,
+    authority: &Keypair,
+    basis_points: u16,
+    maximum_fee: u64,
+) -> Pubkey {
+    let mint = Keypair::new();
+    let space = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(&[
+        ExtensionType::TransferFeeConfig,
+    ])
+    .unwrap();
+    let ixs = [
+        system_instruction::create_account(
+            &authority.pubkey(),
+            &mint.pubkey(),
+            svm.minimum_balance_for_rent_exemption(space),
+            space as u64,
+            &spl_token_2022::ID,
+        ),
+        spl_token_2022::extension::transfer_fee::instruction::initialize_transfer_fee_config(
+            &spl_token_2022::ID,
+            &mint.pubkey(),
+            Some(&authority.pubkey()),
+            Some(&authority.pubkey()),
+            basis_points,
+            maximum_fee,
+        )
+        .unwrap(),
+        spl_token_2022::instruction::initialize_mint2(
+            &spl_token_2022::ID,
+            &mint.pubkey(),
+            &authority.pubkey(),
+            None,
+            6,
+        )
+        .unwrap(),
+    ];
+    send(svm, &ixs, authority, &[&mint]).unwrap();
+    mint.pubkey()
+}
+
+/// airdrops a new candidate, and funds its usdc ata
+pub fn new_candidate(tv: &mut TestValues) -> (Keypair, Pubkey) {
+    let candidate = Keypair::new();
+    tv.svm
+        .airdrop(&candidate.pubkey(), 10 * LAMPORTS_PER_SOL)
+        .unwrap();
+    let ata = get_associated_token_address_with_program_id(
+        &candidate.pubkey(),
+        &tv.mint,
+        &tv.token_program,
+    );
+    let ixs = [
+        spl_associated_token_account::instruction::create_associated_token_account(
+            &candidate.pubkey(),
+            &candidate.pubkey(),
+            &tv.mint,
+            &tv.token_program,
+        ),
+        spl_token_2022::instruction::mint_to(
+            &tv.token_program,
+            &tv.mint,
+            &ata,
+            &tv.admin.pubkey(),
+            &[],
+            900_000_000,
+        )
+        .unwrap(),
+    ];
+    let admin = tv.admin.insecure_clone();
+    send(&mut tv.svm, &ixs, &candidate, &[&admin]).unwrap();
+    (candidate, ata)
+}
+
+pub fn initialize(tv: &mut TestValues) -> TransactionResult {
+    let ix = aaas_client::initialize(
+        &tv.admin.pubkey(),
+        &tv.mint,
+        &tv.token_program,
+        tv.signers.iter().map(|signer| signer.pubkey()).collect(),
+        tv.threshold,
+    );
+    let admin = tv.admin.insecure_clone();
+    send(&mut tv.svm, &[ix], &admin, &[])
+}
+
+/// creates a proposal with the first signer, and approves it with the next `approvals - 1` signers
+pub fn propose(tv: &mut TestValues, action: AdminAction, approvals: usize) -> Pubkey {
+    let id = Pubkey::new_unique();
+    let proposer = tv.signers[0].insecure_clone();
+    let ix = aaas_client::create_proposal(&proposer.pubkey(), id, action);
+    send(&mut tv.svm, &[ix], &proposer, &[]).unwrap();
+
+    for approver in tv.signers[1..approvals].iter() {
+        let ix = aaas_client::approve_proposal(&approver.pubkey(), &id);
+        let approver = approver.insecure_clone();
+        send(&mut tv.svm, &[ix], &approver, &[]).unwrap();
+    }
+    id
+}
+
+/// proposes an admin action, approves it till threshold, and executes the instruction built for the
+/// executor, proposal id and proposer
+pub fn execute(
+    tv: &mut TestValues,
+    action: AdminAction,
+    build: impl FnOnce(&Pubkey, &Pubkey, &Pubkey) -> Instruction,
+) -> TransactionResult {
+    let approvals = tv.threshold as usize;
+    let proposal_id = propose(tv, action, approvals);
+    let executor = tv.admin.insecure_clone();
+    let ix = build(&executor.pubkey(), &proposal_id, &tv.signers[0].pubkey());
+    send(&mut tv.svm, &[ix], &executor, &[])
+}
+
+pub fn create_service_action(tv: &TestValues) -> AdminAction {
+    AdminAction::CreateService {
+        id: tv.service_id,
+        fee: tv.fee,
+        forfeit_fee: tv.forfeit_fee,
+        limits: tv.limits.clone(),
+        mints: vec![tv.mint],
+    }
+}
+
+pub fn initialize_service_ix(tv: &TestValues, proposal_id: &Pubkey) -> Instruction {
+    aaas_client::initialize_service(
+        &tv.admin.pubkey(),
+        proposal_id,
+        &tv.signers[0].pubkey(),
+        &tv.service_id,
+    )
+}
+
+/// proposes the service, approves it till threshold, and initializes it
+pub fn initialize_service(tv: &mut TestValues) -> TransactionResult {
+    let action = create_service_action(tv);
+    let proposal_id = propose(tv, action, tv.threshold as usize);
+    let ix = initialize_service_ix(tv, &proposal_id);
+    let admin = tv.admin.insecure_clone();
+    send(&mut tv.svm, &[ix], &admin, &[])
+}
+
+pub fn create_challenge_ix(tv: &TestValues, start_time: u64, end_time: u64) -> Instruction {
+    create_challenge_with(tv, challenge_args(tv, start_time, end_time))
+}
+
+pub fn create_challenge_with(tv: &TestValues, args: instruction::CreateChallenge) -> Instruction {
+    aaas_client::create_challenge(
+        &tv.challenge.creator.pubkey(),
+        &tv.service,
+        &tv.mint,
+        &tv.token_program,
+        args,
+    )
+}
+
+/// creates another challenge of the service with the changed args, it becomes the tested challenge
+pub fn new_challenge(
+    tv: &mut TestValues,
+    change: impl FnOnce(&mut instruction::CreateChallenge),
+) -> TransactionResult {
+    let now = tv.svm.get_sysvar::<Clock>().unix_timestamp as u64;
+    let id = Pubkey::new_unique();
+    tv.challenge.id = id;
+    tv.challenge.key = pda::challenge(&tv.service, &id).0;
+    let mut args = challenge_args(tv, now + DAY, now + 7 * DAY);
+    change(&mut args);
+
+    let challenge = &mut tv.challenge;
+    challenge.start_time = args.start_time;
+    challenge.end_time = args.end_time;
+    challenge.stake_amnt = args.stake_amnt;
+    challenge.validation_period = args.validation_period;
+    challenge.winning_threshold = args.winning_threshold;
+    challenge.quorum = args.quorum;
+    let ix = create_challenge_with(tv, args);
+    let creator = tv.challenge.creator.insecure_clone();
+    send(&mut tv.svm, &[ix], &creator, &[])
+}
+
+/// args of the tested challenge, with equal weighting and nothing optional enabled
+pub fn challenge_args(
+    tv: &TestValues,
+    start_time: u64,
+    end_time: u64,
+) -> instruction::CreateChallenge {
+    let challenge = &tv.challenge;
+    instruction::CreateChallenge {
+        id: challenge.id,
+        start_time,
+        end_time,
+        stake_amnt: challenge.stake_amnt,
+        validation_period: challenge.validation_period,
+        proof: String::new(),
+        winning_threshold: challenge.winning_threshold,
+        quorum: challenge.quorum,
+        weighting: VoteWeighting::Equal,
+        incentives: ValidatorIncentives {
+            reward: 0,
+            min_votes: 0,
+            min_agreement: 0,
+            penalty: 0,
+        }, //disabled
+        reveal_period: 0,
+        milestones: vec![],
+        min_milestones: 0,
+        max_candidates: 100,
+        allowlist_root: None,
+    }
+}
+
+pub fn join_ix(
+    tv: &TestValues,
+    candidate: &Pubkey,
+    ata: &Pubkey,
+    stake: u64,
+    allowlist_proof: Vec<[u8; 32]>,
+) -> Instruction {
+    aaas_client::join_challenge(
+        candidate,
+        &tv.service,
+        &tv.challenge.key,
+        &tv.mint,
+        ata,
+        &tv.token_program,
+        tv.profiles.contains(candidate),
+        stake,
+        allowlist_proof,
+    )
+}
+
+pub fn join_challenge(tv: &mut TestValues, candidate: &Keypair, ata: &Pubkey) -> TransactionResult {
+    let ix = join_ix(
+        tv,
+        &candidate.pubkey(),
+        ata,
+        tv.challenge.stake_amnt,
+        vec![],
+    );
+    send(&mut tv.svm, &[ix], candidate, &[])
+}
+
+pub fn init_profile(tv: &mut TestValues, owner: &Keypair) {
+    let ix = aaas_client::init_profile(&owner.pubkey());
+    send(&mut tv.svm, &[ix], owner, &[]).unwrap();
+    tv.profiles.push(owner.pubkey());
+}
+
+/// creates a candidate and joins the challenge, the clock must be before start_time
+pub fn join_with_new_candidate(tv: &mut TestValues) -> (Keypair, Pubkey) {
+    let (candidate, ata) = new_candidate(tv);
+    join_challenge(tv, &candidate, &ata).unwrap();
+    (candidate, ata)
+}
+
+/// sha256 of a proof artifact, as submitted and voted on
+pub fn proof_hash(artifact: &str) -> [u8; 32] {
+    hash(artifact.as_bytes()).to_bytes()
+}
+
+pub fn submit_proof(tv: &mut TestValues, candidate: &Keypair, proof: &str) -> TransactionResult {
+    let ix = aaas_client::submit_proof(
+        &candidate.pubkey(),
+        &tv.service,
+        &tv.challenge.key,
+        proof_hash(proof),
+        Some(proof.to_string()),
+    );
+    send(&mut tv.svm, &[ix], candidate, &[])
+}
+
+/// votes on the proof hash currently submitted by the candidate
+pub fn validate_proof(
+    tv: &mut TestValues,
+    validator: &Keypair,
+    candidate: &Pubkey,
+    vote: Vote,
+) -> TransactionResult {
+    let reviewed: CandidateAccount = get_account(&tv.svm, &tv.candidate_account(candidate));
+    let ix = aaas_client::validate_proof(
+        &validator.pubkey(),
+        &tv.service,
+        &tv.challenge.key,
+        candidate,
+        tv.profiles.contains(&validator.pubkey()),
+        vote,
+        reviewed.proof_hash,
+    );
+    send(&mut tv.svm, &[ix], validator, &[])
+}
+
+/// finalizes the challenge in batches of candidate accounts, so the transaction stays within the size limit
+pub fn finalize_challenge(tv: &mut TestValues, candidate_accounts: &[Pubkey]) {
+    let cranker = tv.admin.insecure_clone();
+    for batch in candidate_accounts.chunks(10) {
+        let ix = aaas_client::finalize_challenge(&cranker.pubkey(), &tv.challenge.key, batch);
+        send(&mut tv.svm, &[ix], &cranker, &[]).unwrap();
+    }
+}
+
+pub fn badge_ata(tv: &TestValues, winner: &Pubkey) -> Pubkey {
+    get_associated_token_address_with_program_id(
+        winner,
+        &pda::badge(&tv.challenge.key, winner).0,
+        &spl_token_2022::ID,
+    )
+}
+
+pub fn claim_badge(tv: &mut TestValues, winner: &Keypair) -> TransactionResult {
+    let ix = aaas_client::claim_badge(&winner.pubkey(), &tv.service_id, &tv.challenge.key);
+    send(&mut tv.svm, &[ix], winner, &[])
+}
+
+pub fn refund_stake(tv: &mut TestValues, candidate: &Keypair, ata: &Pubkey) -> TransactionResult {
+    let ix = aaas_client::refund_stake(
+        &candidate.pubkey(),
+        &tv.admin.pubkey(),
+        &tv.service,
+        &tv.challenge.key,
+        &tv.mint,
+        ata,
+        &tv.token_program,
+        tv.profiles.contains(&candidate.pubkey()),
+    );
+    send(&mut tv.svm, &[ix], candidate, &[])
+}
+
+/// joins `count` new candidates, the first one submits a proof that all the others approve
+pub fn challenge_with_winner(tv: &mut TestValues, count: usize) -> Vec<(Keypair, Pubkey)> {
+    let candidates: Vec<(Keypair, Pubkey)> =
+        (0..count).map(|_| join_with_new_candidate(tv)).collect();
+    let winner = candidates[0].0.insecure_clone();
+
+    set_clock(&mut tv.svm, tv.challenge.start_time + 500);
+    submit_proof(tv, &winner, "http://linkt/to/proof").unwrap();
+    set_clock(&mut tv.svm, tv.challenge.start_time + 1000);
+    for (validator, _) in &candidates[1..] {
+        validate_proof(tv, validator, &winner.pubkey(), Vote::Approve).unwrap();
+    }
+    candidates
+}
+
+/// candidate accounts of the given candidates, as passed to finalize_challenge
+pub fn candidate_accounts(tv: &TestValues, candidates: &[(Keypair, Pubkey)]) -> Vec<Pubkey> {
+    candidates
+        .iter()
+        .map(|(candidate, _)| tv.candidate_account(&candidate.pubkey()))
+        .collect()
+}
+
+/// closes the challenge and its vault, the rent goes back to the creator
+pub fn close_challenge(tv: &mut TestValues) -> TransactionResult {
+    let closer = tv.admin.insecure_clone();
+    let ix = aaas_client::close_challenge(
+        &closer.pubkey(),
+        &tv.challenge.creator.pubkey(),
+        &tv.admin.pubkey(),
+        &tv.service,
+        &tv.challenge.key,
+        &tv.mint,
+        &tv.token_program,
+    );
+    send(&mut tv.svm, &[ix], &closer, &[])
+}
+
+pub fn withdraw_reward(tv: &mut TestValues, winner: &Keypair, ata: &Pubkey) -> TransactionResult {
+    let ix = aaas_client::withdraw_reward(
+        &winner.pubkey(),
+        &tv.admin.pubkey(),
+        &tv.service,
+        &tv.challenge.key,
+        &tv.mint,
+        ata,
+        &tv.token_program,
+        tv.profiles.contains(&winner.pubkey()),
+    );
+    send(&mut tv.svm, &[ix], winner, &[])
+}
+
+/// compares the validator's vote on the candidate's proof to the outcome
+pub fn tally_vote(
+    tv: &mut TestValues,
+    candidate: &Pubkey,
+    validator: &Pubkey,
+) -> TransactionResult {
+    let cranker = tv.admin.insecure_clone();
+    let ix = aaas_client::tally_vote(
+        &cranker.pubkey(),
+        &tv.service,
+        &tv.challenge.key,
+        candidate,
+        validator,
+        tv.profiles.contains(validator),
+    );
+    send(&mut tv.svm, &[ix], &cranker, &[])
+}
+
+pub fn claim_validator_reward(
+    tv: &mut TestValues,
+    validator: &Keypair,
+    ata: &Pubkey,
+) -> TransactionResult {
+    let ix = aaas_client::claim_validator_reward(
+        &validator.pubkey(),
+        &tv.service,
+        &tv.challenge.key,
+        &tv.mint,
+        ata,
+        &tv.token_program,
+    );
+    send(&mut tv.svm, &[ix], validator, &[])
+}
+
+/// commits a hidden vote on the candidate's current proof
+pub fn commit_vote(
+    tv: &mut TestValues,
+    validator: &Keypair,
+    candidate: &Pubkey,
+    commitment: [u8; 32],
+) -> TransactionResult {
+    let reviewed: CandidateAccount = get_account(&tv.svm, &tv.candidate_account(candidate));
+    let ix = aaas_client::commit_vote(
+        &validator.pubkey(),
+        &tv.service,
+        &tv.challenge.key,
+        candidate,
+        commitment,
+        reviewed.proof_hash,
+    );
+    send(&mut tv.svm, &[ix], validator, &[])
+}
+
+pub fn reveal_vote(
+    tv: &mut TestValues,
+    validator: &Keypair,
+    candidate: &Pubkey,
+    vote: Vote,
+    salt: [u8; 32],
+) -> TransactionResult {
+    let ix = aaas_client::reveal_vote(
+        &validator.pubkey(),
+        &tv.service,
+        &tv.challenge.key,
+        candidate,
+        tv.profiles.contains(&validator.pubkey()),
+        vote,
+        salt,
+    );
+    send(&mut tv.svm, &[ix], validator, &[])
+}
+
+/// cancels the challenge with an approved CancelChallenge proposal
+pub fn emergency_cancel(tv: &mut TestValues) -> TransactionResult {
+    let challenge = tv.challenge.key;
+    execute(
+        tv,
+        AdminAction::CancelChallenge { challenge },
+        |executor, proposal_id, proposer| {
+            aaas_client::emergency_cancel_challenge(executor, proposal_id, proposer, &challenge)
+        },
+    )
+}
+
+pub fn milestone_proof(tv: &TestValues, candidate: &Pubkey, index: u8) -> Pubkey {
+    pda::milestone(&tv.challenge.key, &tv.candidate_account(candidate), index).0
+}
+
+pub fn submit_milestone(
+    tv: &mut TestValues,
+    candidate: &Keypair,
+    index: u8,
+    proof: &str,
+) -> TransactionResult {
+    let ix = aaas_client::submit_milestone(
+        &candidate.pubkey(),
+        &tv.service,
+        &tv.challenge.key,
+        index,
+        proof_hash(proof),
+        Some(proof.to_string()),
+    );
+    send(&mut tv.svm, &[ix], candidate, &[])
+}
+
+/// votes on the hash of the candidate's milestone proof
+pub fn validate_milestone(
+    tv: &mut TestValues,
+    validator: &Keypair,
+    candidate: &Pubkey,
+    index: u8,
+    vote: Vote,
+) -> TransactionResult {
+    let reviewed: aaas::MilestoneProof =
+        get_account(&tv.svm, &milestone_proof(tv, candidate, index));
+    let ix = aaas_client::validate_milestone(
+        &validator.pubkey(),
+        &tv.service,
+        &tv.challenge.key,
+        candidate,
+        index,
+        tv.profiles.contains(&validator.pubkey()),
+        vote,
+        reviewed.proof_hash,
+    );
+    send(&mut tv.svm, &[ix], validator, &[])
+}
+
+pub fn finalize_milestone(tv: &mut TestValues, candidate: &Pubkey, index: u8) -> TransactionResult {
+    let cranker = tv.admin.insecure_clone();
+    let ix = aaas_client::finalize_milestone(
+        &cranker.pubkey(),
+        &tv.service,
+        &tv.challenge.key,
+        candidate,
+        index,
+    );
+    send(&mut tv.svm, &[ix], &cranker, &[])
+}
+
+/// sets the service attestor, challenges created afterwards are attested by it
+pub fn set_attestor(tv: &mut TestValues, attestor: Option<Pubkey>) -> TransactionResult {
+    let action = AdminAction::SetServiceAttestor {
+        id: tv.service_id,
+        attestor,
+    };
+    let service_id = tv.service_id;
+    execute(tv, action, |executor, proposal_id, proposer| {
+        aaas_client::set_service_attestor(executor, proposal_id, proposer, &service_id)
+    })
+}
+
+/// ed25519 program instruction with a single signature of the message, every offset points in itself
+pub fn ed25519_ix(signer: &Keypair, message: &[u8]) -> Instruction {
+    //signature count and padding, followed by the seven u16 offsets
+    let public_key_offset: u16 = 2 + 7 * 2;
+    let signature_offset = public_key_offset + 32;
+    let message_offset = signature_offset + 64;
+    let mut data = vec![1u8, 0];
+    for offset in [
+        signature_offset,
+        u16::MAX,
+        public_key_offset,
+        u16::MAX,
+        message_offset,
+        message.len() as u16,
+        u16::MAX,
+    ] {
+        data.extend(offset.to_le_bytes());
+    }
+    data.extend(signer.pubkey().as_ref());
+    data.extend(signer.sign_message(message).as_ref());
+    data.extend(message);
+    Instruction {
+        program_id: ed25519_program::ID,
+        accounts: vec![],
+        data,
+    }
+}
+
+/// the message an attestor signs, challenge || candidate || passed
+pub fn attestation_message(tv: &TestValues, candidate: &Pubkey, passed: bool) -> Vec<u8> {
+    [
+        tv.challenge.key.as_ref(),
+        candidate.as_ref(),
+        &[passed as u8],
+    ]
+    .concat()
+}
+
+pub fn attest_ix(tv: &TestValues, candidate: &Pubkey, passed: bool) -> Instruction {
+    aaas_client::attest_result(
+        &tv.admin.pubkey(),
+        &tv.service,
+        &tv.challenge.key,
+        candidate,
+        passed,
+    )
+}
+
+/// the attestor signs the candidate's result, and the admin submits it
+pub fn attest_result(
+    tv: &mut TestValues,
+    attestor: &Keypair,
+    candidate: &Pubkey,
+    passed: bool,
+) -> TransactionResult {
+    let message = attestation_message(tv, candidate, passed);
+    let ixs = [
+        ed25519_ix(attestor, &message),
+        attest_ix(tv, candidate, passed),
+    ];
+    let admin = tv.admin.insecure_clone();
+    send(&mut tv.svm, &ixs, &admin, &[])
+}
+
+/// exits the challenge before it starts, the stake goes back to the candidate
+pub fn exit_challenge(tv: &mut TestValues, candidate: &Keypair, ata: &Pubkey) -> TransactionResult {
+    let ix = aaas_client::exit_challenge(
+        &candidate.pubkey(),
+        &tv.service,
+        &tv.challenge.key,
+        &tv.mint,
+        ata,
+        &tv.token_program,
+        tv.profiles.contains(&candidate.pubkey()),
+    );
+    send(&mut tv.svm, &[ix], candidate, &[])
+}
+
+pub fn sponsor_challenge(
+    tv: &mut TestValues,
+    sponsor: &Keypair,
+    ata: &Pubkey,
+    amount: u64,
+) -> TransactionResult {
+    let ix = aaas_client::sponsor_challenge(
+        &sponsor.pubkey(),
+        &tv.challenge.key,
+        &tv.mint,
+        ata,
+        &tv.token_program,
+        amount,
+    );
+    send(&mut tv.svm, &[ix], sponsor, &[])
+}
+
+pub fn refund_sponsorship(
+    tv: &mut TestValues,
+    sponsor: &Keypair,
+    ata: &Pubkey,
+) -> TransactionResult {
+    let ix = aaas_client::refund_sponsorship(
+        &sponsor.pubkey(),
+        &tv.challenge.key,
+        &tv.mint,
+        ata,
+        &tv.token_program,
+    );
+    send(&mut tv.svm, &[ix], sponsor, &[])
+}
+
+/// closes the sponsorship of a settled challenge, the rent goes back to the sponsor
+pub fn close_sponsorship(tv: &mut TestValues, sponsor: &Pubkey) -> TransactionResult {
+    let closer = tv.admin.insecure_clone();
+    let ix = aaas_client::close_sponsorship(&closer.pubkey(), sponsor, &tv.challenge.key);
+    send(&mut tv.svm, &[ix], &closer, &[])
+}
+
+/// the creator cancels the challenge
+pub fn cancel_challenge(tv: &mut TestValues) -> TransactionResult {
+    let creator = tv.challenge.creator.insecure_clone();
+    let ix = aaas_client::cancel_challenge(&creator.pubkey(), &tv.challenge.key);
+    send(&mut tv.svm, &[ix], &creator, &[])
+}
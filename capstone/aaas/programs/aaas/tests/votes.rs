@@ -0,0 +1,83 @@
+mod utils;
+
+use aaas::{CandidateAccount, Challenge, Vote};
+use anchor_lang::prelude::Pubkey;
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use utils::*;
+
+/// joins `count` candidates to a new challenge, the first one submits a proof
+fn challenge_with_proof(
+    tv: &mut TestValues,
+    winning_threshold: u16,
+    quorum: u16,
+    count: usize,
+) -> Vec<(Keypair, Pubkey)> {
+    new_challenge(tv, |args| {
+        args.winning_threshold = winning_threshold;
+        args.quorum = quorum;
+    })
+    .unwrap();
+    let candidates: Vec<(Keypair, Pubkey)> =
+        (0..count).map(|_| join_with_new_candidate(tv)).collect();
+    set_clock(&mut tv.svm, tv.challenge.start_time + 1);
+    submit_proof(tv, &candidates[0].0, "http://linkt/to/proof").unwrap();
+    candidates
+}
+
+/// finalizes the challenge, and tells if the first candidate won
+fn first_wins(tv: &mut TestValues, candidates: &[(Keypair, Pubkey)]) -> bool {
+    set_clock(&mut tv.svm, tv.challenge.validation_end() + 1);
+    let accounts = candidate_accounts(tv, candidates);
+    finalize_challenge(tv, &accounts);
+    let candidate: CandidateAccount = get_account(&tv.svm, &accounts[0]);
+    candidate.winner
+}
+
+#[test]
+fn full_quorum_counts_the_other_candidates() {
+    let mut tv = setup();
+    let candidates = challenge_with_proof(&mut tv, 10000, 10000, 3);
+    let reviewed = candidates[0].0.pubkey();
+    for (validator, _) in &candidates[1..] {
+        validate_proof(&mut tv, validator, &reviewed, Vote::Approve).unwrap();
+    }
+    assert!(first_wins(&mut tv, &candidates));
+    let challenge: Challenge = get_account(&tv.svm, &tv.challenge.key);
+    assert_eq!(challenge.winner_count, 1);
+}
+
+#[test]
+fn quorum_not_reached() {
+    let mut tv = setup();
+    let candidates = challenge_with_proof(&mut tv, 10000, 10000, 3);
+    let reviewed = candidates[0].0.pubkey();
+    validate_proof(&mut tv, &candidates[1].0, &reviewed, Vote::Approve).unwrap();
+    assert!(!first_wins(&mut tv, &candidates));
+}
+
+#[test]
+fn silent_candidates_dont_count_against() {
+    let mut tv = setup();
+    //half of the other candidates approve, the rest are silent
+    let candidates = challenge_with_proof(&mut tv, 8500, 5000, 5);
+    let reviewed = candidates[0].0.pubkey();
+    for (validator, _) in &candidates[1..3] {
+        validate_proof(&mut tv, validator, &reviewed, Vote::Approve).unwrap();
+    }
+    assert!(first_wins(&mut tv, &candidates));
+}
+
+#[test]
+fn rejections_count_against() {
+    let mut tv = setup();
+    let candidates = challenge_with_proof(&mut tv, 8500, 5000, 5);
+    let reviewed = candidates[0].0.pubkey();
+    validate_proof(&mut tv, &candidates[1].0, &reviewed, Vote::Approve).unwrap();
+    validate_proof(&mut tv, &candidates[2].0, &reviewed, Vote::Reject).unwrap();
+
+    let candidate: CandidateAccount = get_account(&tv.svm, &tv.candidate_account(&reviewed));
+    assert_eq!(candidate.acceptance, 1);
+    assert_eq!(candidate.rejections, 1);
+    assert!(!first_wins(&mut tv, &candidates));
+}
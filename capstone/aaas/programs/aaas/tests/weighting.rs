@@ -0,0 +1,111 @@
+mod utils;
+
+use aaas::error::AaasError;
+use aaas::{CandidateAccount, Challenge, Profile, Vote, VoteWeighting};
+use aaas_client::pda;
+use anchor_lang::prelude::Pubkey;
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use utils::*;
+
+fn candidate(tv: &TestValues, candidate: &Pubkey) -> CandidateAccount {
+    get_account(&tv.svm, &tv.candidate_account(candidate))
+}
+
+/// joins with a new candidate staking `stake`
+fn join_staking(tv: &mut TestValues, stake: u64) -> (Keypair, Pubkey) {
+    let (candidate, ata) = new_candidate(tv);
+    let ix = join_ix(tv, &candidate.pubkey(), &ata, stake, vec![]);
+    send(&mut tv.svm, &[ix], &candidate, &[]).unwrap();
+    (candidate, ata)
+}
+
+#[test]
+fn equal_weighting_needs_the_exact_stake() {
+    let mut tv = setup();
+    let (candidate, ata) = new_candidate(&mut tv);
+
+    let ix = join_ix(
+        &tv,
+        &candidate.pubkey(),
+        &ata,
+        tv.challenge.stake_amnt + 1,
+        vec![],
+    );
+    expect_error(
+        send(&mut tv.svm, &[ix], &candidate, &[]),
+        AaasError::InvalidStakeAmount,
+    );
+}
+
+#[test]
+fn stake_weighted_votes() {
+    let mut tv = setup();
+    new_challenge(&mut tv, |args| args.weighting = VoteWeighting::Stake).unwrap();
+    let stake_amnt = tv.challenge.stake_amnt;
+
+    //at least the stake amnt
+    let (low, ata) = new_candidate(&mut tv);
+    let ix = join_ix(&tv, &low.pubkey(), &ata, stake_amnt - 1, vec![]);
+    expect_error(
+        send(&mut tv.svm, &[ix], &low, &[]),
+        AaasError::InvalidStakeAmount,
+    );
+
+    let (whale, _) = join_staking(&mut tv, 10 * stake_amnt);
+    let (reviewed, _) = join_staking(&mut tv, stake_amnt);
+    let (minnow, _) = join_staking(&mut tv, stake_amnt);
+    assert_eq!(candidate(&tv, &whale.pubkey()).weight, 10 * stake_amnt);
+    let challenge: Challenge = get_account(&tv.svm, &tv.challenge.key);
+    assert_eq!(challenge.total_weight, 12 * stake_amnt);
+    assert_eq!(challenge.total_stake, 12 * stake_amnt);
+
+    set_clock(&mut tv.svm, tv.challenge.start_time + 1);
+    submit_proof(&mut tv, &reviewed, "http://linkt/to/proof").unwrap();
+    validate_proof(&mut tv, &whale, &reviewed.pubkey(), Vote::Approve).unwrap();
+    validate_proof(&mut tv, &minnow, &reviewed.pubkey(), Vote::Reject).unwrap();
+
+    let reviewed = candidate(&tv, &reviewed.pubkey());
+    assert_eq!(reviewed.acceptance, 10 * stake_amnt);
+    assert_eq!(reviewed.rejections, stake_amnt);
+}
+
+#[test]
+fn reputation_weighted_votes() {
+    let mut tv = setup();
+
+    //the winner of a first challenge gets reputation on its profile
+    let (winner, winner_ata) = new_candidate(&mut tv);
+    init_profile(&mut tv, &winner);
+    join_challenge(&mut tv, &winner, &winner_ata).unwrap();
+    let mut candidates = vec![(winner.insecure_clone(), winner_ata)];
+    candidates.extend((0..6).map(|_| join_with_new_candidate(&mut tv)));
+    set_clock(&mut tv.svm, tv.challenge.start_time + 500);
+    submit_proof(&mut tv, &winner, "http://linkt/to/proof").unwrap();
+    for (validator, _) in &candidates[1..] {
+        validate_proof(&mut tv, validator, &winner.pubkey(), Vote::Approve).unwrap();
+    }
+    set_clock(&mut tv.svm, tv.challenge.validation_end() + 1);
+    let accounts = candidate_accounts(&tv, &candidates);
+    finalize_challenge(&mut tv, &accounts);
+    withdraw_reward(&mut tv, &winner, &winner_ata).unwrap();
+    let profile: Profile = get_account(&tv.svm, &pda::profile(&winner.pubkey()).0);
+    assert_eq!(profile.reputation, 1);
+
+    //votes weigh 1 + reputation, a missing profile counts as no reputation
+    new_challenge(&mut tv, |args| args.weighting = VoteWeighting::Reputation).unwrap();
+    join_challenge(&mut tv, &winner, &winner_ata).unwrap();
+    let (newcomer, _) = join_with_new_candidate(&mut tv);
+    let (reviewed, _) = join_with_new_candidate(&mut tv);
+    assert_eq!(candidate(&tv, &winner.pubkey()).weight, 2);
+    assert_eq!(candidate(&tv, &newcomer.pubkey()).weight, 1);
+
+    set_clock(&mut tv.svm, tv.challenge.start_time + 1);
+    submit_proof(&mut tv, &reviewed, "http://linkt/to/proof").unwrap();
+    validate_proof(&mut tv, &winner, &reviewed.pubkey(), Vote::Approve).unwrap();
+    validate_proof(&mut tv, &newcomer, &reviewed.pubkey(), Vote::Reject).unwrap();
+
+    let reviewed = candidate(&tv, &reviewed.pubkey());
+    assert_eq!(reviewed.acceptance, 2);
+    assert_eq!(reviewed.rejections, 1);
+}
@@ -4,7 +4,7 @@ import { Aaas } from "../target/types/aaas";
 import { Keypair, PublicKey, SystemProgram, Transaction, TransactionInstruction } from "@solana/web3.js";
 import { getAssociatedTokenAddressSync, TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, AccountLayout } from "@solana/spl-token";
 import { join } from "path";
-import { createValues, generateCandidate, initCandidateAta, ITestValues, joinChallengeWithNewCandidate, setClock, submitProof, validateProof } from "./utils";
+import { createValues, finalizeChallenge, generateCandidate, initCandidateAta, ITestValues, joinChallengeWithNewCandidate, proposeAndApprove, proofHash, setClock, submitProof, validateProof } from "./utils";
 
 import { FailedTransactionMetadata, LiteSVM, SimulatedTransactionInfo, TransactionMetadata } from "litesvm";
 import { expect } from "chai";
@@ -27,19 +27,13 @@ describe.only("aaas with litesvm", () => {
     const tx = await program.methods.initialize(testValues.config.signers.map(ms => ms.publicKey), 2)
       .accounts({
         tokenProgram: TOKEN_PROGRAM_ID,
-        usdcMint: testValues.usdcMint.publicKey,
+        mint: testValues.usdcMint.publicKey,
         admin: testValues.admin.publicKey,
       })
-      .remainingAccounts(
-        [
-          { isSigner: true, isWritable: false, pubkey: testValues.config.signers[1].publicKey },
-          { isSigner: true, isWritable: false, pubkey: testValues.config.signers[2].publicKey }
-        ]
-      )
       .transaction();
     tx.feePayer = testValues.admin.publicKey;
     tx.recentBlockhash = svm.latestBlockhash();
-    tx.sign(testValues.admin, testValues.config.signers[1], testValues.config.signers[2]); //tx fee testValues.payer 
+    tx.sign(testValues.admin); //tx fee testValues.payer 
 
     const res = svm.sendTransaction(tx);
     if (res instanceof FailedTransactionMetadata) throw new Error("Expected successfull send transaction!");
@@ -58,21 +52,32 @@ describe.only("aaas with litesvm", () => {
   })
 
   it("Is Service Initialized!", async () => {
-    const tx = await program.methods.initializeService(testValues.service.id, testValues.service.fee)
+    const [proposal, proposer] = await proposeAndApprove(svm, testValues, program, {
+      createService: {
+        id: testValues.service.id,
+        fee: testValues.service.fee,
+        forfeitFee: testValues.service.forfeitFee,
+        limits: testValues.service.limits,
+        mints: [testValues.usdcMint.publicKey],
+      }
+    });
+
+    //the service values are read from the proposal, the id only derives the service address
+    const tx = await program.methods.initializeService(testValues.service.id)
       .accounts({
-        initializer: testValues.admin.publicKey,
-        //@ts-ignore
-        config: testValues.config.key,
-      }).remainingAccounts([
-        { isSigner: true, isWritable: false, pubkey: testValues.config.signers[0].publicKey },
-        { isSigner: true, isWritable: false, pubkey: testValues.config.signers[3].publicKey },
-      ])
-      //.signers([testValues.payer, testValues.config.signers[0], testValues.config.signers[3]]) //this is just defining metadata - what are the keypairs which will sign the tx, actual sign will be call when rpc or simulate method is called
+        execution: {
+          executor: testValues.admin.publicKey,
+          //@ts-ignore
+          config: testValues.config.key,
+          proposal,
+          proposer: proposer.publicKey,
+        },
+      })
       .transaction();
 
     tx.recentBlockhash = svm.latestBlockhash();
     //tx.feePayer = testValues.payer.publicKey; //by default the first keypair will be the fee payer in sign(...)
-    tx.sign(testValues.admin, testValues.config.signers[0], testValues.config.signers[3]);
+    tx.sign(testValues.admin);
 
     // const res = svm.simulateTransaction(tx); //use simulate to find logs
     // console.log(res.meta().logs());
@@ -89,12 +94,32 @@ describe.only("aaas with litesvm", () => {
     console.log("Expectation✅ - service account have expected fee, and id values");
   })
 
+  it("shouldn't create challenge that ends before it starts!", async () => {
+    const { startTime, stakeAmnt, validationPeriod, proof, winningThreshold, quorum, incentives, revealPeriod, milestones, minMilestones, maxCandidates, allowlistRoot, creator } = testValues.challenge;
+    const id = PublicKey.unique();
+    const tx = await program.methods.createChallenge(id, startTime, startTime.sub(new BN(1)), new BN(stakeAmnt), validationPeriod, proof, winningThreshold, quorum, { equal: {} }, incentives, revealPeriod, milestones, minMilestones, maxCandidates, allowlistRoot)
+      .accounts({
+        tokenProgram: TOKEN_PROGRAM_ID,
+        mint: testValues.usdcMint.publicKey,
+        creator: creator.publicKey,
+        //@ts-ignore
+        service: testValues.service.key,
+      }).transaction();
+
+    tx.recentBlockhash = svm.latestBlockhash();
+    tx.sign(creator);
+
+    const res = svm.sendTransaction(tx);
+    expect(res).to.be.instanceOf(FailedTransactionMetadata);
+    console.log("Expectation✅ - challenge with end time before start time is rejected");
+  })
+
   it("is challenge created!", async () => {
-    const { id, endTime, startTime, stakeAmnt, key, proof, winningThreshold, vault, creator } = testValues.challenge;
-    const tx = await program.methods.createChallenge(id, startTime, endTime, new BN(stakeAmnt), proof, winningThreshold)
+    const { id, endTime, startTime, stakeAmnt, validationPeriod, key, proof, winningThreshold, quorum, incentives, revealPeriod, milestones, minMilestones, maxCandidates, allowlistRoot, vault, creator } = testValues.challenge;
+    const tx = await program.methods.createChallenge(id, startTime, endTime, new BN(stakeAmnt), validationPeriod, proof, winningThreshold, quorum, { equal: {} }, incentives, revealPeriod, milestones, minMilestones, maxCandidates, allowlistRoot)
       .accounts({
         tokenProgram: TOKEN_PROGRAM_ID,
-        usdcMint: testValues.usdcMint.publicKey,
+        mint: testValues.usdcMint.publicKey,
         creator: creator.publicKey,
         //@ts-ignore
         service: testValues.service.key,
@@ -126,13 +151,14 @@ describe.only("aaas with litesvm", () => {
   })
 
   it("should join challenge!", async () => {
-    const tx = await program.methods.joinChallenge()
+    const tx = await program.methods.joinChallenge(new BN(testValues.challenge.stakeAmnt), [])
       .accounts({
         tokenProgram: TOKEN_PROGRAM_ID,
-        usdcMint: testValues.usdcMint.publicKey,
+        mint: testValues.usdcMint.publicKey,
         candidate: testValues.candidate.payer.publicKey,
         //@ts-ignore
         challenge: testValues.challenge.key, //just passing challenge is not enough to derive both challenge, and candidateAccount PDAs
+        service: testValues.service.key,
         candidateAccount: testValues.candidate.account, //this is needed as anchor will need to go to depth 2 as it first need to derive the pda for challlenge 
       }).transaction();
     tx.recentBlockhash = svm.latestBlockhash();
@@ -151,7 +177,7 @@ describe.only("aaas with litesvm", () => {
 
     const candidateAccount = program.coder.accounts.decode<AaasAccounts["candidateAccount"]>(
       "candidateAccount", Buffer.from(candidateInfo?.data!));
-    expect(candidateAccount.acceptance).to.be.equal(0);
+    expect(candidateAccount.acceptance.toNumber()).to.be.equal(0);
     expect(candidateAccount.candidate.toBase58()).to.be.equal(testValues.candidate.payer.publicKey.toBase58());
     expect(candidateAccount.proof).to.be.equal("");
     expect(candidateAccount.rewarded).to.be.false;
@@ -184,13 +210,14 @@ describe.only("aaas with litesvm", () => {
       svm, testValues.service.key, testValues.challenge.key, program.programId);
     await initCandidateAta(testValues.usdcMint, candidate, svm, testValues.admin);
 
-    const tx = await program.methods.joinChallenge()
+    const tx = await program.methods.joinChallenge(new BN(testValues.challenge.stakeAmnt), [])
       .accounts({
         tokenProgram: TOKEN_PROGRAM_ID,
-        usdcMint: testValues.usdcMint.publicKey,
+        mint: testValues.usdcMint.publicKey,
         candidate: candidate.publicKey,
         //@ts-ignore
         challenge: testValues.challenge.key, //just passing challenge is not enough to derive both challenge, and candidateAccount PDAs
+        service: testValues.service.key,
         candidateAccount, //this is needed as 
       }).transaction();
     tx.recentBlockhash = svm.latestBlockhash();
@@ -221,7 +248,7 @@ describe.only("aaas with litesvm", () => {
     const tx = await program.methods.exitChallenge()
       .accounts({
         tokenProgram: TOKEN_PROGRAM_ID,
-        usdcMint: testValues.usdcMint.publicKey,
+        mint: testValues.usdcMint.publicKey,
         //@ts-ignore
         candidate: candidate.publicKey,
         challenge: testValues.challenge.key,
@@ -263,7 +290,7 @@ describe.only("aaas with litesvm", () => {
     const tx = await program.methods.exitChallenge()
       .accounts({
         tokenProgram: TOKEN_PROGRAM_ID,
-        usdcMint: testValues.usdcMint.publicKey,
+        mint: testValues.usdcMint.publicKey,
         //@ts-ignore
         candidate: candidate.publicKey,
         candidateAccount,
@@ -297,7 +324,7 @@ describe.only("aaas with litesvm", () => {
     svm.setClock(clock);
 
     const proof = "http://linkt/to/proof";
-    const tx = await program.methods.submitProof(proof)
+    const tx = await program.methods.submitProof(proofHash(proof), proof)
       .accounts({
         challenge: testValues.challenge.key,
         candidate: testValues.candidate.payer.publicKey,
@@ -314,6 +341,7 @@ describe.only("aaas with litesvm", () => {
     const candidateAccount = program.coder.accounts.decode<AaasAccounts["candidateAccount"]>(
       "candidateAccount", Buffer.from(candidateAccountInfo?.data!));
     expect(candidateAccount.proof).to.be.equal(proof);
+    expect(candidateAccount.proofHash).to.deep.equal(proofHash(proof));
     console.log("Expectations✅ - submitted proof as expected");
 
     //set time to just before the challenge ends
@@ -322,7 +350,7 @@ describe.only("aaas with litesvm", () => {
     console.log("clock set, for shouldn't resubmit case, to ", svm.getClock().unixTimestamp);
 
     const newProof = "https://new/proof/link"
-    const resubmitTx = await program.methods.submitProof(newProof)
+    const resubmitTx = await program.methods.submitProof(proofHash(newProof), newProof)
       .accounts({
         challenge: testValues.challenge.key,
         candidate: testValues.candidate.payer.publicKey,
@@ -351,7 +379,7 @@ describe.only("aaas with litesvm", () => {
     const [candidate, candidateAccount] = await joinChallengeWithNewCandidate(svm, testValues, program);
     const proof = "https://some/link/to/proof";
     //clock is at reset or 0
-    const earlyTx = await program.methods.submitProof(proof)
+    const earlyTx = await program.methods.submitProof(proofHash(proof), proof)
       .accounts({
         challenge: testValues.challenge.key,
         candidate: candidate.publicKey,
@@ -370,7 +398,7 @@ describe.only("aaas with litesvm", () => {
     const candidateAccountData = program.coder.accounts.decode<AaasAccounts["candidateAccount"]>(
       "candidateAccount", Buffer.from(svm.getAccount(candidateAccount)?.data!));
     expect(candidateAccountData.proof).to.be.empty;
-    expect(candidateAccountData.acceptance).to.be.equal(0);
+    expect(candidateAccountData.acceptance.toNumber()).to.be.equal(0);
     console.log("Expectations✅ - proof submit is failed before challenge started");
 
     //add to candidates
@@ -387,7 +415,7 @@ describe.only("aaas with litesvm", () => {
     svm.setClock(clock);
     console.log("in shouldn't submit proof, set clock at:", svm.getClock().unixTimestamp);
 
-    const afterTx = await program.methods.submitProof(proof)
+    const afterTx = await program.methods.submitProof(proofHash(proof), proof)
       .accounts({
         challenge: testValues.challenge.key,
         candidate: candidate.publicKey,
@@ -406,7 +434,7 @@ describe.only("aaas with litesvm", () => {
     const candidateAccountDataAfter = program.coder.accounts.decode<AaasAccounts["candidateAccount"]>(
       "candidateAccount", Buffer.from(svm.getAccount(candidateAccount)?.data!));
     expect(candidateAccountDataAfter.proof).to.be.empty;
-    expect(candidateAccountDataAfter.acceptance).to.be.equal(0);
+    expect(candidateAccountDataAfter.acceptance.toNumber()).to.be.equal(0);
 
     //add to candidate list
     candidates.push([candidate, candidateAccount]);
@@ -424,7 +452,9 @@ describe.only("aaas with litesvm", () => {
 
     //set time after challenge but should be within validation period
     setClock(svm, BigInt(testValues.challenge.endTime.toNumber() + 500));
-    const tx = await program.methods.validateProof()
+    const { proofHash: reviewedHash } = program.coder.accounts.decode<AaasAccounts["candidateAccount"]>(
+      "candidateAccount", Buffer.from(svm.getAccount(candidateAccount)?.data!));
+    const tx = await program.methods.validateProof({ approve: {} }, reviewedHash)
       .accounts({
         validator: validator.publicKey,
         //@ts-ignore
@@ -445,10 +475,32 @@ describe.only("aaas with litesvm", () => {
 
     const candidateAccData = program.coder.accounts.decode<AaasAccounts["candidateAccount"]>(
       "candidateAccount", Buffer.from(svm.getAccount(candidateAccount)?.data!));
-    expect(candidateAccData.acceptance).to.be.greaterThan(0);
+    expect(candidateAccData.acceptance.toNumber()).to.be.greaterThan(0);
     console.log("Expectation✅ - validated proof as expected!");
   })
 
+  it("should reject proof!", async () => {
+    setClock(svm, BigInt(0));
+    const [candidate, candidateAccount] = await joinChallengeWithNewCandidate(svm, testValues, program);
+
+    setClock(svm, BigInt(testValues.challenge.endTime.toNumber() - 1000));
+    await submitProof(svm, program, testValues.challenge.key, candidate, candidateAccount);
+
+    setClock(svm, BigInt(testValues.challenge.endTime.toNumber() + 500));
+    const [sim, res] = await validateProof(svm, testValues, testValues.candidate.payer, candidateAccount, program, { reject: {} });
+    if (sim instanceof FailedTransactionMetadata) console.log(sim.meta().logs());
+    expect(res).to.be.instanceOf(TransactionMetadata);
+
+    const candidateAccData = program.coder.accounts.decode<AaasAccounts["candidateAccount"]>(
+      "candidateAccount", Buffer.from(svm.getAccount(candidateAccount)?.data!));
+    expect(candidateAccData.acceptance.toNumber()).to.be.equal(0);
+    expect(candidateAccData.rejections.toNumber()).to.be.equal(1);
+    console.log("Expectation✅ - rejected proof as expected!");
+
+    //add to candidates
+    candidates.push([candidate, candidateAccount]);
+  })
+
   it("shouldn't validate proof, after validation period", async () => {
     setClock(svm, BigInt(0));
     console.log("time at start:", svm.getClock().unixTimestamp);
@@ -461,7 +513,7 @@ describe.only("aaas with litesvm", () => {
     await submitProof(svm, program, testValues.challenge.key, candidate, candidateAccount);
 
     //set clock after validation period i.e. end_time + 24 hrs
-    setClock(svm, BigInt(testValues.challenge.endTime.toNumber() + (60 * 60 * 24) + 100));
+    setClock(svm, BigInt(testValues.challenge.endTime.add(testValues.challenge.validationPeriod).toNumber() + 100));
     console.log("time at validate proof:", svm.getClock().unixTimestamp);
 
     const [sim, res] = await validateProof(svm, testValues, testValues.candidate.payer, candidateAccount, program);
@@ -474,8 +526,11 @@ describe.only("aaas with litesvm", () => {
     const candidateAccountData = await program.coder.accounts.decode<AaasAccounts["candidateAccount"]>(
       "candidateAccount", Buffer.from(svm.getAccount(candidateAccount)?.data!)
     );
-    expect(candidateAccountData.acceptance).to.be.equal(0);
+    expect(candidateAccountData.acceptance.toNumber()).to.be.equal(0);
     console.log("Expectation✅ - proof validation failed as expected!");
+
+    //add to candidates
+    candidates.push([candidate, candidateAccount]);
   })
 
   /** 
@@ -513,18 +568,18 @@ describe.only("aaas with litesvm", () => {
     expect(candidateAccountData1.proof).to.be.not.empty;
     expect(candidateAccountData1.candidate.equals(testValues.candidate.payer.publicKey)).to.be.true;
     expect(candidateAccountData1.rewarded).to.be.false;
-    expect(candidateAccountData1.acceptance).to.be.lessThan(5);
+    expect(candidateAccountData1.acceptance.toNumber()).to.be.lessThan(5);
     console.log("proof submit✔️");
 
     //withdraw too soon within validation period
     const tooSoonTx = await program.methods.withdrawReward().accounts({
       tokenProgram: TOKEN_PROGRAM_ID,
-      usdcMint: testValues.usdcMint.publicKey,
+      mint: testValues.usdcMint.publicKey,
       winner: testValues.candidate.payer.publicKey,
       //@ts-ignore
       winnerAccount: testValues.candidate.account,
       config: testValues.config.key,
-      service: testValues.service.key,
+      admin: testValues.admin.publicKey,
       challenge: testValues.challenge.key,
       treasury: testValues.treasury
     }).transaction();
@@ -540,22 +595,22 @@ describe.only("aaas with litesvm", () => {
     setClock(svm, BigInt(0)); //reset clock 
   })
 
-  it("withdraw reward with low votes, and it should failed!", async () => {
+  it("withdraw reward before finalization, and it should failed!", async () => {
     //set time to after validation period
-    const newTime = BigInt(testValues.challenge.endTime.toNumber() + (60 * 60 * 24) + 1000);
+    const newTime = BigInt(testValues.challenge.endTime.add(testValues.challenge.validationPeriod).toNumber() + 1000);
     svm.expireBlockhash();
     setClock(svm, newTime);
     console.log("new time:", svm.getClock().unixTimestamp, " is it what I set?", newTime === svm.getClock().unixTimestamp);
 
-    //withdraw with no enough acceptance, and it will failed
+    //withdraw before the winners are finalized, and it will failed
     const lowVoteTx = await program.methods.withdrawReward().accounts({
       tokenProgram: TOKEN_PROGRAM_ID,
-      usdcMint: testValues.usdcMint.publicKey,
+      mint: testValues.usdcMint.publicKey,
       winner: testValues.candidate.payer.publicKey,
       //@ts-ignore
       winnerAccount: testValues.candidate.account,
       config: testValues.config.key,
-      service: testValues.service.key,
+      admin: testValues.admin.publicKey,
       challenge: testValues.challenge.key,
       treasury: testValues.treasury,
     }).transaction();
@@ -565,8 +620,8 @@ describe.only("aaas with litesvm", () => {
     const lowVoteRes = svm.sendTransaction(lowVoteTx);
     console.log(lowVoteSim.meta().logs());
     expect(lowVoteRes).to.be.instanceOf(FailedTransactionMetadata);
-    expect(lowVoteSim.meta().logs().some(log => log.includes("WinningThreshold"))).to.be.true;
-    console.log("withdraw reward with low acceptance, denied✔️");
+    expect(lowVoteSim.meta().logs().some(log => log.includes("ChallengeNotFinalized"))).to.be.true;
+    console.log("withdraw reward before finalization, denied✔️");
 
     setClock(svm, BigInt(0)); //reset time, this is not necessary
   })
@@ -588,18 +643,27 @@ describe.only("aaas with litesvm", () => {
     const candidateAccountData = program.coder.accounts.decode<AaasAccounts["candidateAccount"]>(
       "candidateAccount", Buffer.from(svm.getAccount(testValues.candidate.account)?.data!)
     );
-    expect(candidateAccountData.acceptance).to.be.greaterThanOrEqual(30);
+    expect(candidateAccountData.acceptance.toNumber()).to.be.greaterThanOrEqual(30);
     const challengeAccount = program.coder.accounts.decode<AaasAccounts["challenge"]>(
       "challenge", Buffer.from(svm.getAccount(testValues.challenge.key)?.data!)
     );
     // console.log("challenge:", challengeAccount);
     // console.log("candidateAccount:", candidateAccountData);
     expect(challengeAccount.candidateCount).to.be.greaterThan(30);
-    expect(challengeAccount.winnerCount).to.be.greaterThan(0);
-    console.log("challenge account is all set for reward withdrawal ✔️");
+    expect(challengeAccount.candidateCount).to.be.equal(candidates.length);
 
     //set time to withdrawal
-    setClock(svm, BigInt(testValues.challenge.endTime.toNumber() + 87000));
+    setClock(svm, BigInt(testValues.challenge.endTime.add(testValues.challenge.validationPeriod).toNumber() + 600));
+
+    //finalize the winners with every candidate account
+    const finalizeResults = await finalizeChallenge(svm, testValues, program, candidates.map(([_, account]) => account));
+    finalizeResults.forEach(res => expect(res).to.be.instanceOf(TransactionMetadata));
+    const finalizedChallenge = program.coder.accounts.decode<AaasAccounts["challenge"]>(
+      "challenge", Buffer.from(svm.getAccount(testValues.challenge.key)?.data!)
+    );
+    expect(finalizedChallenge.finalized).to.be.true;
+    expect(finalizedChallenge.winnerCount).to.be.greaterThan(0);
+    console.log("challenge account is all set for reward withdrawal ✔️");
 
     //ata states before withdrawal
     const vaultInfo = AccountLayout.decode(Buffer.from(svm.getAccount(testValues.challenge.vault)?.data!));
@@ -608,12 +672,12 @@ describe.only("aaas with litesvm", () => {
     //withdraw during validation period, and it will failed
     const tx = await program.methods.withdrawReward().accounts({
       tokenProgram: TOKEN_PROGRAM_ID,
-      usdcMint: testValues.usdcMint.publicKey,
+      mint: testValues.usdcMint.publicKey,
       winner: testValues.candidate.payer.publicKey,
       //@ts-ignore
       winnerAccount: testValues.candidate.account,
       config: testValues.config.key,
-      service: testValues.service.key,
+      admin: testValues.admin.publicKey,
       challenge: testValues.challenge.key,
       treasury: testValues.treasury,
     }).transaction();
@@ -633,6 +697,31 @@ describe.only("aaas with litesvm", () => {
     expect(new BN(vaultInfo.amount.toString(10)).gt(new BN(vaultInfo2.amount.toString(10)))).to.be.true;
     expect(new BN(treasuryInfo2.amount.toString(10)).gt(new BN(treasuryInfo.amount.toString(10)))).to.be.true;
     console.log("Expectation✅ - token transfer are as expected");
+
+    const winnerAccountData = program.coder.accounts.decode<AaasAccounts["candidateAccount"]>(
+      "candidateAccount", Buffer.from(svm.getAccount(testValues.candidate.account)?.data!)
+    );
+    expect(winnerAccountData.rewarded).to.be.true;
+
+    //withdraw again, and it should fail
+    svm.expireBlockhash();
+    const againTx = await program.methods.withdrawReward().accounts({
+      tokenProgram: TOKEN_PROGRAM_ID,
+      mint: testValues.usdcMint.publicKey,
+      winner: testValues.candidate.payer.publicKey,
+      //@ts-ignore
+      winnerAccount: testValues.candidate.account,
+      config: testValues.config.key,
+      admin: testValues.admin.publicKey,
+      challenge: testValues.challenge.key,
+      treasury: testValues.treasury,
+    }).transaction();
+    againTx.recentBlockhash = svm.latestBlockhash();
+    againTx.sign(testValues.candidate.payer);
+    const againSim = svm.simulateTransaction(againTx);
+    expect(svm.sendTransaction(againTx)).to.be.instanceOf(FailedTransactionMetadata);
+    expect(againSim.meta().logs().some(log => log.includes("AlreadyRewarded"))).to.be.true;
+    console.log("withdraw reward twice, denied✔️");
   })
 
 })
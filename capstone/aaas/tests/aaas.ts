@@ -24,14 +24,9 @@ describe("aaas", () => {
       2
     ).accounts({
       tokenProgram: TOKEN_PROGRAM_ID,
-      usdcMint,
+      mint: usdcMint,
       admin: wallet?.publicKey
-    }).remainingAccounts(
-      [
-        { isSigner: true, isWritable: false, pubkey: multiSigners[0].publicKey },
-        { isSigner: true, isWritable: false, pubkey: multiSigners[1].publicKey }
-      ]
-    ).signers([wallet?.payer!, multiSigners[0], multiSigners[1]])
+    }).signers([wallet?.payer!])
       .rpc();
   });
 });
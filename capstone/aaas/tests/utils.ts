@@ -1,9 +1,10 @@
-import { BN, Program } from "@coral-xyz/anchor";
+import { BN, IdlAccounts, IdlTypes, Program } from "@coral-xyz/anchor";
 import { createAssociatedTokenAccountInstruction, createInitializeMintInstruction, createMintToInstruction, getAssociatedTokenAddressSync, MINT_SIZE, TOKEN_PROGRAM_ID } from "@solana/spl-token";
 import { Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram, Transaction } from "@solana/web3.js";
 import { FailedTransactionMetadata, LiteSVM, SimulatedTransactionInfo, TransactionMetadata } from "litesvm";
 import { Aaas } from "../target/types/aaas";
 import { expect } from "chai";
+import { createHash } from "crypto";
 
 export type ITestValues = {
     admin: Keypair;
@@ -17,13 +18,23 @@ export type ITestValues = {
         id: PublicKey;
         key: PublicKey;
         fee: number;
+        forfeitFee: number;
+        limits: IdlTypes<Aaas>["serviceLimits"];
     };
     challenge: {
         id: PublicKey;
         startTime: BN;
         endTime: BN;
         stakeAmnt: number;
+        validationPeriod: BN; //in seconds
         winningThreshold: number; //basis point
+        quorum: number; //basis point
+        incentives: IdlTypes<Aaas>["validatorIncentives"];
+        revealPeriod: BN; //in seconds, 0 for open voting
+        milestones: BN[]; //milestone end times, empty for a single proof challenge
+        minMilestones: number;
+        maxCandidates: number;
+        allowlistRoot: number[] | null; //merkle root of the allowed candidates, null for a public challenge
         proof: string;
         key: PublicKey;
         bump: number;
@@ -104,6 +115,14 @@ export const createValues = async (svm: LiteSVM, program: Program<Aaas>): Promis
         },
         service: {
             fee: 30, //in basis point
+            forfeitFee: 100, //in basis point
+            limits: {
+                minDuration: new BN(60 * 60), //in seconds
+                maxDuration: new BN(60 * 60 * 24 * 30),
+                minValidationPeriod: new BN(60 * 60),
+                maxValidationPeriod: new BN(60 * 60 * 24 * 3),
+                minReputation: new BN(0), //open to everyone
+            },
             id: serviceId,
             key: servicePda[0],
         },
@@ -115,7 +134,15 @@ export const createValues = async (svm: LiteSVM, program: Program<Aaas>): Promis
             startTime: new BN(svm.getClock().unixTimestamp).add(new BN(60 * 60 * 24)),
             proof: "",
             stakeAmnt: 500,
+            validationPeriod: new BN(60 * 60 * 24),
             winningThreshold: 8500,
+            quorum: 8500,
+            incentives: { reward: 0, minVotes: 0, minAgreement: 0, penalty: 0 }, //disabled
+            revealPeriod: new BN(0),
+            milestones: [],
+            minMilestones: 0,
+            maxCandidates: 100,
+            allowlistRoot: null,
             creator,
             vault
         },
@@ -201,13 +228,14 @@ export const joinChallengeWithNewCandidate = async (
     await initCandidateAta(testValues.usdcMint, candidate, svm, testValues.admin);
 
     //join the challenge and then later exit after the challenge started
-    const joinTx = await program.methods.joinChallenge()
+    const joinTx = await program.methods.joinChallenge(new BN(testValues.challenge.stakeAmnt), [])
         .accounts({
             tokenProgram: TOKEN_PROGRAM_ID,
-            usdcMint: testValues.usdcMint.publicKey,
+            mint: testValues.usdcMint.publicKey,
             candidate: candidate.publicKey,
             //@ts-ignore
             challenge: testValues.challenge.key, //just passing challenge is not enough to derive both challenge, and candidateAccount PDAs
+            service: testValues.service.key,
             candidateAccount, //this is needed as 
         }).transaction();
     joinTx.recentBlockhash = svm.latestBlockhash();
@@ -230,6 +258,12 @@ export const joinChallengeWithNewCandidate = async (
  * @param candidate 
  * @param candidateAccount 
  */
+/**
+ * sha256 of a proof artifact, as submitted and voted on
+ * @param artifact proof content
+ */
+export const proofHash = (artifact: string): number[] => Array.from(createHash("sha256").update(artifact).digest());
+
 export const submitProof = async (
     svm: LiteSVM,
     program: Program<Aaas>,
@@ -238,7 +272,7 @@ export const submitProof = async (
     candidateAccount: PublicKey
 ) => {
     const proof = "http://linkt/to/proof";
-    const tx = await program.methods.submitProof(proof)
+    const tx = await program.methods.submitProof(proofHash(proof), proof)
         .accounts({
             challenge,
             candidate: candidate.publicKey,
@@ -278,7 +312,8 @@ export const validateProof = async (
     testValues: ITestValues,
     validatorKp: Keypair,
     candidateAccount: PublicKey,
-    program: Program<Aaas>
+    program: Program<Aaas>,
+    vote: IdlTypes<Aaas>["vote"] = { approve: {} }
 ): Promise<[
     FailedTransactionMetadata | SimulatedTransactionInfo,
     FailedTransactionMetadata | TransactionMetadata
@@ -286,7 +321,10 @@ export const validateProof = async (
     const [validator, validatorAccount] = generateCandidate(svm, testValues.service.key, testValues.challenge.key, program.programId, validatorKp);
     const validation = PublicKey.findProgramAddressSync([Buffer.from("aaasValidation"), testValues.service.key.toBuffer(), testValues.challenge.key.toBuffer(), candidateAccount.toBuffer(), validator.publicKey.toBuffer()], program.programId)[0];
 
-    const tx = await program.methods.validateProof()
+    //the validator votes on the proof hash it reviewed
+    const { proofHash } = program.coder.accounts.decode<IdlAccounts<Aaas>["candidateAccount"]>(
+        "candidateAccount", Buffer.from(svm.getAccount(candidateAccount)?.data!));
+    const tx = await program.methods.validateProof(vote, proofHash)
         .accounts({
             validator: validator.publicKey,
             //@ts-ignore
@@ -303,3 +341,80 @@ export const validateProof = async (
 
     return [sim, res];
 }
+
+/**
+ * creates a proposal for an admin action with the first signer, and approves it with the next signers till threshold
+ * @param svm 
+ * @param testValues 
+ * @param program 
+ * @param action admin action encoded in the proposal
+ * @returns proposal key, and the proposer keypair
+ */
+export const proposeAndApprove = async (
+    svm: LiteSVM,
+    testValues: ITestValues,
+    program: Program<Aaas>,
+    action: IdlTypes<Aaas>["adminAction"],
+): Promise<[PublicKey, Keypair]> => {
+    const id = PublicKey.unique();
+    const proposal = PublicKey.findProgramAddressSync([Buffer.from("aaasProposal"), id.toBuffer()], program.programId)[0];
+    const [proposer, ...approvers] = testValues.config.signers.slice(0, testValues.config.threshold);
+
+    const createTx = await program.methods.createProposal(id, action)
+        .accounts({
+            proposer: proposer.publicKey,
+            //@ts-ignore
+            proposal,
+        }).transaction();
+    createTx.recentBlockhash = svm.latestBlockhash();
+    createTx.sign(proposer);
+    expect(svm.sendTransaction(createTx)).to.be.instanceOf(TransactionMetadata);
+
+    for (const approver of approvers) {
+        if (svm.getBalance(approver.publicKey) === null)
+            svm.airdrop(approver.publicKey, BigInt(LAMPORTS_PER_SOL));
+        const approveTx = await program.methods.approveProposal()
+            .accounts({
+                signer: approver.publicKey,
+                proposal,
+            }).transaction();
+        approveTx.recentBlockhash = svm.latestBlockhash();
+        approveTx.sign(approver);
+        expect(svm.sendTransaction(approveTx)).to.be.instanceOf(TransactionMetadata);
+    }
+    console.log("Proposal Approved✔️");
+
+    return [proposal, proposer];
+}
+
+/**
+ * finalizes the challenge in batches of candidate accounts, so the transaction stays within the size limit
+ * @param svm 
+ * @param testValues 
+ * @param program 
+ * @param candidateAccounts every candidate account that joined and didn't exit the challenge
+ * @returns send transaction result of each batch
+ */
+export const finalizeChallenge = async (
+    svm: LiteSVM,
+    testValues: ITestValues,
+    program: Program<Aaas>,
+    candidateAccounts: PublicKey[],
+): Promise<(FailedTransactionMetadata | TransactionMetadata)[]> => {
+    const cranker = testValues.admin;
+    const results: (FailedTransactionMetadata | TransactionMetadata)[] = [];
+    for (let i = 0; i < candidateAccounts.length; i += 10) {
+        const tx = await program.methods.finalizeChallenge()
+            .accounts({
+                cranker: cranker.publicKey,
+                challenge: testValues.challenge.key,
+            })
+            .remainingAccounts(candidateAccounts.slice(i, i + 10).map(pubkey => ({ pubkey, isSigner: false, isWritable: true })))
+            .transaction();
+        tx.recentBlockhash = svm.latestBlockhash();
+        tx.sign(cranker);
+        results.push(svm.sendTransaction(tx));
+    }
+
+    return results;
+}